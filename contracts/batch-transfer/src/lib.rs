@@ -7,8 +7,8 @@ mod validation;
 use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, Env, Vec};
 
 pub use crate::types::{
-    BatchBurnResult, BatchTransferResult, BurnRequest, BurnResult, DataKey, TransferEvents,
-    TransferRequest, TransferResult, MAX_BATCH_SIZE,
+    BatchBurnResult, BatchTransferPreview, BatchTransferResult, BurnRequest, BurnResult, DataKey,
+    TransferEvents, TransferMode, TransferRequest, TransferResult, MAX_BATCH_SIZE,
 };
 use crate::validation::{validate_address, validate_amount};
 
@@ -28,6 +28,8 @@ pub enum BatchTransferError {
     BatchTooLarge = 5,
     /// Invalid token contract
     InvalidToken = 6,
+    /// An item of an atomic batch would fail
+    AtomicBatchFailed = 7,
 }
 
 impl From<BatchTransferError> for soroban_sdk::Error {
@@ -58,24 +60,35 @@ impl BatchTransferContract {
     }
 
     /// Executes batch transfers of XLM to multiple recipients.
+    ///
+    /// Items that fail validation or exceed the remaining balance are
+    /// reported in the result while the rest are transferred.
     pub fn batch_transfer(
         env: Env,
         caller: Address,
         token: Address,
         transfers: Vec<TransferRequest>,
+    ) -> BatchTransferResult {
+        Self::batch_transfer_with_mode(env, caller, token, transfers, TransferMode::BestEffort)
+    }
+
+    /// Executes batch transfers in the given mode.
+    ///
+    /// In `Atomic` mode the batch reverts with `AtomicBatchFailed` if any
+    /// item would fail, so either every transfer happens or none does.
+    pub fn batch_transfer_with_mode(
+        env: Env,
+        caller: Address,
+        token: Address,
+        transfers: Vec<TransferRequest>,
+        mode: TransferMode,
     ) -> BatchTransferResult {
         // Verify authorization
         caller.require_auth();
         Self::require_admin(&env, &caller);
 
         // Validate batch size
-        let request_count = transfers.len();
-        if request_count == 0 {
-            panic_with_error!(&env, BatchTransferError::EmptyBatch);
-        }
-        if request_count > MAX_BATCH_SIZE {
-            panic_with_error!(&env, BatchTransferError::BatchTooLarge);
-        }
+        let request_count = Self::require_batch_size(&env, &transfers);
 
         // Get batch ID and increment
         let batch_id: u64 = env
@@ -85,108 +98,46 @@ impl BatchTransferContract {
             .unwrap_or(0)
             + 1;
 
+        // Create token client
+        let token_client = token::Client::new(&env, &token);
+
+        // Work out every item's outcome before moving any funds
+        let plan = Self::plan_transfers(&env, &token_client, &caller, &transfers);
+        if mode == TransferMode::Atomic && plan.would_fail > 0 {
+            panic_with_error!(&env, BatchTransferError::AtomicBatchFailed);
+        }
+
         // Emit batch started event
         TransferEvents::batch_started(&env, batch_id, request_count);
 
-        // Initialize result vectors
-        let mut results: Vec<TransferResult> = Vec::new(&env);
         let mut successful_count: u32 = 0;
         let mut failed_count: u32 = 0;
         let mut total_transferred: i128 = 0;
 
-        // Create token client
-        let token_client = token::Client::new(&env, &token);
-
-        // Get initial balance
-        let mut available_balance = token_client.balance(&caller);
-
-        // Calculate total needed for all valid transfers and validate upfront
-        let mut total_needed: i128 = 0;
-        let mut validated_requests: Vec<(TransferRequest, bool, u32)> = Vec::new(&env);
-
-        // First pass: Validate all requests and calculate total needed
-        for request in transfers.iter() {
-            let mut is_valid = true;
-            let mut error_code = 0u32;
-
-            // Validate recipient address
-            if validate_address(&env, &request.recipient).is_err() {
-                is_valid = false;
-                error_code = 0; // Invalid address
-            }
-            // Validate amount
-            else if validate_amount(request.amount).is_err() {
-                is_valid = false;
-                error_code = 1; // Invalid amount
-            }
-
-            if is_valid {
-                total_needed = total_needed
-                    .checked_add(request.amount)
-                    .unwrap_or(i128::MAX);
-            }
-
-            validated_requests.push_back((request.clone(), is_valid, error_code));
-        }
-
-        // Second pass: Process each request
-        for (request, is_valid, error_code) in validated_requests.iter() {
-            if !is_valid {
-                // Validation failed - record and continue
-                results.push_back(TransferResult::Failure(
-                    request.recipient.clone(),
-                    request.amount,
-                    error_code.clone(),
-                ));
-                failed_count += 1;
-                TransferEvents::transfer_failure(
-                    &env,
-                    batch_id,
-                    &request.recipient,
-                    request.amount,
-                    error_code.clone(),
-                );
-                continue;
-            }
-
-            // Check balance for this transfer
-            if available_balance < request.amount {
-                // Insufficient balance
-                results.push_back(TransferResult::Failure(
-                    request.recipient.clone(),
-                    request.amount,
-                    2, // Insufficient balance
-                ));
-                failed_count += 1;
-                TransferEvents::transfer_failure(
-                    &env,
-                    batch_id,
-                    &request.recipient,
-                    request.amount,
-                    2,
-                );
-                continue;
+        for result in plan.results.iter() {
+            match result {
+                TransferResult::Failure(recipient, amount, error_code) => {
+                    failed_count += 1;
+                    TransferEvents::transfer_failure(
+                        &env, batch_id, &recipient, amount, error_code,
+                    );
+                }
+                TransferResult::Success(recipient, amount) => {
+                    // Execute transfer
+                    // Note: After thorough validation, transfers should succeed.
+                    // If a transfer fails due to contract-level issues (authorization, etc.),
+                    // it will panic and revert the entire batch. This is acceptable as
+                    // we've validated all inputs and balances.
+                    token_client.transfer(&caller, &recipient, &amount);
+
+                    successful_count += 1;
+                    total_transferred = total_transferred
+                        .checked_add(amount)
+                        .unwrap_or(total_transferred);
+
+                    TransferEvents::transfer_success(&env, batch_id, &recipient, amount);
+                }
             }
-
-            // Execute transfer
-            // Note: After thorough validation, transfers should succeed.
-            // If a transfer fails due to contract-level issues (authorization, etc.),
-            // it will panic and revert the entire batch. This is acceptable as
-            // we've validated all inputs and balances.
-            token_client.transfer(&caller, &request.recipient, &request.amount);
-
-            // Transfer succeeded
-            available_balance -= request.amount;
-            results.push_back(TransferResult::Success(
-                request.recipient.clone(),
-                request.amount,
-            ));
-            successful_count += 1;
-            total_transferred = total_transferred
-                .checked_add(request.amount)
-                .unwrap_or(total_transferred);
-
-            TransferEvents::transfer_success(&env, batch_id, &request.recipient, request.amount);
         }
 
         // Update storage (batched at the end for efficiency)
@@ -234,10 +185,25 @@ impl BatchTransferContract {
             successful: successful_count,
             failed: failed_count,
             total_transferred,
-            results,
+            results: plan.results,
         }
     }
 
+    /// Reports what `batch_transfer` would do for `caller` without moving
+    /// funds, emitting events or updating statistics.
+    pub fn preview_batch_transfer(
+        env: Env,
+        caller: Address,
+        token: Address,
+        transfers: Vec<TransferRequest>,
+    ) -> BatchTransferPreview {
+        Self::require_admin(&env, &caller);
+        Self::require_batch_size(&env, &transfers);
+
+        let token_client = token::Client::new(&env, &token);
+        Self::plan_transfers(&env, &token_client, &caller, &transfers)
+    }
+
     pub fn batch_burn(
         env: Env,
         caller: Address,
@@ -247,13 +213,7 @@ impl BatchTransferContract {
         caller.require_auth();
         Self::require_admin(&env, &caller);
 
-        let request_count = burns.len();
-        if request_count == 0 {
-            panic_with_error!(&env, BatchTransferError::EmptyBatch);
-        }
-        if request_count > MAX_BATCH_SIZE {
-            panic_with_error!(&env, BatchTransferError::BatchTooLarge);
-        }
+        let request_count = Self::require_batch_size(&env, &burns);
 
         let batch_id: u64 = env
             .storage()
@@ -381,6 +341,75 @@ impl BatchTransferContract {
             .unwrap_or(0)
     }
 
+    // Internal helper to reject empty and oversized batches
+    fn require_batch_size<T>(env: &Env, requests: &Vec<T>) -> u32 {
+        let request_count = requests.len();
+        if request_count == 0 {
+            panic_with_error!(env, BatchTransferError::EmptyBatch);
+        }
+        if request_count > MAX_BATCH_SIZE {
+            panic_with_error!(env, BatchTransferError::BatchTooLarge);
+        }
+        request_count
+    }
+
+    // Internal helper that decides each transfer's outcome against the
+    // caller's current balance, in request order
+    fn plan_transfers(
+        env: &Env,
+        token_client: &token::Client,
+        caller: &Address,
+        transfers: &Vec<TransferRequest>,
+    ) -> BatchTransferPreview {
+        let available_balance = token_client.balance(caller);
+        let mut remaining_balance = available_balance;
+
+        let mut results: Vec<TransferResult> = Vec::new(env);
+        let mut would_succeed: u32 = 0;
+        let mut would_fail: u32 = 0;
+        let mut total_transferable: i128 = 0;
+
+        for request in transfers.iter() {
+            let error_code = if validate_address(env, &request.recipient).is_err() {
+                Some(0) // Invalid address
+            } else if validate_amount(request.amount).is_err() {
+                Some(1) // Invalid amount
+            } else if remaining_balance < request.amount {
+                Some(2) // Insufficient balance
+            } else {
+                None
+            };
+
+            match error_code {
+                Some(error_code) => {
+                    would_fail += 1;
+                    results.push_back(TransferResult::Failure(
+                        request.recipient,
+                        request.amount,
+                        error_code,
+                    ));
+                }
+                None => {
+                    would_succeed += 1;
+                    remaining_balance -= request.amount;
+                    total_transferable = total_transferable
+                        .checked_add(request.amount)
+                        .unwrap_or(total_transferable);
+                    results.push_back(TransferResult::Success(request.recipient, request.amount));
+                }
+            }
+        }
+
+        BatchTransferPreview {
+            total_requests: transfers.len(),
+            would_succeed,
+            would_fail,
+            total_transferable,
+            available_balance,
+            results,
+        }
+    }
+
     // Internal helper to verify admin
     fn require_admin(env: &Env, caller: &Address) {
         let admin: Address = env
@@ -3,7 +3,7 @@
 #![cfg(test)]
 
 use crate::{
-    BatchBurnResult, BatchTransferContract, BatchTransferContractClient, BurnRequest,
+    BatchBurnResult, BatchTransferContract, BatchTransferContractClient, BurnRequest, TransferMode,
     TransferRequest, TransferResult,
 };
 use soroban_sdk::{
@@ -546,3 +546,216 @@ fn test_batch_burn_unauthorized() {
     let unauthorized = Address::generate(&env);
     client.batch_burn(&unauthorized, &token, &burns);
 }
+
+#[test]
+fn test_atomic_batch_transfer_succeeds() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+
+    let recipient1 = Address::generate(&env);
+    let recipient2 = Address::generate(&env);
+
+    let token_admin_client = token::StellarAssetClient::new(&env, &token);
+    token_admin_client.mint(&admin, &30_000_000);
+
+    let mut transfers: Vec<TransferRequest> = Vec::new(&env);
+    transfers.push_back(create_transfer_request(
+        &env,
+        recipient1.clone(),
+        10_000_000,
+    ));
+    transfers.push_back(create_transfer_request(
+        &env,
+        recipient2.clone(),
+        20_000_000,
+    ));
+
+    let result = client.batch_transfer_with_mode(&admin, &token, &transfers, &TransferMode::Atomic);
+
+    assert_eq!(result.successful, 2);
+    assert_eq!(result.failed, 0);
+    assert_eq!(result.total_transferred, 30_000_000);
+    assert_eq!(token_client.balance(&recipient1), 10_000_000);
+    assert_eq!(token_client.balance(&recipient2), 20_000_000);
+    assert_eq!(client.get_total_batches(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #7)")]
+fn test_atomic_batch_transfer_rejects_invalid_amount() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+
+    let token_admin_client = token::StellarAssetClient::new(&env, &token);
+    token_admin_client.mint(&admin, &10_000_000);
+
+    let mut transfers: Vec<TransferRequest> = Vec::new(&env);
+    transfers.push_back(create_transfer_request(
+        &env,
+        Address::generate(&env),
+        10_000_000,
+    ));
+    transfers.push_back(create_transfer_request(&env, Address::generate(&env), 0));
+
+    client.batch_transfer_with_mode(&admin, &token, &transfers, &TransferMode::Atomic);
+}
+
+#[test]
+fn test_atomic_batch_transfer_moves_nothing_on_failure() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+
+    let recipient1 = Address::generate(&env);
+    let recipient2 = Address::generate(&env);
+
+    let token_admin_client = token::StellarAssetClient::new(&env, &token);
+    token_admin_client.mint(&admin, &15_000_000);
+
+    // The second item exceeds the balance left after the first
+    let mut transfers: Vec<TransferRequest> = Vec::new(&env);
+    transfers.push_back(create_transfer_request(
+        &env,
+        recipient1.clone(),
+        10_000_000,
+    ));
+    transfers.push_back(create_transfer_request(
+        &env,
+        recipient2.clone(),
+        10_000_000,
+    ));
+
+    let result =
+        client.try_batch_transfer_with_mode(&admin, &token, &transfers, &TransferMode::Atomic);
+    assert!(result.is_err());
+
+    assert_eq!(token_client.balance(&admin), 15_000_000);
+    assert_eq!(token_client.balance(&recipient1), 0);
+    assert_eq!(client.get_total_batches(), 0);
+    assert_eq!(client.get_total_transfers_processed(), 0);
+}
+
+#[test]
+fn test_best_effort_mode_matches_batch_transfer() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+
+    let recipient1 = Address::generate(&env);
+    let recipient2 = Address::generate(&env);
+
+    let token_admin_client = token::StellarAssetClient::new(&env, &token);
+    token_admin_client.mint(&admin, &15_000_000);
+
+    let mut transfers: Vec<TransferRequest> = Vec::new(&env);
+    transfers.push_back(create_transfer_request(
+        &env,
+        recipient1.clone(),
+        10_000_000,
+    ));
+    transfers.push_back(create_transfer_request(
+        &env,
+        recipient2.clone(),
+        10_000_000,
+    ));
+
+    let result =
+        client.batch_transfer_with_mode(&admin, &token, &transfers, &TransferMode::BestEffort);
+
+    assert_eq!(result.successful, 1);
+    assert_eq!(result.failed, 1);
+    assert_eq!(token_client.balance(&recipient1), 10_000_000);
+    assert_eq!(token_client.balance(&recipient2), 0);
+}
+
+#[test]
+fn test_preview_batch_transfer_reports_failures() {
+    let (env, admin, token, token_client, client) = setup_test_env();
+
+    let recipient1 = Address::generate(&env);
+    let recipient2 = Address::generate(&env);
+    let recipient3 = Address::generate(&env);
+
+    let token_admin_client = token::StellarAssetClient::new(&env, &token);
+    token_admin_client.mint(&admin, &25_000_000);
+
+    let mut transfers: Vec<TransferRequest> = Vec::new(&env);
+    transfers.push_back(create_transfer_request(
+        &env,
+        recipient1.clone(),
+        20_000_000,
+    ));
+    transfers.push_back(create_transfer_request(&env, recipient2.clone(), -5));
+    transfers.push_back(create_transfer_request(
+        &env,
+        recipient3.clone(),
+        10_000_000,
+    ));
+
+    let preview = client.preview_batch_transfer(&admin, &token, &transfers);
+
+    assert_eq!(preview.total_requests, 3);
+    assert_eq!(preview.would_succeed, 1);
+    assert_eq!(preview.would_fail, 2);
+    assert_eq!(preview.total_transferable, 20_000_000);
+    assert_eq!(preview.available_balance, 25_000_000);
+    assert_eq!(
+        preview.results.get(0).unwrap(),
+        TransferResult::Success(recipient1, 20_000_000)
+    );
+    assert_eq!(
+        preview.results.get(1).unwrap(),
+        TransferResult::Failure(recipient2, -5, 1)
+    );
+    assert_eq!(
+        preview.results.get(2).unwrap(),
+        TransferResult::Failure(recipient3.clone(), 10_000_000, 2)
+    );
+
+    // Nothing moved and no batch was recorded
+    assert_eq!(token_client.balance(&admin), 25_000_000);
+    assert_eq!(token_client.balance(&recipient3), 0);
+    assert_eq!(client.get_total_batches(), 0);
+}
+
+#[test]
+fn test_preview_matches_execution() {
+    let (env, admin, token, _token_client, client) = setup_test_env();
+
+    let token_admin_client = token::StellarAssetClient::new(&env, &token);
+    token_admin_client.mint(&admin, &15_000_000);
+
+    let mut transfers: Vec<TransferRequest> = Vec::new(&env);
+    transfers.push_back(create_transfer_request(
+        &env,
+        Address::generate(&env),
+        10_000_000,
+    ));
+    transfers.push_back(create_transfer_request(
+        &env,
+        Address::generate(&env),
+        10_000_000,
+    ));
+    transfers.push_back(create_transfer_request(
+        &env,
+        Address::generate(&env),
+        5_000_000,
+    ));
+
+    let preview = client.preview_batch_transfer(&admin, &token, &transfers);
+    let result = client.batch_transfer(&admin, &token, &transfers);
+
+    assert_eq!(preview.would_succeed, result.successful);
+    assert_eq!(preview.would_fail, result.failed);
+    assert_eq!(preview.total_transferable, result.total_transferred);
+    assert_eq!(preview.results, result.results);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_preview_batch_transfer_unauthorized() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+
+    let mut transfers: Vec<TransferRequest> = Vec::new(&env);
+    transfers.push_back(create_transfer_request(
+        &env,
+        Address::generate(&env),
+        10_000_000,
+    ));
+
+    client.preview_batch_transfer(&Address::generate(&env), &token, &transfers);
+}
@@ -16,7 +16,17 @@ pub struct BurnRequest {
     pub amount: i128,
 }
 
-#[derive(Clone, Debug)]
+/// How a batch transfer handles items that cannot be transferred.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum TransferMode {
+    /// Transfer every valid item and report failures per item.
+    BestEffort,
+    /// Revert the whole batch if any item would fail.
+    Atomic,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub enum TransferResult {
     Success(Address, i128),
@@ -40,6 +50,20 @@ pub struct BatchTransferResult {
     pub results: Vec<TransferResult>,
 }
 
+/// Outcome of a batch transfer dry run. No funds move.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct BatchTransferPreview {
+    pub total_requests: u32,
+    pub would_succeed: u32,
+    pub would_fail: u32,
+    /// Sum of the items that would succeed.
+    pub total_transferable: i128,
+    /// Caller balance the preview was computed against.
+    pub available_balance: i128,
+    pub results: Vec<TransferResult>,
+}
+
 #[derive(Clone, Debug)]
 #[contracttype]
 pub struct BatchBurnResult {
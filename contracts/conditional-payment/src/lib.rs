@@ -0,0 +1,1305 @@
+//! Conditional Payments Contract
+//!
+//! Enables payments that execute only when predefined on-chain conditions
+//! are satisfied. A payer locks funds into a payment order and specifies
+//! one or more conditions; any caller may attempt execution, but the
+//! transfer only proceeds when every condition is met.
+//!
+//! # Supported condition types
+//!
+//! | Variant              | Triggers when…                                          |
+//! |----------------------|---------------------------------------------------------|
+//! | `TimeAfter`          | `now >= threshold_timestamp`                            |
+//! | `TimeBefore`         | `now < threshold_timestamp`                             |
+//! | `BalanceAbove`       | token balance of `watch_address >= threshold_amount`    |
+//! | `BalanceBelow`       | token balance of `watch_address < threshold_amount`     |
+//! | `MultiSigApproved`   | at least `required` of the listed signers have approved |
+//! | `PriceAbove`         | fresh oracle price of `asset >= threshold`              |
+//! | `PriceBelow`         | fresh oracle price of `asset < threshold`               |
+//! | `ContractPredicate`  | allowed `contract.function(args)` returns `true`        |
+//! | `PaymentSettled`     | another payment or escrow reached a terminal state      |
+//!
+//! # Split settlement
+//!
+//! `create_split_payment` pays several recipients on execution. Each
+//! `SplitLeg` takes a fixed amount or a share in basis points of the total;
+//! whatever the legs leave over, including bps rounding dust, goes to the
+//! payment's `recipient`. All legs are paid in the same call, so either
+//! every leg settles or none does.
+//!
+//! Price conditions read a SEP-40 style price feed via `lastprice` and fail
+//! when the latest price is older than `max_age` seconds. External calls
+//! made while evaluating conditions never abort evaluation: a failing or
+//! misbehaving contract simply makes its condition fail.
+//!
+//! This contract is the direct invoker of a predicate call, so a callee
+//! that trusts its caller (a token holding the escrow, for instance) would
+//! act on the contract's behalf. Predicates may therefore only target
+//! contracts the admin has allowed with `set_predicate_contract`, and only
+//! contracts whose functions cannot move funds or change state on behalf
+//! of the caller should be allowed. A contract that is disallowed later
+//! makes its existing predicates fail without being called.
+//!
+//! Conditions are combined into a boolean tree of `ConditionNode`s. Leaves
+//! wrap a `Condition`; `AllOf`, `AnyOf`, `Not` and `AtLeastN` nodes combine
+//! earlier nodes by index, and the last node is the root. `create_payment`
+//! accepts a flat list and wraps it in a single `AllOf` root, so all of its
+//! conditions must pass for the payment to execute.
+//!
+//! For example, "(after T AND multisig approved) OR (arbiter approved)":
+//!
+//! ```text
+//! 0: Leaf(TimeAfter(T))
+//! 1: Leaf(MultiSigApproved(..))
+//! 2: AllOf([0, 1])
+//! 3: Leaf(MultiSigApproved({ signers: [arbiter], required: 1 }))
+//! 4: AnyOf([2, 3])        <- root
+//! ```
+//!
+//! # Lifecycle
+//!
+//! 1. **`initialize`** — one-time setup with an admin, who may then allow
+//!    contracts for `ContractPredicate` via `set_predicate_contract`.
+//! 2. **`create_payment`** / **`create_payment_with_tree`** — payer locks
+//!    funds and attaches conditions.
+//! 3. **`approve`** — a signer records their approval for a MultiSig condition.
+//! 4. **`execute_payment`** — anyone triggers execution; reverts unless the
+//!    root node passes.
+//! 5. **`cancel_payment`** — payer reclaims funds and voids the order.
+//!
+//! # Security properties
+//!
+//! - `require_auth` is the first statement in every mutating entry point.
+//! - Funds are transferred into the contract on `create_payment` (escrow
+//!   model) so execution can never fail for insufficient balance.
+//! - State is written before the outbound token transfer
+//!   (checks-effects-interactions).
+//! - Duplicate approvals are rejected via a typed stamp key.
+//! - Cancelled and executed payments cannot be re-executed.
+//! - Predicate calls only reach contracts the admin has allowed.
+//! - All arithmetic uses `checked_*`; overflow surfaces as a typed error.
+//! - TTL is bumped on every persistent-storage access.
+
+#![no_std]
+
+use soroban_sdk::{
+    contract, contractclient, contracterror, contractimpl, contracttype, panic_with_error,
+    symbol_short, token, Address, Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec,
+};
+
+// ── Constants ────────────────────────────────────────────────────────────────
+
+/// Ledger TTL bump for persistent payment records (~2 years).
+const PERSISTENT_TTL_BUMP: u32 = 12_614_400;
+
+/// Maximum number of conditions allowed per payment.
+pub const MAX_CONDITIONS: usize = 8;
+
+/// Maximum number of nodes in a condition tree, including combinators.
+pub const MAX_CONDITION_NODES: usize = 24;
+
+/// Maximum depth of a condition tree; a single leaf has depth 1.
+pub const MAX_CONDITION_DEPTH: u32 = 5;
+
+/// Maximum number of signers allowed in a MultiSig condition.
+pub const MAX_SIGNERS: usize = 10;
+
+/// Maximum number of split legs per payment, excluding the remainder.
+pub const MAX_SPLIT_LEGS: usize = 10;
+
+/// Basis points in 100%.
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+// ── Storage keys ─────────────────────────────────────────────────────────────
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    /// Admin address (instance storage).
+    Admin,
+    /// Auto-incrementing payment ID counter (instance storage).
+    NextPaymentId,
+    /// Per-payment record (persistent storage).
+    Payment(u64),
+    /// MultiSig approval stamp: `(payment_id, signer)` → `bool`.
+    ApprovalStamp(u64, Address),
+    /// Contract allowed as a `ContractPredicate` target → `bool`.
+    PredicateContract(Address),
+}
+
+// ── Condition types ───────────────────────────────────────────────────────────
+
+/// A single evaluatable condition attached to a payment.
+#[derive(Clone)]
+#[contracttype]
+pub enum Condition {
+    /// Payment executes only after this ledger timestamp.
+    TimeAfter(u64),
+
+    /// Payment executes only before this ledger timestamp (deadline).
+    TimeBefore(u64),
+
+    /// Balance of `watch_address` in `token` must be >= `threshold`.
+    BalanceAbove(BalanceCondition),
+
+    /// Balance of `watch_address` in `token` must be < `threshold`.
+    BalanceBelow(BalanceCondition),
+
+    /// At least `required` of `signers` must have called `approve`.
+    MultiSigApproved(MultiSigCondition),
+
+    /// Latest `oracle` price of `asset` must be >= `threshold` and no older
+    /// than `max_age` seconds.
+    PriceAbove(PriceCondition),
+
+    /// Latest `oracle` price of `asset` must be < `threshold` and no older
+    /// than `max_age` seconds.
+    PriceBelow(PriceCondition),
+
+    /// `contract.function(args)` must return `true`.
+    ContractPredicate(PredicateCondition),
+
+    /// `source` must have reached a terminal state. With `require_success`
+    /// only an executed payment or released escrow counts.
+    PaymentSettled(SettlementCondition),
+}
+
+/// Parameters of a `BalanceAbove` or `BalanceBelow` condition.
+#[derive(Clone)]
+#[contracttype]
+pub struct BalanceCondition {
+    pub token: Address,
+    pub watch_address: Address,
+    pub threshold: i128,
+}
+
+/// Parameters of a `MultiSigApproved` condition.
+#[derive(Clone)]
+#[contracttype]
+pub struct MultiSigCondition {
+    pub signers: Vec<Address>,
+    pub required: u32,
+}
+
+/// Parameters of a `PriceAbove` or `PriceBelow` condition.
+#[derive(Clone)]
+#[contracttype]
+pub struct PriceCondition {
+    /// SEP-40 price feed contract.
+    pub oracle: Address,
+    pub asset: PriceAsset,
+    pub threshold: i128,
+    /// Maximum age of the latest price, in seconds.
+    pub max_age: u64,
+}
+
+/// Parameters of a `ContractPredicate` condition.
+#[derive(Clone)]
+#[contracttype]
+pub struct PredicateCondition {
+    pub contract: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
+
+/// Parameters of a `PaymentSettled` condition.
+#[derive(Clone)]
+#[contracttype]
+pub struct SettlementCondition {
+    pub source: SettlementSource,
+    pub require_success: bool,
+}
+
+/// A node of a condition tree.
+///
+/// Combinators refer to their children by index into the payment's node
+/// list. Children must precede their parent and belong to exactly one
+/// parent; the last node is the root.
+#[derive(Clone)]
+#[contracttype]
+pub enum ConditionNode {
+    /// A single on-chain condition.
+    Leaf(Condition),
+    /// Passes when every child passes.
+    AllOf(Vec<u32>),
+    /// Passes when at least one child passes.
+    AnyOf(Vec<u32>),
+    /// Passes when the child fails.
+    Not(u32),
+    /// Passes when at least `n` of the children pass.
+    AtLeastN(u32, Vec<u32>),
+}
+
+/// Asset identifier used by SEP-40 price feeds.
+#[derive(Clone)]
+#[contracttype]
+pub enum PriceAsset {
+    /// A Stellar asset identified by its token contract.
+    Stellar(Address),
+    /// Any other asset identified by its ticker.
+    Other(Symbol),
+}
+
+/// Price record returned by a SEP-40 price feed.
+#[derive(Clone)]
+#[contracttype]
+pub struct PriceData {
+    pub price: i128,
+    /// Ledger timestamp at which the price was recorded.
+    pub timestamp: u64,
+}
+
+/// The subset of the SEP-40 price feed interface used by price conditions.
+#[contractclient(name = "PriceFeedClient")]
+pub trait PriceFeed {
+    fn lastprice(env: Env, asset: PriceAsset) -> Option<PriceData>;
+}
+
+/// Escrow lifecycle states as exposed by the escrow contract's `get_escrow`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum EscrowStatus {
+    Active,
+    Released,
+    Reversed,
+    Disputed,
+    Resolved,
+}
+
+/// What a `PaymentSettled` condition waits on.
+#[derive(Clone)]
+#[contracttype]
+pub enum SettlementSource {
+    /// An earlier payment in this contract.
+    ConditionalPayment(u64),
+    /// An escrow held by an escrow contract.
+    Escrow(Address, u64),
+}
+
+/// How much of a payment one split leg receives.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum SplitShare {
+    /// A fixed token amount.
+    Fixed(i128),
+    /// A share of the payment amount in basis points, rounded down.
+    Bps(u32),
+}
+
+/// One recipient of a split payment.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct SplitLeg {
+    pub recipient: Address,
+    pub share: SplitShare,
+}
+
+// ── Payment status ────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum PaymentStatus {
+    /// Awaiting condition fulfilment.
+    Pending = 0,
+    /// All conditions met and funds transferred.
+    Executed = 1,
+    /// Cancelled by the payer; funds returned.
+    Cancelled = 2,
+}
+
+// ── Payment record ────────────────────────────────────────────────────────────
+
+#[derive(Clone)]
+#[contracttype]
+pub struct ConditionalPayment {
+    /// Address that created and funded this payment.
+    pub payer: Address,
+    /// Destination address for the funds on execution. For split payments
+    /// this is the remainder recipient.
+    pub recipient: Address,
+    /// Token contract address.
+    pub token: Address,
+    /// Amount locked in escrow (stroops).
+    pub amount: i128,
+    /// Condition tree; execution proceeds when the last (root) node passes.
+    pub conditions: Vec<ConditionNode>,
+    /// Split legs paid before the remainder (empty for single-recipient payments).
+    pub splits: Vec<SplitLeg>,
+    /// Current lifecycle status.
+    pub status: PaymentStatus,
+    /// Ledger timestamp when this payment was created.
+    pub created_at: u64,
+    /// Ledger timestamp of execution or cancellation (0 if still pending).
+    pub settled_at: u64,
+}
+
+// ── Errors ────────────────────────────────────────────────────────────────────
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum ConditionalPaymentError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    Unauthorized = 3,
+    InvalidAmount = 4,
+    PaymentNotFound = 5,
+    /// Payment is not in `Pending` status.
+    PaymentNotPending = 6,
+    /// One or more conditions are not yet satisfied.
+    ConditionNotMet = 7,
+    /// The caller is not a valid signer for this payment's MultiSig.
+    NotASigner = 8,
+    /// This signer has already approved.
+    AlreadyApproved = 9,
+    /// Too many conditions supplied.
+    TooManyConditions = 10,
+    /// Too many signers in a MultiSig condition.
+    TooManySigners = 11,
+    /// `required` approvals exceeds number of signers.
+    InvalidThreshold = 12,
+    /// Payer and recipient are the same address.
+    SamePayerRecipient = 13,
+    /// Arithmetic overflow.
+    Overflow = 14,
+    /// A condition parameter is logically invalid (e.g. zero threshold).
+    InvalidCondition = 15,
+    /// A combinator references a missing, later or already-used node, or
+    /// a node is not reachable from the root.
+    InvalidConditionTree = 16,
+    /// The condition tree is deeper than `MAX_CONDITION_DEPTH`.
+    ConditionTreeTooDeep = 17,
+    /// Split legs are empty, too many, non-positive, or exceed the amount.
+    InvalidSplit = 18,
+    /// A `ContractPredicate` targets a contract the admin has not allowed.
+    PredicateNotAllowed = 19,
+}
+
+// ── Events ────────────────────────────────────────────────────────────────────
+
+pub struct ConditionalPaymentEvents;
+
+impl ConditionalPaymentEvents {
+    /// Emitted when a new conditional payment is created.
+    /// Payload: `(payer, payment_id, recipient, token, amount, timestamp)`
+    pub fn payment_created(
+        env: &Env,
+        payer: &Address,
+        payment_id: u64,
+        recipient: &Address,
+        token: &Address,
+        amount: i128,
+    ) {
+        env.events().publish(
+            (symbol_short!("condpay"), symbol_short!("created")),
+            (
+                payer.clone(),
+                payment_id,
+                recipient.clone(),
+                token.clone(),
+                amount,
+                env.ledger().timestamp(),
+            ),
+        );
+    }
+
+    /// Emitted when all conditions are met and the payment is executed.
+    /// Payload: `(payment_id, payer, recipient, amount, timestamp)`
+    pub fn payment_executed(
+        env: &Env,
+        payment_id: u64,
+        payer: &Address,
+        recipient: &Address,
+        amount: i128,
+    ) {
+        env.events().publish(
+            (symbol_short!("condpay"), symbol_short!("executed")),
+            (
+                payment_id,
+                payer.clone(),
+                recipient.clone(),
+                amount,
+                env.ledger().timestamp(),
+            ),
+        );
+    }
+
+    /// Emitted for each leg paid when a split payment executes, including
+    /// the remainder leg (index `splits.len()`).
+    /// Payload: `(payment_id, leg_index, recipient, amount, timestamp)`
+    pub fn split_leg_paid(
+        env: &Env,
+        payment_id: u64,
+        leg_index: u32,
+        recipient: &Address,
+        amount: i128,
+    ) {
+        env.events().publish(
+            (symbol_short!("condpay"), symbol_short!("leg_paid")),
+            (
+                payment_id,
+                leg_index,
+                recipient.clone(),
+                amount,
+                env.ledger().timestamp(),
+            ),
+        );
+    }
+
+    /// Emitted when a payment is cancelled and funds returned to payer.
+    /// Payload: `(payment_id, payer, amount, timestamp)`
+    pub fn payment_cancelled(env: &Env, payment_id: u64, payer: &Address, amount: i128) {
+        env.events().publish(
+            (symbol_short!("condpay"), symbol_short!("cancel")),
+            (payment_id, payer.clone(), amount, env.ledger().timestamp()),
+        );
+    }
+
+    /// Emitted when a signer approves a MultiSig condition.
+    /// Payload: `(payment_id, signer, timestamp)`
+    pub fn approval_recorded(env: &Env, payment_id: u64, signer: &Address) {
+        env.events().publish(
+            (symbol_short!("condpay"), symbol_short!("approved")),
+            (payment_id, signer.clone(), env.ledger().timestamp()),
+        );
+    }
+
+    /// Emitted when the admin allows or disallows a predicate contract.
+    /// Payload: `(contract, allowed)`
+    pub fn predicate_set(env: &Env, contract: &Address, allowed: bool) {
+        env.events().publish(
+            (symbol_short!("condpay"), symbol_short!("pred_set")),
+            (contract.clone(), allowed),
+        );
+    }
+
+    /// Emitted when the root condition fails during an execution attempt.
+    /// Payload: `(payment_id, node_index, timestamp)`
+    pub fn condition_failed(env: &Env, payment_id: u64, node_index: u32) {
+        env.events().publish(
+            (symbol_short!("condpay"), symbol_short!("cond_fail")),
+            (payment_id, node_index, env.ledger().timestamp()),
+        );
+    }
+}
+
+// ── Internal helpers ──────────────────────────────────────────────────────────
+
+impl ConditionalPaymentsContract {
+    fn require_initialized(env: &Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic_with_error!(env, ConditionalPaymentError::NotInitialized))
+    }
+
+    fn next_payment_id(env: &Env) -> u64 {
+        let id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextPaymentId)
+            .unwrap_or(0u64);
+        let next = id
+            .checked_add(1)
+            .unwrap_or_else(|| panic_with_error!(env, ConditionalPaymentError::Overflow));
+        env.storage().instance().set(&DataKey::NextPaymentId, &next);
+        id
+    }
+
+    fn load_payment(env: &Env, payment_id: u64) -> ConditionalPayment {
+        let key = DataKey::Payment(payment_id);
+        let payment: ConditionalPayment =
+            env.storage().persistent().get(&key).unwrap_or_else(|| {
+                panic_with_error!(env, ConditionalPaymentError::PaymentNotFound)
+            });
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_TTL_BUMP, PERSISTENT_TTL_BUMP);
+        payment
+    }
+
+    fn predicate_allowed(env: &Env, contract: &Address) -> bool {
+        let key = DataKey::PredicateContract(contract.clone());
+        let allowed = env.storage().persistent().has(&key);
+        if allowed {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, PERSISTENT_TTL_BUMP, PERSISTENT_TTL_BUMP);
+        }
+        allowed
+    }
+
+    fn save_payment(env: &Env, payment_id: u64, payment: &ConditionalPayment) {
+        let key = DataKey::Payment(payment_id);
+        env.storage().persistent().set(&key, payment);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_TTL_BUMP, PERSISTENT_TTL_BUMP);
+    }
+
+    /// Validate, store and escrow a new payment. The caller has already
+    /// checked the payer's authorization.
+    fn create_with_nodes(
+        env: &Env,
+        payer: Address,
+        recipient: Address,
+        token: Address,
+        amount: i128,
+        conditions: Vec<ConditionNode>,
+        splits: Vec<SplitLeg>,
+    ) -> u64 {
+        Self::require_initialized(env);
+
+        if amount <= 0 {
+            panic_with_error!(env, ConditionalPaymentError::InvalidAmount);
+        }
+        if payer == recipient {
+            panic_with_error!(env, ConditionalPaymentError::SamePayerRecipient);
+        }
+
+        // Validate the whole tree and the split before writing state.
+        Self::validate_tree(env, &conditions);
+        if !splits.is_empty() {
+            Self::split_amounts(env, &payer, amount, &splits);
+        }
+
+        let payment_id = Self::next_payment_id(env);
+
+        let payment = ConditionalPayment {
+            payer: payer.clone(),
+            recipient: recipient.clone(),
+            token: token.clone(),
+            amount,
+            conditions,
+            splits,
+            status: PaymentStatus::Pending,
+            created_at: env.ledger().timestamp(),
+            settled_at: 0,
+        };
+
+        Self::save_payment(env, payment_id, &payment);
+
+        // Escrow: pull funds from payer into this contract now.
+        token::Client::new(env, &token).transfer_from(
+            &env.current_contract_address(),
+            &payer,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        ConditionalPaymentEvents::payment_created(
+            env, &payer, payment_id, &recipient, &token, amount,
+        );
+
+        payment_id
+    }
+
+    /// Resolve each split leg to a token amount, validating the split.
+    ///
+    /// Returns one amount per leg followed by the remainder.
+    fn split_amounts(
+        env: &Env,
+        payer: &Address,
+        amount: i128,
+        splits: &Vec<SplitLeg>,
+    ) -> Vec<i128> {
+        if splits.is_empty() || splits.len() > MAX_SPLIT_LEGS as u32 {
+            panic_with_error!(env, ConditionalPaymentError::InvalidSplit);
+        }
+
+        let mut amounts = Vec::new(env);
+        let mut allocated: i128 = 0;
+        for leg in splits.iter() {
+            if leg.recipient == *payer {
+                panic_with_error!(env, ConditionalPaymentError::SamePayerRecipient);
+            }
+            let leg_amount = match leg.share {
+                SplitShare::Fixed(fixed) => fixed,
+                SplitShare::Bps(bps) => {
+                    if bps > BPS_DENOMINATOR {
+                        panic_with_error!(env, ConditionalPaymentError::InvalidSplit);
+                    }
+                    amount.checked_mul(bps as i128).unwrap_or_else(|| {
+                        panic_with_error!(env, ConditionalPaymentError::Overflow)
+                    }) / BPS_DENOMINATOR as i128
+                }
+            };
+            if leg_amount <= 0 {
+                panic_with_error!(env, ConditionalPaymentError::InvalidSplit);
+            }
+            allocated = allocated
+                .checked_add(leg_amount)
+                .unwrap_or_else(|| panic_with_error!(env, ConditionalPaymentError::Overflow));
+            amounts.push_back(leg_amount);
+        }
+
+        if allocated > amount {
+            panic_with_error!(env, ConditionalPaymentError::InvalidSplit);
+        }
+        amounts.push_back(amount - allocated);
+        amounts
+    }
+
+    /// Validate a single condition's internal parameters at creation time.
+    fn validate_condition(env: &Env, condition: &Condition) {
+        match condition {
+            Condition::TimeAfter(ts) => {
+                // ts == 0 would always be true — disallow as it is meaningless.
+                if *ts == 0 {
+                    panic_with_error!(env, ConditionalPaymentError::InvalidCondition);
+                }
+            }
+            Condition::TimeBefore(ts) => {
+                if *ts == 0 {
+                    panic_with_error!(env, ConditionalPaymentError::InvalidCondition);
+                }
+            }
+            Condition::BalanceAbove(balance) | Condition::BalanceBelow(balance) => {
+                // Non-positive thresholds are always trivially true or false.
+                if balance.threshold <= 0 {
+                    panic_with_error!(env, ConditionalPaymentError::InvalidCondition);
+                }
+            }
+            Condition::MultiSigApproved(multisig) => {
+                let n = multisig.signers.len();
+                if n == 0 || n > MAX_SIGNERS as u32 {
+                    panic_with_error!(env, ConditionalPaymentError::TooManySigners);
+                }
+                if multisig.required == 0 || multisig.required > n {
+                    panic_with_error!(env, ConditionalPaymentError::InvalidThreshold);
+                }
+            }
+            Condition::PriceAbove(price) | Condition::PriceBelow(price) => {
+                // A zero max age would require a price from this very ledger.
+                if price.threshold <= 0 || price.max_age == 0 {
+                    panic_with_error!(env, ConditionalPaymentError::InvalidCondition);
+                }
+            }
+            Condition::ContractPredicate(predicate) => {
+                // Calling back into this contract would be rejected as re-entry.
+                if predicate.contract == env.current_contract_address() {
+                    panic_with_error!(env, ConditionalPaymentError::InvalidCondition);
+                }
+                if !Self::predicate_allowed(env, &predicate.contract) {
+                    panic_with_error!(env, ConditionalPaymentError::PredicateNotAllowed);
+                }
+            }
+            Condition::PaymentSettled(settlement) => {
+                // Only earlier payments may be referenced, which rules out cycles.
+                if let SettlementSource::ConditionalPayment(id) = settlement.source {
+                    if !env.storage().persistent().has(&DataKey::Payment(id)) {
+                        panic_with_error!(env, ConditionalPaymentError::InvalidCondition);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Validate the shape, size and depth of a condition tree and the
+    /// parameters of every leaf.
+    fn validate_tree(env: &Env, nodes: &Vec<ConditionNode>) {
+        let count = nodes.len();
+        if count == 0 || count > MAX_CONDITION_NODES as u32 {
+            panic_with_error!(env, ConditionalPaymentError::TooManyConditions);
+        }
+
+        let mut referenced: Vec<bool> = Vec::new(env);
+        let mut depths: Vec<u32> = Vec::new(env);
+        for index in 0..count {
+            let node = nodes.get_unchecked(index);
+            let children = match &node {
+                ConditionNode::Leaf(condition) => {
+                    Self::validate_condition(env, condition);
+                    Vec::new(env)
+                }
+                ConditionNode::AllOf(children) | ConditionNode::AnyOf(children) => {
+                    if children.is_empty() {
+                        panic_with_error!(env, ConditionalPaymentError::InvalidConditionTree);
+                    }
+                    children.clone()
+                }
+                ConditionNode::Not(child) => Vec::from_array(env, [*child]),
+                ConditionNode::AtLeastN(n, children) => {
+                    if *n == 0 || *n > children.len() {
+                        panic_with_error!(env, ConditionalPaymentError::InvalidThreshold);
+                    }
+                    children.clone()
+                }
+            };
+
+            let mut depth: u32 = 1;
+            for child in children.iter() {
+                // Children precede their parent and have a single parent.
+                if child >= index || referenced.get_unchecked(child) {
+                    panic_with_error!(env, ConditionalPaymentError::InvalidConditionTree);
+                }
+                referenced.set(child, true);
+                depth = depth.max(depths.get_unchecked(child) + 1);
+            }
+            if depth > MAX_CONDITION_DEPTH {
+                panic_with_error!(env, ConditionalPaymentError::ConditionTreeTooDeep);
+            }
+
+            referenced.push_back(false);
+            depths.push_back(depth);
+        }
+
+        // Every node except the root must hang off the tree.
+        for index in 0..count - 1 {
+            if !referenced.get_unchecked(index) {
+                panic_with_error!(env, ConditionalPaymentError::InvalidConditionTree);
+            }
+        }
+    }
+
+    /// Evaluate every node of a condition tree.
+    ///
+    /// Returns one result per node, in node order; the last entry is the
+    /// outcome of the whole tree. Nothing is short-circuited so the trace
+    /// explains every branch.
+    fn evaluate_tree(env: &Env, payment_id: u64, nodes: &Vec<ConditionNode>) -> Vec<bool> {
+        let mut trace: Vec<bool> = Vec::new(env);
+        for node in nodes.iter() {
+            let passed = match node {
+                ConditionNode::Leaf(condition) => {
+                    Self::evaluate_condition(env, payment_id, &condition)
+                }
+                ConditionNode::AllOf(children) => {
+                    children.iter().all(|child| trace.get_unchecked(child))
+                }
+                ConditionNode::AnyOf(children) => {
+                    children.iter().any(|child| trace.get_unchecked(child))
+                }
+                ConditionNode::Not(child) => !trace.get_unchecked(child),
+                ConditionNode::AtLeastN(n, children) => {
+                    let passing = children
+                        .iter()
+                        .filter(|child| trace.get_unchecked(*child))
+                        .count() as u32;
+                    passing >= n
+                }
+            };
+            trace.push_back(passed);
+        }
+        trace
+    }
+
+    /// Returns the `MultiSigApproved` leaves of a condition tree.
+    fn multisig_signers(env: &Env, nodes: &Vec<ConditionNode>) -> Vec<Vec<Address>> {
+        let mut groups = Vec::new(env);
+        for node in nodes.iter() {
+            if let ConditionNode::Leaf(Condition::MultiSigApproved(multisig)) = node {
+                groups.push_back(multisig.signers);
+            }
+        }
+        groups
+    }
+
+    /// Evaluate a single condition against current on-chain state.
+    ///
+    /// Returns `true` if the condition passes, `false` otherwise.
+    /// Does NOT panic — callers decide whether to abort or emit an event.
+    fn evaluate_condition(env: &Env, payment_id: u64, condition: &Condition) -> bool {
+        match condition {
+            Condition::TimeAfter(threshold) => env.ledger().timestamp() >= *threshold,
+
+            Condition::TimeBefore(threshold) => env.ledger().timestamp() < *threshold,
+
+            Condition::BalanceAbove(balance) => {
+                let current =
+                    token::Client::new(env, &balance.token).balance(&balance.watch_address);
+                current >= balance.threshold
+            }
+
+            Condition::BalanceBelow(balance) => {
+                let current =
+                    token::Client::new(env, &balance.token).balance(&balance.watch_address);
+                current < balance.threshold
+            }
+
+            Condition::MultiSigApproved(multisig) => {
+                let mut approvals: u32 = 0;
+                for signer in multisig.signers.iter() {
+                    let stamp_key = DataKey::ApprovalStamp(payment_id, signer.clone());
+                    if env.storage().persistent().has(&stamp_key) {
+                        approvals = approvals.saturating_add(1);
+                    }
+                    if approvals >= multisig.required {
+                        return true;
+                    }
+                }
+                false
+            }
+
+            Condition::PriceAbove(price) => match Self::fresh_price(env, price) {
+                Some(latest) => latest >= price.threshold,
+                None => false,
+            },
+
+            Condition::PriceBelow(price) => match Self::fresh_price(env, price) {
+                Some(latest) => latest < price.threshold,
+                None => false,
+            },
+
+            // Checked again here so a disallowed contract is never called.
+            Condition::ContractPredicate(predicate) => {
+                Self::predicate_allowed(env, &predicate.contract)
+                    && matches!(
+                        env.try_invoke_contract::<bool, soroban_sdk::Error>(
+                            &predicate.contract,
+                            &predicate.function,
+                            predicate.args.clone()
+                        ),
+                        Ok(Ok(true))
+                    )
+            }
+
+            Condition::PaymentSettled(settlement) => match &settlement.source {
+                SettlementSource::ConditionalPayment(id) => {
+                    let payment: Option<ConditionalPayment> =
+                        env.storage().persistent().get(&DataKey::Payment(*id));
+                    match payment.map(|p| p.status) {
+                        Some(PaymentStatus::Executed) => true,
+                        Some(PaymentStatus::Cancelled) => !settlement.require_success,
+                        _ => false,
+                    }
+                }
+                SettlementSource::Escrow(escrow, escrow_id) => {
+                    match Self::escrow_status(env, escrow, *escrow_id) {
+                        Some(EscrowStatus::Released) => true,
+                        Some(EscrowStatus::Reversed) | Some(EscrowStatus::Resolved) => {
+                            !settlement.require_success
+                        }
+                        _ => false,
+                    }
+                }
+            },
+        }
+    }
+
+    /// Latest price from the condition's oracle, or `None` when the feed has
+    /// no price, the price is older than `max_age` seconds, or the call fails.
+    fn fresh_price(env: &Env, condition: &PriceCondition) -> Option<i128> {
+        let data =
+            match PriceFeedClient::new(env, &condition.oracle).try_lastprice(&condition.asset) {
+                Ok(Ok(Some(data))) => data,
+                _ => return None,
+            };
+        let age = env.ledger().timestamp().saturating_sub(data.timestamp);
+        if age > condition.max_age {
+            return None;
+        }
+        Some(data.price)
+    }
+
+    /// Status of an escrow read through the escrow contract's `get_escrow`.
+    ///
+    /// The record is decoded as a map so that only its `status` field has
+    /// to match this contract's view of the escrow types.
+    fn escrow_status(env: &Env, escrow: &Address, escrow_id: u64) -> Option<EscrowStatus> {
+        let args = Vec::from_array(env, [escrow_id.into_val(env)]);
+        let record = match env.try_invoke_contract::<Option<Map<Symbol, Val>>, soroban_sdk::Error>(
+            escrow,
+            &Symbol::new(env, "get_escrow"),
+            args,
+        ) {
+            Ok(Ok(Some(record))) => record,
+            _ => return None,
+        };
+        let status = record.get(Symbol::new(env, "status"))?;
+        EscrowStatus::try_from_val(env, &status).ok()
+    }
+}
+
+// ── Contract ──────────────────────────────────────────────────────────────────
+
+#[contract]
+pub struct ConditionalPaymentsContract;
+
+#[contractimpl]
+impl ConditionalPaymentsContract {
+    // ── Lifecycle ────────────────────────────────────────────────────────────
+
+    /// Initialise the contract. Only callable once.
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic_with_error!(&env, ConditionalPaymentError::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::NextPaymentId, &0u64);
+    }
+
+    // ── Administration ───────────────────────────────────────────────────────
+
+    /// Allow or disallow `contract` as the target of `ContractPredicate`
+    /// conditions. Only the admin may call this.
+    ///
+    /// Only allow contracts whose functions are read-only: the predicate
+    /// call is made by this contract, which holds every payment's escrow.
+    ///
+    /// # Security
+    /// - `caller.require_auth()` is first.
+    /// - This contract itself can never be allowed.
+    pub fn set_predicate_contract(env: Env, caller: Address, contract: Address, allowed: bool) {
+        caller.require_auth();
+        let admin = Self::require_initialized(&env);
+        if caller != admin {
+            panic_with_error!(&env, ConditionalPaymentError::Unauthorized);
+        }
+        if contract == env.current_contract_address() {
+            panic_with_error!(&env, ConditionalPaymentError::InvalidCondition);
+        }
+
+        let key = DataKey::PredicateContract(contract.clone());
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, PERSISTENT_TTL_BUMP, PERSISTENT_TTL_BUMP);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        ConditionalPaymentEvents::predicate_set(&env, &contract, allowed);
+    }
+
+    // ── Payment creation ─────────────────────────────────────────────────────
+
+    /// Create a conditional payment, immediately escrowing `amount` tokens.
+    ///
+    /// The payer must have pre-approved the contract for at least `amount`
+    /// via the token's `approve` mechanism.
+    ///
+    /// # Parameters
+    /// - `conditions` — 1–`MAX_CONDITIONS` conditions, ALL must pass to execute.
+    ///   They are stored as leaves `0..n` under a single `AllOf` root.
+    ///
+    /// Returns the new payment ID.
+    ///
+    /// # Security
+    /// - `payer.require_auth()` is first.
+    /// - Payer ≠ recipient (prevents zero-net-effect circular payments).
+    /// - All condition parameters are validated before the token transfer.
+    /// - Funds move into escrow at creation; execution can never fail for
+    ///   insufficient balance.
+    pub fn create_payment(
+        env: Env,
+        payer: Address,
+        recipient: Address,
+        token: Address,
+        amount: i128,
+        conditions: Vec<Condition>,
+    ) -> u64 {
+        payer.require_auth();
+
+        if conditions.is_empty() || conditions.len() > MAX_CONDITIONS as u32 {
+            panic_with_error!(&env, ConditionalPaymentError::TooManyConditions);
+        }
+
+        let mut nodes = Vec::new(&env);
+        let mut children = Vec::new(&env);
+        for (index, condition) in conditions.iter().enumerate() {
+            nodes.push_back(ConditionNode::Leaf(condition));
+            children.push_back(index as u32);
+        }
+        nodes.push_back(ConditionNode::AllOf(children));
+
+        Self::create_with_nodes(&env, payer, recipient, token, amount, nodes, Vec::new(&env))
+    }
+
+    /// Create a conditional payment guarded by a condition tree,
+    /// immediately escrowing `amount` tokens.
+    ///
+    /// # Parameters
+    /// - `conditions` — 1–`MAX_CONDITION_NODES` nodes, at most
+    ///   `MAX_CONDITION_DEPTH` deep. Children precede their parent and the
+    ///   last node is the root; the payment executes when the root passes.
+    ///
+    /// Returns the new payment ID.
+    ///
+    /// # Security
+    /// Same guarantees as `create_payment`; the whole tree is validated
+    /// before the token transfer.
+    pub fn create_payment_with_tree(
+        env: Env,
+        payer: Address,
+        recipient: Address,
+        token: Address,
+        amount: i128,
+        conditions: Vec<ConditionNode>,
+    ) -> u64 {
+        payer.require_auth();
+        Self::create_with_nodes(
+            &env,
+            payer,
+            recipient,
+            token,
+            amount,
+            conditions,
+            Vec::new(&env),
+        )
+    }
+
+    /// Create a conditional payment that is split across several
+    /// recipients on execution, immediately escrowing `amount` tokens.
+    ///
+    /// # Parameters
+    /// - `remainder` — receives whatever the legs leave over, including
+    ///   rounding dust from basis-point shares.
+    /// - `conditions` — condition tree, as for `create_payment_with_tree`.
+    /// - `splits` — 1–`MAX_SPLIT_LEGS` legs; fixed and bps shares must be
+    ///   positive and together may not exceed `amount`.
+    ///
+    /// Returns the new payment ID.
+    ///
+    /// # Security
+    /// Same guarantees as `create_payment`; the split is validated before
+    /// the token transfer, and no leg may pay the payer.
+    pub fn create_split_payment(
+        env: Env,
+        payer: Address,
+        remainder: Address,
+        token: Address,
+        amount: i128,
+        conditions: Vec<ConditionNode>,
+        splits: Vec<SplitLeg>,
+    ) -> u64 {
+        payer.require_auth();
+        if splits.is_empty() {
+            panic_with_error!(&env, ConditionalPaymentError::InvalidSplit);
+        }
+        Self::create_with_nodes(&env, payer, remainder, token, amount, conditions, splits)
+    }
+
+    // ── MultiSig approval ────────────────────────────────────────────────────
+
+    /// Record `signer`'s approval for a pending payment's MultiSig condition.
+    ///
+    /// Can be called independently of execution, allowing signers to approve
+    /// asynchronously before the time window opens.
+    ///
+    /// # Security
+    /// - `signer.require_auth()` is first.
+    /// - Caller must appear in at least one `MultiSigApproved` condition.
+    /// - Duplicate approvals are rejected via a typed `ApprovalStamp` key.
+    /// - Only pending payments accept approvals.
+    pub fn approve(env: Env, signer: Address, payment_id: u64) {
+        signer.require_auth();
+        Self::require_initialized(&env);
+
+        let payment = Self::load_payment(&env, payment_id);
+
+        if payment.status != PaymentStatus::Pending {
+            panic_with_error!(&env, ConditionalPaymentError::PaymentNotPending);
+        }
+
+        // Verify the signer appears in at least one MultiSig condition.
+        let mut valid_signer = false;
+        for signers in Self::multisig_signers(&env, &payment.conditions).iter() {
+            if signers.contains(&signer) {
+                valid_signer = true;
+                break;
+            }
+        }
+        if !valid_signer {
+            panic_with_error!(&env, ConditionalPaymentError::NotASigner);
+        }
+
+        // Duplicate approval guard.
+        let stamp_key = DataKey::ApprovalStamp(payment_id, signer.clone());
+        if env.storage().persistent().has(&stamp_key) {
+            panic_with_error!(&env, ConditionalPaymentError::AlreadyApproved);
+        }
+
+        // Write approval stamp and bump TTL.
+        env.storage().persistent().set(&stamp_key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&stamp_key, PERSISTENT_TTL_BUMP, PERSISTENT_TTL_BUMP);
+
+        ConditionalPaymentEvents::approval_recorded(&env, payment_id, &signer);
+    }
+
+    // ── Execution ────────────────────────────────────────────────────────────
+
+    /// Attempt to execute a conditional payment.
+    ///
+    /// May be called by anyone — if the root of the condition tree passes
+    /// the funds are transferred from escrow to the recipient. Otherwise a
+    /// `condition_failed` event is emitted for the root index and the call
+    /// reverts with `ConditionNotMet`.
+    ///
+    /// # Security
+    /// - Payment must be `Pending`.
+    /// - The whole tree is evaluated; a failing root reverts the call.
+    /// - State is updated to `Executed` BEFORE the outbound token transfer
+    ///   (checks-effects-interactions pattern).
+    pub fn execute_payment(env: Env, payment_id: u64) -> i128 {
+        Self::require_initialized(&env);
+
+        let payment = Self::load_payment(&env, payment_id);
+
+        if payment.status != PaymentStatus::Pending {
+            panic_with_error!(&env, ConditionalPaymentError::PaymentNotPending);
+        }
+
+        // Evaluate the tree; revert unless the root passes.
+        let trace = Self::evaluate_tree(&env, payment_id, &payment.conditions);
+        let root = trace.len() - 1;
+        if !trace.get_unchecked(root) {
+            ConditionalPaymentEvents::condition_failed(&env, payment_id, root);
+            panic_with_error!(&env, ConditionalPaymentError::ConditionNotMet);
+        }
+
+        // Conditions passed — update state before the transfer.
+        let mut settled = payment.clone();
+        settled.status = PaymentStatus::Executed;
+        settled.settled_at = env.ledger().timestamp();
+        Self::save_payment(&env, payment_id, &settled);
+
+        // Transfer escrowed funds to the recipient, or to every split leg
+        // and the remainder recipient. Any failed leg reverts the whole call.
+        let token_client = token::Client::new(&env, &payment.token);
+        if payment.splits.is_empty() {
+            token_client.transfer(
+                &env.current_contract_address(),
+                &payment.recipient,
+                &payment.amount,
+            );
+        } else {
+            let amounts =
+                Self::split_amounts(&env, &payment.payer, payment.amount, &payment.splits);
+            for (index, leg_amount) in amounts.iter().enumerate() {
+                if leg_amount == 0 {
+                    continue;
+                }
+                let leg_recipient = match payment.splits.get(index as u32) {
+                    Some(leg) => leg.recipient,
+                    None => payment.recipient.clone(),
+                };
+                token_client.transfer(&env.current_contract_address(), &leg_recipient, &leg_amount);
+                ConditionalPaymentEvents::split_leg_paid(
+                    &env,
+                    payment_id,
+                    index as u32,
+                    &leg_recipient,
+                    leg_amount,
+                );
+            }
+        }
+
+        ConditionalPaymentEvents::payment_executed(
+            &env,
+            payment_id,
+            &payment.payer,
+            &payment.recipient,
+            payment.amount,
+        );
+
+        payment.amount
+    }
+
+    // ── Cancellation ─────────────────────────────────────────────────────────
+
+    /// Cancel a pending payment and return escrowed funds to the payer.
+    ///
+    /// Only the original payer may cancel.
+    ///
+    /// # Security
+    /// - `caller.require_auth()` is first.
+    /// - Only pending payments can be cancelled.
+    /// - Escrowed funds are returned via the outbound transfer AFTER state
+    ///   is updated (checks-effects-interactions).
+    pub fn cancel_payment(env: Env, caller: Address, payment_id: u64) {
+        caller.require_auth();
+        Self::require_initialized(&env);
+
+        let payment = Self::load_payment(&env, payment_id);
+
+        if caller != payment.payer {
+            panic_with_error!(&env, ConditionalPaymentError::Unauthorized);
+        }
+        if payment.status != PaymentStatus::Pending {
+            panic_with_error!(&env, ConditionalPaymentError::PaymentNotPending);
+        }
+
+        // Update state before the outbound transfer.
+        let mut cancelled = payment.clone();
+        cancelled.status = PaymentStatus::Cancelled;
+        cancelled.settled_at = env.ledger().timestamp();
+        Self::save_payment(&env, payment_id, &cancelled);
+
+        // Refund escrowed amount back to payer.
+        token::Client::new(&env, &payment.token).transfer(
+            &env.current_contract_address(),
+            &payment.payer,
+            &payment.amount,
+        );
+
+        ConditionalPaymentEvents::payment_cancelled(
+            &env,
+            payment_id,
+            &payment.payer,
+            payment.amount,
+        );
+    }
+
+    // ── Read-only queries ────────────────────────────────────────────────────
+
+    /// Return the full payment record.
+    pub fn get_payment(env: Env, payment_id: u64) -> ConditionalPayment {
+        Self::require_initialized(&env);
+        Self::load_payment(&env, payment_id)
+    }
+
+    /// Dry-run condition evaluation without executing.
+    ///
+    /// Returns a `Vec<bool>` evaluation trace — one entry per tree node in
+    /// order, with the root last. For payments created with
+    /// `create_payment`, entries `0..n` are the original conditions.
+    /// Useful for frontends to explain why a payment is blocked.
+    pub fn check_conditions(env: Env, payment_id: u64) -> Vec<bool> {
+        Self::require_initialized(&env);
+        let payment = Self::load_payment(&env, payment_id);
+        Self::evaluate_tree(&env, payment_id, &payment.conditions)
+    }
+
+    /// Return how many approvals the MultiSig leaf at node `condition_index`
+    /// has accumulated so far.
+    pub fn approval_count(env: Env, payment_id: u64, condition_index: u32) -> u32 {
+        Self::require_initialized(&env);
+        let payment = Self::load_payment(&env, payment_id);
+        let condition = payment
+            .conditions
+            .get(condition_index)
+            .unwrap_or_else(|| panic_with_error!(&env, ConditionalPaymentError::InvalidCondition));
+        match condition {
+            ConditionNode::Leaf(Condition::MultiSigApproved(multisig)) => {
+                let mut count: u32 = 0;
+                for signer in multisig.signers.iter() {
+                    let stamp_key = DataKey::ApprovalStamp(payment_id, signer.clone());
+                    if env.storage().persistent().has(&stamp_key) {
+                        count = count.saturating_add(1);
+                    }
+                }
+                count
+            }
+            _ => panic_with_error!(&env, ConditionalPaymentError::InvalidCondition),
+        }
+    }
+
+    /// Return whether `contract` may be used as a `ContractPredicate` target.
+    pub fn is_predicate_contract(env: Env, contract: Address) -> bool {
+        Self::predicate_allowed(&env, &contract)
+    }
+
+    /// Return total number of payments ever created.
+    pub fn payment_count(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::NextPaymentId)
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,2041 @@
+// Integration tests for the conditional payments contract.
+//
+// Test taxonomy
+// ─────────────
+// happy_*     — correct flows
+// neg_*       — invalid inputs / calls that must be rejected
+// edge_*      — boundary / timing / combination corner cases
+// auth_*      — authorization guard tests
+// cond_*      — per-condition-type validation tests
+// tree_*      — condition tree composition and validation
+// ext_*       — oracle, contract-predicate and settlement conditions
+// split_*     — multi-recipient split settlement
+
+#![cfg(test)]
+
+use crate::{
+    BalanceCondition, Condition, ConditionNode, ConditionalPaymentError,
+    ConditionalPaymentsContract, ConditionalPaymentsContractClient, EscrowStatus,
+    MultiSigCondition, PaymentStatus, PredicateCondition, PriceAsset, PriceCondition, PriceData,
+    SettlementCondition, SettlementSource, SplitLeg, SplitShare, MAX_CONDITIONS,
+    MAX_CONDITION_DEPTH, MAX_CONDITION_NODES, MAX_SPLIT_LEGS,
+};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short,
+    testutils::{Address as _, IssuerFlags, Ledger, LedgerInfo},
+    token, vec, Address, Env, IntoVal, Symbol, Vec,
+};
+
+// ── Harness ───────────────────────────────────────────────────────────────────
+
+const AMOUNT: i128 = 10_000;
+
+struct Ctx {
+    env: Env,
+    client: ConditionalPaymentsContractClient<'static>,
+    admin: Address,
+    token_id: Address,
+}
+
+impl Ctx {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let token_admin = Address::generate(&env);
+        let token = env.register_stellar_asset_contract_v2(token_admin);
+        // Revocable, so tests can stop an address from receiving the token.
+        token.issuer().set_flag(IssuerFlags::RevocableFlag);
+        let token_id = token.address();
+
+        let contract_id = env.register(ConditionalPaymentsContract, ());
+        let client = ConditionalPaymentsContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+
+        Self {
+            env,
+            client,
+            admin,
+            token_id,
+        }
+    }
+
+    /// Mint `balance` to `owner` and approve the contract to spend it.
+    fn funded_payer(&self, balance: i128) -> Address {
+        let owner = Address::generate(&self.env);
+        token::StellarAssetClient::new(&self.env, &self.token_id).mint(&owner, &balance);
+        token::Client::new(&self.env, &self.token_id).approve(
+            &owner,
+            &self.client.address,
+            &balance,
+            &(self.env.ledger().sequence() + 10_000),
+        );
+        owner
+    }
+
+    /// Register a `MockInvoice` the admin allows for predicates.
+    fn allowed_invoice(&self) -> Address {
+        let invoice = self.env.register(MockInvoice, ());
+        self.client
+            .set_predicate_contract(&self.admin, &invoice, &true);
+        invoice
+    }
+
+    fn set_timestamp(&self, ts: u64) {
+        self.env.ledger().set(LedgerInfo {
+            timestamp: ts,
+            ..self.env.ledger().get()
+        });
+    }
+
+    fn balance(&self, addr: &Address) -> i128 {
+        token::Client::new(&self.env, &self.token_id).balance(addr)
+    }
+
+    /// Shorthand: create a payment with a single `TimeAfter` condition.
+    fn create_time_after(&self, payer: &Address, recipient: &Address, after: u64) -> u64 {
+        self.client.create_payment(
+            payer,
+            recipient,
+            &self.token_id,
+            &AMOUNT,
+            &vec![&self.env, Condition::TimeAfter(after)],
+        )
+    }
+}
+
+// ── Initialization ────────────────────────────────────────────────────────────
+
+#[test]
+fn happy_initialize_sets_admin() {
+    let ctx = Ctx::new();
+    assert_eq!(ctx.client.payment_count(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn neg_reinit_blocked() {
+    let ctx = Ctx::new();
+    ctx.client.initialize(&ctx.admin);
+}
+
+// ── create_payment ────────────────────────────────────────────────────────────
+
+#[test]
+fn happy_create_payment_stores_record_and_escrows_funds() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(1_000);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+
+    let id = ctx.create_time_after(&payer, &recipient, 2_000);
+
+    let p = ctx.client.get_payment(&id);
+    assert_eq!(p.payer, payer);
+    assert_eq!(p.recipient, recipient);
+    assert_eq!(p.amount, AMOUNT);
+    assert_eq!(p.status, PaymentStatus::Pending);
+    assert_eq!(p.created_at, 1_000);
+    assert_eq!(p.settled_at, 0);
+
+    // Funds left payer and arrived at contract.
+    assert_eq!(ctx.balance(&payer), 0);
+    assert_eq!(ctx.balance(&ctx.client.address), AMOUNT);
+}
+
+#[test]
+fn happy_payment_ids_are_incrementing() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT * 3);
+    let recipient = Address::generate(&ctx.env);
+
+    let id0 = ctx.create_time_after(&payer, &recipient, 1);
+    let id1 = ctx.create_time_after(&payer, &recipient, 1);
+    let id2 = ctx.create_time_after(&payer, &recipient, 1);
+
+    assert_eq!((id0, id1, id2), (0, 1, 2));
+    assert_eq!(ctx.client.payment_count(), 3);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn neg_create_zero_amount() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &0,
+        &vec![&ctx.env, Condition::TimeAfter(1)],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn neg_create_negative_amount() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &-1,
+        &vec![&ctx.env, Condition::TimeAfter(1)],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #13)")]
+fn neg_payer_equals_recipient() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    ctx.client.create_payment(
+        &payer,
+        &payer,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![&ctx.env, Condition::TimeAfter(1)],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn neg_too_many_conditions() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let mut conds = Vec::new(&ctx.env);
+    for i in 0..(MAX_CONDITIONS + 1) as u64 {
+        conds.push_back(Condition::TimeAfter(i + 1));
+    }
+    ctx.client
+        .create_payment(&payer, &recipient, &ctx.token_id, &AMOUNT, &conds);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn neg_zero_conditions() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &Vec::new(&ctx.env),
+    );
+}
+
+// ── cond_*: per-condition validation at creation ──────────────────────────────
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn cond_time_after_zero_timestamp_invalid() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![&ctx.env, Condition::TimeAfter(0)],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn cond_time_before_zero_timestamp_invalid() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![&ctx.env, Condition::TimeBefore(0)],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn cond_balance_above_zero_threshold_invalid() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let watch = Address::generate(&ctx.env);
+    ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::BalanceAbove(BalanceCondition {
+                token: ctx.token_id.clone(),
+                watch_address: watch,
+                threshold: 0,
+            }),
+        ],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn cond_multisig_no_signers_invalid() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::MultiSigApproved(MultiSigCondition {
+                signers: Vec::new(&ctx.env),
+                required: 1,
+            }),
+        ],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn cond_multisig_required_exceeds_signers() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let s1 = Address::generate(&ctx.env);
+    ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::MultiSigApproved(MultiSigCondition {
+                signers: vec![&ctx.env, s1],
+                required: 2, // only 1 signer but requires 2
+            }),
+        ],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn cond_multisig_required_zero_invalid() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let s1 = Address::generate(&ctx.env);
+    ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::MultiSigApproved(MultiSigCondition {
+                signers: vec![&ctx.env, s1],
+                required: 0,
+            }),
+        ],
+    );
+}
+
+// ── cond_*: TimeAfter execution ───────────────────────────────────────────────
+
+#[test]
+fn cond_time_after_executes_when_due() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(500);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let id = ctx.create_time_after(&payer, &recipient, 1_000);
+
+    ctx.set_timestamp(1_000);
+    let paid = ctx.client.execute_payment(&id);
+    assert_eq!(paid, AMOUNT);
+    assert_eq!(ctx.balance(&recipient), AMOUNT);
+}
+
+#[test]
+fn cond_time_after_blocks_before_threshold() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(500);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let id = ctx.create_time_after(&payer, &recipient, 1_000);
+
+    ctx.set_timestamp(999);
+    let result = ctx.client.try_execute_payment(&id);
+    assert_eq!(
+        result,
+        Err(Ok(ConditionalPaymentError::ConditionNotMet.into()))
+    );
+}
+
+#[test]
+fn cond_time_after_exactly_at_threshold_passes() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let id = ctx.create_time_after(&payer, &recipient, 1_000);
+
+    ctx.set_timestamp(1_000); // exactly at threshold
+    let paid = ctx.client.execute_payment(&id);
+    assert_eq!(paid, AMOUNT);
+}
+
+// ── cond_*: TimeBefore execution ──────────────────────────────────────────────
+
+#[test]
+fn cond_time_before_executes_before_deadline() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(500);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![&ctx.env, Condition::TimeBefore(1_000)],
+    );
+
+    ctx.set_timestamp(999);
+    let paid = ctx.client.execute_payment(&id);
+    assert_eq!(paid, AMOUNT);
+}
+
+#[test]
+fn cond_time_before_blocks_at_or_after_deadline() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![&ctx.env, Condition::TimeBefore(1_000)],
+    );
+
+    // At exactly the deadline — condition should FAIL (now < threshold is false).
+    ctx.set_timestamp(1_000);
+    let result = ctx.client.try_execute_payment(&id);
+    assert_eq!(
+        result,
+        Err(Ok(ConditionalPaymentError::ConditionNotMet.into()))
+    );
+}
+
+// ── cond_*: BalanceAbove execution ────────────────────────────────────────────
+
+#[test]
+fn cond_balance_above_executes_when_balance_sufficient() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let watch = Address::generate(&ctx.env);
+
+    // Fund the watched address.
+    token::StellarAssetClient::new(&ctx.env, &ctx.token_id).mint(&watch, &5_000);
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::BalanceAbove(BalanceCondition {
+                token: ctx.token_id.clone(),
+                watch_address: watch.clone(),
+                threshold: 5_000,
+            }),
+        ],
+    );
+
+    let paid = ctx.client.execute_payment(&id);
+    assert_eq!(paid, AMOUNT);
+}
+
+#[test]
+fn cond_balance_above_blocks_when_balance_insufficient() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let watch = Address::generate(&ctx.env);
+
+    // watched address has 4_999 — below threshold of 5_000.
+    token::StellarAssetClient::new(&ctx.env, &ctx.token_id).mint(&watch, &4_999);
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::BalanceAbove(BalanceCondition {
+                token: ctx.token_id.clone(),
+                watch_address: watch,
+                threshold: 5_000,
+            }),
+        ],
+    );
+
+    let result = ctx.client.try_execute_payment(&id);
+    assert_eq!(
+        result,
+        Err(Ok(ConditionalPaymentError::ConditionNotMet.into()))
+    );
+}
+
+// ── cond_*: BalanceBelow execution ────────────────────────────────────────────
+
+#[test]
+fn cond_balance_below_executes_when_balance_low() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let watch = Address::generate(&ctx.env); // zero balance
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::BalanceBelow(BalanceCondition {
+                token: ctx.token_id.clone(),
+                watch_address: watch,
+                threshold: 1_000,
+            }),
+        ],
+    );
+
+    let paid = ctx.client.execute_payment(&id);
+    assert_eq!(paid, AMOUNT);
+}
+
+#[test]
+fn cond_balance_below_blocks_when_balance_high() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let watch = Address::generate(&ctx.env);
+
+    token::StellarAssetClient::new(&ctx.env, &ctx.token_id).mint(&watch, &2_000);
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::BalanceBelow(BalanceCondition {
+                token: ctx.token_id.clone(),
+                watch_address: watch,
+                threshold: 1_000, // watch has 2_000 >= 1_000, so "below" fails
+            }),
+        ],
+    );
+
+    let result = ctx.client.try_execute_payment(&id);
+    assert_eq!(
+        result,
+        Err(Ok(ConditionalPaymentError::ConditionNotMet.into()))
+    );
+}
+
+// ── cond_*: MultiSig execution ────────────────────────────────────────────────
+
+#[test]
+fn cond_multisig_executes_when_threshold_met() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let s1 = Address::generate(&ctx.env);
+    let s2 = Address::generate(&ctx.env);
+    let s3 = Address::generate(&ctx.env);
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::MultiSigApproved(MultiSigCondition {
+                signers: vec![&ctx.env, s1.clone(), s2.clone(), s3.clone()],
+                required: 2,
+            }),
+        ],
+    );
+
+    // Only 2-of-3 needed.
+    ctx.client.approve(&s1, &id);
+    ctx.client.approve(&s2, &id);
+
+    let paid = ctx.client.execute_payment(&id);
+    assert_eq!(paid, AMOUNT);
+}
+
+#[test]
+fn cond_multisig_blocks_when_insufficient_approvals() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let s1 = Address::generate(&ctx.env);
+    let s2 = Address::generate(&ctx.env);
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::MultiSigApproved(MultiSigCondition {
+                signers: vec![&ctx.env, s1.clone(), s2.clone()],
+                required: 2,
+            }),
+        ],
+    );
+
+    // Only 1 of 2 approvals.
+    ctx.client.approve(&s1, &id);
+
+    let result = ctx.client.try_execute_payment(&id);
+    assert_eq!(
+        result,
+        Err(Ok(ConditionalPaymentError::ConditionNotMet.into()))
+    );
+}
+
+#[test]
+fn cond_multisig_approval_count_tracks_correctly() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let s1 = Address::generate(&ctx.env);
+    let s2 = Address::generate(&ctx.env);
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::MultiSigApproved(MultiSigCondition {
+                signers: vec![&ctx.env, s1.clone(), s2.clone()],
+                required: 2,
+            }),
+        ],
+    );
+
+    assert_eq!(ctx.client.approval_count(&id, &0), 0);
+    ctx.client.approve(&s1, &id);
+    assert_eq!(ctx.client.approval_count(&id, &0), 1);
+    ctx.client.approve(&s2, &id);
+    assert_eq!(ctx.client.approval_count(&id, &0), 2);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn auth_non_signer_cannot_approve() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let s1 = Address::generate(&ctx.env);
+    let interloper = Address::generate(&ctx.env);
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::MultiSigApproved(MultiSigCondition {
+                signers: vec![&ctx.env, s1],
+                required: 1,
+            }),
+        ],
+    );
+
+    ctx.client.approve(&interloper, &id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")]
+fn neg_duplicate_approval_rejected() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let s1 = Address::generate(&ctx.env);
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::MultiSigApproved(MultiSigCondition {
+                signers: vec![&ctx.env, s1.clone()],
+                required: 1,
+            }),
+        ],
+    );
+
+    ctx.client.approve(&s1, &id);
+    ctx.client.approve(&s1, &id); // must fail
+}
+
+// ── execute_payment: state transitions ───────────────────────────────────────
+
+#[test]
+fn happy_execution_marks_payment_executed() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let id = ctx.create_time_after(&payer, &recipient, 1);
+
+    ctx.set_timestamp(1);
+    ctx.client.execute_payment(&id);
+
+    let p = ctx.client.get_payment(&id);
+    assert_eq!(p.status, PaymentStatus::Executed);
+    assert!(p.settled_at > 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn neg_execute_already_executed_payment() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let id = ctx.create_time_after(&payer, &recipient, 1);
+
+    ctx.set_timestamp(1);
+    ctx.client.execute_payment(&id);
+    ctx.client.execute_payment(&id); // second attempt must fail
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn neg_execute_cancelled_payment() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let id = ctx.create_time_after(&payer, &recipient, 1_000);
+
+    ctx.client.cancel_payment(&payer, &id);
+
+    ctx.set_timestamp(1_000);
+    ctx.client.execute_payment(&id);
+}
+
+// ── cancel_payment ────────────────────────────────────────────────────────────
+
+#[test]
+fn happy_payer_can_cancel_and_funds_returned() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+
+    let id = ctx.create_time_after(&payer, &recipient, 1_000);
+    assert_eq!(ctx.balance(&payer), 0); // funds in escrow after create
+
+    ctx.set_timestamp(500);
+    ctx.client.cancel_payment(&payer, &id);
+
+    // Funds returned.
+    assert_eq!(ctx.balance(&payer), AMOUNT);
+    assert_eq!(ctx.balance(&ctx.client.address), 0);
+
+    let p = ctx.client.get_payment(&id);
+    assert_eq!(p.status, PaymentStatus::Cancelled);
+    assert!(p.settled_at > 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #3)")]
+fn auth_non_payer_cannot_cancel() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let id = ctx.create_time_after(&payer, &recipient, 1_000);
+
+    let attacker = Address::generate(&ctx.env);
+    ctx.client.cancel_payment(&attacker, &id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn neg_cancel_executed_payment() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let id = ctx.create_time_after(&payer, &recipient, 1);
+
+    ctx.set_timestamp(1);
+    ctx.client.execute_payment(&id);
+    ctx.client.cancel_payment(&payer, &id); // must fail
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn neg_cancel_twice() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let id = ctx.create_time_after(&payer, &recipient, 1_000);
+
+    ctx.client.cancel_payment(&payer, &id);
+    ctx.client.cancel_payment(&payer, &id); // must fail
+}
+
+// ── check_conditions dry-run ──────────────────────────────────────────────────
+
+#[test]
+fn happy_check_conditions_returns_per_condition_results() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(500);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+
+    // Two conditions: TimeAfter(1000) [fails now] and TimeBefore(2000) [passes now].
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::TimeAfter(1_000),
+            Condition::TimeBefore(2_000),
+        ],
+    );
+
+    let results = ctx.client.check_conditions(&id);
+    assert_eq!(results.get(0), Some(false)); // TimeAfter(1000) not yet
+    assert_eq!(results.get(1), Some(true)); // TimeBefore(2000) passes
+
+    // Advance past TimeAfter threshold.
+    ctx.set_timestamp(1_000);
+    let results2 = ctx.client.check_conditions(&id);
+    assert_eq!(results2.get(0), Some(true));
+    assert_eq!(results2.get(1), Some(true));
+}
+
+// ── edge_*: compound AND conditions ──────────────────────────────────────────
+
+#[test]
+fn edge_all_conditions_must_pass_for_execution() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let s1 = Address::generate(&ctx.env);
+
+    // TimeAfter(1000) AND MultiSig(1-of-1).
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::TimeAfter(1_000),
+            Condition::MultiSigApproved(MultiSigCondition {
+                signers: vec![&ctx.env, s1.clone()],
+                required: 1,
+            }),
+        ],
+    );
+
+    // Advance time but don't approve — should fail.
+    ctx.set_timestamp(1_000);
+    let result = ctx.client.try_execute_payment(&id);
+    assert_eq!(
+        result,
+        Err(Ok(ConditionalPaymentError::ConditionNotMet.into()))
+    );
+
+    // Approve but reset time — should fail.
+    ctx.set_timestamp(0);
+    ctx.client.approve(&s1, &id);
+    let result2 = ctx.client.try_execute_payment(&id);
+    assert_eq!(
+        result2,
+        Err(Ok(ConditionalPaymentError::ConditionNotMet.into()))
+    );
+
+    // Both satisfied — should execute.
+    ctx.set_timestamp(1_000);
+    let paid = ctx.client.execute_payment(&id);
+    assert_eq!(paid, AMOUNT);
+}
+
+#[test]
+fn edge_time_window_both_after_and_before() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+
+    // Only executable in window [1000, 2000).
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::TimeAfter(1_000),
+            Condition::TimeBefore(2_000),
+        ],
+    );
+
+    // Before window.
+    ctx.set_timestamp(999);
+    assert_eq!(
+        ctx.client.try_execute_payment(&id),
+        Err(Ok(ConditionalPaymentError::ConditionNotMet.into()))
+    );
+
+    // Inside window.
+    ctx.set_timestamp(1_500);
+    let paid = ctx.client.execute_payment(&id);
+    assert_eq!(paid, AMOUNT);
+}
+
+#[test]
+fn edge_time_window_expired_before_execution() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let payer = ctx.funded_payer(AMOUNT * 2);
+    let recipient = Address::generate(&ctx.env);
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::TimeAfter(1_000),
+            Condition::TimeBefore(2_000),
+        ],
+    );
+
+    // Past the window — TimeBefore fails.
+    ctx.set_timestamp(2_001);
+    let result = ctx.client.try_execute_payment(&id);
+    assert_eq!(
+        result,
+        Err(Ok(ConditionalPaymentError::ConditionNotMet.into()))
+    );
+
+    // Payer can still cancel and recover funds.
+    ctx.client.cancel_payment(&payer, &id);
+    assert_eq!(ctx.balance(&payer), AMOUNT * 2);
+}
+
+#[test]
+fn edge_max_conditions_boundary_is_accepted() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+
+    let mut conds = Vec::new(&ctx.env);
+    for i in 1..=(MAX_CONDITIONS as u64) {
+        conds.push_back(Condition::TimeAfter(i));
+    }
+    // Should not panic.
+    let id = ctx
+        .client
+        .create_payment(&payer, &recipient, &ctx.token_id, &AMOUNT, &conds);
+
+    ctx.set_timestamp(MAX_CONDITIONS as u64);
+    let paid = ctx.client.execute_payment(&id);
+    assert_eq!(paid, AMOUNT);
+}
+
+#[test]
+fn edge_multisig_1_of_n_any_signer_suffices() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT * 3);
+    let recipient = Address::generate(&ctx.env);
+    let mut signers = Vec::new(&ctx.env);
+    for _ in 0..5 {
+        signers.push_back(Address::generate(&ctx.env));
+    }
+
+    for signer in signers.iter() {
+        // Fresh payment per signer.
+        let id = ctx.client.create_payment(
+            &payer,
+            &recipient,
+            &ctx.token_id,
+            &1, // small amount per test
+            &vec![
+                &ctx.env,
+                Condition::MultiSigApproved(MultiSigCondition {
+                    signers: signers.clone(),
+                    required: 1,
+                }),
+            ],
+        );
+        // Any single signer should unlock it.
+        ctx.client.approve(&signer, &id);
+        ctx.client.execute_payment(&id);
+    }
+}
+
+#[test]
+fn edge_approve_on_cancelled_payment_rejected() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let s1 = Address::generate(&ctx.env);
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::MultiSigApproved(MultiSigCondition {
+                signers: vec![&ctx.env, s1.clone()],
+                required: 1,
+            }),
+        ],
+    );
+
+    ctx.client.cancel_payment(&payer, &id);
+
+    let result = ctx.client.try_approve(&s1, &id);
+    assert_eq!(
+        result,
+        Err(Ok(ConditionalPaymentError::PaymentNotPending.into()))
+    );
+}
+
+// ── auth_*: require_auth guards ───────────────────────────────────────────────
+
+#[test]
+#[should_panic]
+fn auth_create_payment_requires_auth() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let contract_id = env.register(ConditionalPaymentsContract, ());
+    let client = ConditionalPaymentsContractClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    let payer = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    // No auth mocked — must panic.
+    client.create_payment(
+        &payer,
+        &recipient,
+        &token_id,
+        &AMOUNT,
+        &vec![&env, Condition::TimeAfter(1)],
+    );
+}
+
+#[test]
+#[should_panic]
+fn auth_cancel_payment_requires_auth() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let contract_id = env.register(ConditionalPaymentsContract, ());
+    let client = ConditionalPaymentsContractClient::new(&env, &contract_id);
+    let payer;
+    let id;
+    {
+        env.mock_all_auths();
+        client.initialize(&admin);
+        payer = Address::generate(&env);
+        token::StellarAssetClient::new(&env, &token_id).mint(&payer, &AMOUNT);
+        token::Client::new(&env, &token_id).approve(
+            &payer,
+            &client.address,
+            &AMOUNT,
+            &(env.ledger().sequence() + 10_000),
+        );
+        id = client.create_payment(
+            &payer,
+            &Address::generate(&env),
+            &token_id,
+            &AMOUNT,
+            &vec![&env, Condition::TimeAfter(1_000)],
+        );
+    }
+    // No auth mocked for cancel — must panic.
+    env.set_auths(&[]);
+    client.cancel_payment(&payer, &id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #5)")]
+fn neg_get_nonexistent_payment() {
+    let ctx = Ctx::new();
+    ctx.client.get_payment(&9999);
+}
+
+// ── tree_*: condition trees ───────────────────────────────────────────────────
+
+/// Builds "(after `after` AND 2-of-2 multisig) OR arbiter approved".
+fn escrow_release_tree(
+    env: &Env,
+    after: u64,
+    s1: &Address,
+    s2: &Address,
+    arbiter: &Address,
+) -> Vec<ConditionNode> {
+    vec![
+        env,
+        ConditionNode::Leaf(Condition::TimeAfter(after)),
+        ConditionNode::Leaf(Condition::MultiSigApproved(MultiSigCondition {
+            signers: vec![env, s1.clone(), s2.clone()],
+            required: 2,
+        })),
+        ConditionNode::AllOf(vec![env, 0, 1]),
+        ConditionNode::Leaf(Condition::MultiSigApproved(MultiSigCondition {
+            signers: vec![env, arbiter.clone()],
+            required: 1,
+        })),
+        ConditionNode::AnyOf(vec![env, 2, 3]),
+    ]
+}
+
+#[test]
+fn tree_any_of_executes_via_arbiter_branch() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let s1 = Address::generate(&ctx.env);
+    let s2 = Address::generate(&ctx.env);
+    let arbiter = Address::generate(&ctx.env);
+
+    let id = ctx.client.create_payment_with_tree(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &escrow_release_tree(&ctx.env, 1_000, &s1, &s2, &arbiter),
+    );
+
+    // Time has not passed and signers have not approved, but the arbiter can release.
+    ctx.client.approve(&arbiter, &id);
+    assert_eq!(ctx.client.execute_payment(&id), AMOUNT);
+    assert_eq!(ctx.balance(&recipient), AMOUNT);
+}
+
+#[test]
+fn tree_any_of_executes_via_all_of_branch() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let s1 = Address::generate(&ctx.env);
+    let s2 = Address::generate(&ctx.env);
+    let arbiter = Address::generate(&ctx.env);
+
+    let id = ctx.client.create_payment_with_tree(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &escrow_release_tree(&ctx.env, 1_000, &s1, &s2, &arbiter),
+    );
+
+    ctx.client.approve(&s1, &id);
+    ctx.client.approve(&s2, &id);
+    let result = ctx.client.try_execute_payment(&id);
+    assert_eq!(
+        result,
+        Err(Ok(ConditionalPaymentError::ConditionNotMet.into()))
+    );
+
+    ctx.set_timestamp(1_000);
+    assert_eq!(ctx.client.execute_payment(&id), AMOUNT);
+}
+
+#[test]
+fn tree_check_conditions_returns_per_node_trace() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let s1 = Address::generate(&ctx.env);
+    let s2 = Address::generate(&ctx.env);
+    let arbiter = Address::generate(&ctx.env);
+
+    let id = ctx.client.create_payment_with_tree(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &escrow_release_tree(&ctx.env, 1_000, &s1, &s2, &arbiter),
+    );
+    ctx.client.approve(&s1, &id);
+    ctx.client.approve(&s2, &id);
+
+    let trace = ctx.client.check_conditions(&id);
+    assert_eq!(
+        trace,
+        vec![&ctx.env, false, true, false, false, false],
+        "blocked only by the time condition"
+    );
+}
+
+#[test]
+fn tree_not_and_at_least_n() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(1_500);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+
+    // At least 2 of: after 1000, NOT after 2000, after 3000
+    let tree = vec![
+        &ctx.env,
+        ConditionNode::Leaf(Condition::TimeAfter(1_000)),
+        ConditionNode::Leaf(Condition::TimeAfter(2_000)),
+        ConditionNode::Not(1),
+        ConditionNode::Leaf(Condition::TimeAfter(3_000)),
+        ConditionNode::AtLeastN(2, vec![&ctx.env, 0, 2, 3]),
+    ];
+    let id = ctx
+        .client
+        .create_payment_with_tree(&payer, &recipient, &ctx.token_id, &AMOUNT, &tree);
+
+    assert_eq!(
+        ctx.client.check_conditions(&id),
+        vec![&ctx.env, true, false, true, false, true]
+    );
+    assert_eq!(ctx.client.execute_payment(&id), AMOUNT);
+}
+
+#[test]
+fn tree_flat_payment_trace_keeps_condition_indices() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(500);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::TimeAfter(1_000),
+            Condition::TimeBefore(2_000),
+        ],
+    );
+
+    // Two leaves followed by the implicit AllOf root.
+    assert_eq!(
+        ctx.client.check_conditions(&id),
+        vec![&ctx.env, false, true, false]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn tree_neg_forward_reference() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let tree = vec![
+        &ctx.env,
+        ConditionNode::Not(1),
+        ConditionNode::Leaf(Condition::TimeAfter(1_000)),
+    ];
+    ctx.client
+        .create_payment_with_tree(&payer, &recipient, &ctx.token_id, &AMOUNT, &tree);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn tree_neg_shared_child() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let tree = vec![
+        &ctx.env,
+        ConditionNode::Leaf(Condition::TimeAfter(1_000)),
+        ConditionNode::Not(0),
+        ConditionNode::AnyOf(vec![&ctx.env, 0, 1]),
+    ];
+    ctx.client
+        .create_payment_with_tree(&payer, &recipient, &ctx.token_id, &AMOUNT, &tree);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn tree_neg_unreachable_node() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let tree = vec![
+        &ctx.env,
+        ConditionNode::Leaf(Condition::TimeAfter(1_000)),
+        ConditionNode::Leaf(Condition::TimeAfter(2_000)),
+        ConditionNode::Not(1),
+    ];
+    ctx.client
+        .create_payment_with_tree(&payer, &recipient, &ctx.token_id, &AMOUNT, &tree);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn tree_neg_too_deep() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let mut tree = vec![&ctx.env, ConditionNode::Leaf(Condition::TimeAfter(1_000))];
+    for i in 0..MAX_CONDITION_DEPTH {
+        tree.push_back(ConditionNode::Not(i));
+    }
+    ctx.client
+        .create_payment_with_tree(&payer, &recipient, &ctx.token_id, &AMOUNT, &tree);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn tree_neg_too_many_nodes() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let mut tree = Vec::new(&ctx.env);
+    let mut children = Vec::new(&ctx.env);
+    for i in 0..MAX_CONDITION_NODES as u32 {
+        tree.push_back(ConditionNode::Leaf(Condition::TimeAfter(i as u64 + 1)));
+        children.push_back(i);
+    }
+    tree.push_back(ConditionNode::AllOf(children));
+    ctx.client
+        .create_payment_with_tree(&payer, &recipient, &ctx.token_id, &AMOUNT, &tree);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn tree_neg_at_least_n_exceeds_children() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let tree = vec![
+        &ctx.env,
+        ConditionNode::Leaf(Condition::TimeAfter(1_000)),
+        ConditionNode::AtLeastN(2, vec![&ctx.env, 0]),
+    ];
+    ctx.client
+        .create_payment_with_tree(&payer, &recipient, &ctx.token_id, &AMOUNT, &tree);
+}
+
+// ── ext_*: oracle, predicate and settlement conditions ───────────────────────
+
+/// Price feed returning whatever price was last pushed to it.
+#[contract]
+pub struct MockPriceFeed;
+
+#[contractimpl]
+impl MockPriceFeed {
+    pub fn set_price(env: Env, price: i128, timestamp: u64) {
+        let data = PriceData { price, timestamp };
+        env.storage().instance().set(&symbol_short!("price"), &data);
+    }
+
+    pub fn lastprice(env: Env, _asset: PriceAsset) -> Option<PriceData> {
+        env.storage().instance().get(&symbol_short!("price"))
+    }
+}
+
+/// Invoice contract exposing a boolean `is_delivered` predicate.
+#[contract]
+pub struct MockInvoice;
+
+#[contractimpl]
+impl MockInvoice {
+    pub fn mark_delivered(env: Env, invoice_id: u64) {
+        env.storage().instance().set(&invoice_id, &true);
+    }
+
+    pub fn is_delivered(env: Env, invoice_id: u64) -> bool {
+        env.storage().instance().get(&invoice_id).unwrap_or(false)
+    }
+
+    pub fn is_disputed(_env: Env, _invoice_id: u64) -> bool {
+        panic!("invoice service unavailable")
+    }
+}
+
+/// Escrow record shaped like the escrow contract's, reduced to a few fields.
+#[contracttype]
+#[derive(Clone)]
+pub struct MockEscrowRecord {
+    pub amount: i128,
+    pub status: EscrowStatus,
+}
+
+#[contract]
+pub struct MockEscrow;
+
+#[contractimpl]
+impl MockEscrow {
+    pub fn set_status(env: Env, escrow_id: u64, status: EscrowStatus) {
+        let record = MockEscrowRecord {
+            amount: AMOUNT,
+            status,
+        };
+        env.storage().instance().set(&escrow_id, &record);
+    }
+
+    pub fn get_escrow(env: Env, escrow_id: u64) -> Option<MockEscrowRecord> {
+        env.storage().instance().get(&escrow_id)
+    }
+}
+
+fn price_above(env: &Env, oracle: &Address, threshold: i128, max_age: u64) -> Condition {
+    Condition::PriceAbove(PriceCondition {
+        oracle: oracle.clone(),
+        asset: PriceAsset::Other(Symbol::new(env, "XLM")),
+        threshold,
+        max_age,
+    })
+}
+
+#[test]
+fn ext_price_above_requires_fresh_price() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(10_000);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let oracle = ctx.env.register(MockPriceFeed, ());
+    let feed = MockPriceFeedClient::new(&ctx.env, &oracle);
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![&ctx.env, price_above(&ctx.env, &oracle, 1_000, 300)],
+    );
+
+    // No price published yet.
+    assert_eq!(ctx.client.check_conditions(&id).get(0), Some(false));
+
+    // High enough but stale.
+    feed.set_price(&1_500, &9_000);
+    assert_eq!(ctx.client.check_conditions(&id).get(0), Some(false));
+
+    // Fresh but too low.
+    feed.set_price(&900, &9_900);
+    assert_eq!(ctx.client.check_conditions(&id).get(0), Some(false));
+
+    feed.set_price(&1_000, &9_900);
+    assert_eq!(ctx.client.execute_payment(&id), AMOUNT);
+}
+
+#[test]
+fn ext_price_below_passes_under_threshold() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(10_000);
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let oracle = ctx.env.register(MockPriceFeed, ());
+    MockPriceFeedClient::new(&ctx.env, &oracle).set_price(&500, &10_000);
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::PriceBelow(PriceCondition {
+                oracle: oracle.clone(),
+                asset: PriceAsset::Stellar(ctx.token_id.clone()),
+                threshold: 501,
+                max_age: 60,
+            }),
+        ],
+    );
+
+    assert_eq!(ctx.client.execute_payment(&id), AMOUNT);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn ext_neg_price_zero_max_age() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let oracle = Address::generate(&ctx.env);
+    ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![&ctx.env, price_above(&ctx.env, &oracle, 1_000, 0)],
+    );
+}
+
+#[test]
+fn ext_contract_predicate_pays_when_invoice_delivered() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let invoice = ctx.allowed_invoice();
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::ContractPredicate(PredicateCondition {
+                contract: invoice.clone(),
+                function: Symbol::new(&ctx.env, "is_delivered"),
+                args: vec![&ctx.env, 42u64.into_val(&ctx.env)],
+            }),
+        ],
+    );
+
+    let result = ctx.client.try_execute_payment(&id);
+    assert_eq!(
+        result,
+        Err(Ok(ConditionalPaymentError::ConditionNotMet.into()))
+    );
+
+    MockInvoiceClient::new(&ctx.env, &invoice).mark_delivered(&42);
+    assert_eq!(ctx.client.execute_payment(&id), AMOUNT);
+}
+
+#[test]
+fn ext_contract_predicate_failing_call_is_false() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let invoice = ctx.allowed_invoice();
+
+    // Unknown function: evaluation must not abort.
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::ContractPredicate(PredicateCondition {
+                contract: invoice,
+                function: Symbol::new(&ctx.env, "missing"),
+                args: Vec::new(&ctx.env),
+            }),
+        ],
+    );
+
+    assert_eq!(ctx.client.check_conditions(&id).get(0), Some(false));
+}
+
+#[test]
+fn ext_contract_predicate_trapping_call_is_false() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let invoice = ctx.allowed_invoice();
+
+    // NOT(disputed): a panicking predicate fails its leaf, which the NOT
+    // turns into a pass, so the trap must not revert the evaluation.
+    let tree = vec![
+        &ctx.env,
+        ConditionNode::Leaf(Condition::ContractPredicate(PredicateCondition {
+            contract: invoice,
+            function: Symbol::new(&ctx.env, "is_disputed"),
+            args: vec![&ctx.env, 42u64.into_val(&ctx.env)],
+        })),
+        ConditionNode::Not(0),
+    ];
+    let id = ctx
+        .client
+        .create_payment_with_tree(&payer, &recipient, &ctx.token_id, &AMOUNT, &tree);
+
+    assert_eq!(
+        ctx.client.check_conditions(&id),
+        vec![&ctx.env, false, true]
+    );
+    assert_eq!(ctx.client.execute_payment(&id), AMOUNT);
+}
+
+#[test]
+fn ext_contract_predicate_requires_allowed_contract() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let invoice = ctx.env.register(MockInvoice, ());
+    let predicate = vec![
+        &ctx.env,
+        Condition::ContractPredicate(PredicateCondition {
+            contract: invoice.clone(),
+            function: Symbol::new(&ctx.env, "is_delivered"),
+            args: vec![&ctx.env, 42u64.into_val(&ctx.env)],
+        }),
+    ];
+
+    let result =
+        ctx.client
+            .try_create_payment(&payer, &recipient, &ctx.token_id, &AMOUNT, &predicate);
+    assert_eq!(
+        result,
+        Err(Ok(ConditionalPaymentError::PredicateNotAllowed.into()))
+    );
+
+    ctx.client
+        .set_predicate_contract(&ctx.admin, &invoice, &true);
+    assert!(ctx.client.is_predicate_contract(&invoice));
+    ctx.client
+        .create_payment(&payer, &recipient, &ctx.token_id, &AMOUNT, &predicate);
+}
+
+#[test]
+fn ext_contract_predicate_token_transfer_cannot_drain_escrow() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let victim = ctx.funded_payer(AMOUNT);
+    let victim_id = ctx.create_time_after(&victim, &Address::generate(&ctx.env), 1_000);
+
+    // A predicate that asks the token to move the contract's own funds,
+    // which the token would authorize because this contract is the caller.
+    let attacker = ctx.funded_payer(1);
+    let tree = vec![
+        &ctx.env,
+        ConditionNode::Leaf(Condition::ContractPredicate(PredicateCondition {
+            contract: ctx.token_id.clone(),
+            function: Symbol::new(&ctx.env, "transfer"),
+            args: vec![
+                &ctx.env,
+                ctx.client.address.into_val(&ctx.env),
+                attacker.into_val(&ctx.env),
+                AMOUNT.into_val(&ctx.env),
+            ],
+        })),
+        ConditionNode::Leaf(Condition::TimeBefore(1_000)),
+        ConditionNode::AnyOf(vec![&ctx.env, 0, 1]),
+    ];
+    let result = ctx.client.try_create_payment_with_tree(
+        &attacker,
+        &Address::generate(&ctx.env),
+        &ctx.token_id,
+        &1,
+        &tree,
+    );
+    assert_eq!(
+        result,
+        Err(Ok(ConditionalPaymentError::PredicateNotAllowed.into()))
+    );
+    assert_eq!(ctx.balance(&ctx.client.address), AMOUNT);
+    assert_eq!(ctx.balance(&attacker), 1);
+    assert_eq!(
+        ctx.client.get_payment(&victim_id).status,
+        PaymentStatus::Pending
+    );
+}
+
+#[test]
+fn ext_contract_predicate_disallowed_contract_is_not_called() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let invoice = ctx.allowed_invoice();
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::ContractPredicate(PredicateCondition {
+                contract: invoice.clone(),
+                function: Symbol::new(&ctx.env, "is_delivered"),
+                args: vec![&ctx.env, 42u64.into_val(&ctx.env)],
+            }),
+        ],
+    );
+    MockInvoiceClient::new(&ctx.env, &invoice).mark_delivered(&42);
+    assert_eq!(ctx.client.check_conditions(&id).get(0), Some(true));
+
+    ctx.client
+        .set_predicate_contract(&ctx.admin, &invoice, &false);
+    assert!(!ctx.client.is_predicate_contract(&invoice));
+    assert_eq!(ctx.client.check_conditions(&id).get(0), Some(false));
+    let result = ctx.client.try_execute_payment(&id);
+    assert_eq!(
+        result,
+        Err(Ok(ConditionalPaymentError::ConditionNotMet.into()))
+    );
+}
+
+#[test]
+fn auth_only_admin_sets_predicate_contracts() {
+    let ctx = Ctx::new();
+    let invoice = ctx.env.register(MockInvoice, ());
+    let outsider = Address::generate(&ctx.env);
+
+    let result = ctx
+        .client
+        .try_set_predicate_contract(&outsider, &invoice, &true);
+    assert_eq!(
+        result,
+        Err(Ok(ConditionalPaymentError::Unauthorized.into()))
+    );
+    let result = ctx
+        .client
+        .try_set_predicate_contract(&ctx.admin, &ctx.client.address, &true);
+    assert_eq!(
+        result,
+        Err(Ok(ConditionalPaymentError::InvalidCondition.into()))
+    );
+    assert!(!ctx.client.is_predicate_contract(&invoice));
+}
+
+#[test]
+fn ext_payment_settled_waits_for_earlier_payment() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let payer = ctx.funded_payer(AMOUNT * 2);
+    let recipient = Address::generate(&ctx.env);
+
+    let first = ctx.create_time_after(&payer, &recipient, 1_000);
+    let second = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::PaymentSettled(SettlementCondition {
+                source: SettlementSource::ConditionalPayment(first),
+                require_success: true,
+            }),
+        ],
+    );
+
+    assert_eq!(ctx.client.check_conditions(&second).get(0), Some(false));
+
+    ctx.set_timestamp(1_000);
+    ctx.client.execute_payment(&first);
+    assert_eq!(ctx.client.execute_payment(&second), AMOUNT);
+}
+
+#[test]
+fn ext_payment_settled_require_success_rejects_cancellation() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT * 2);
+    let recipient = Address::generate(&ctx.env);
+
+    let first = ctx.create_time_after(&payer, &recipient, 1_000);
+    let nodes = |require_success| {
+        vec![
+            &ctx.env,
+            Condition::PaymentSettled(SettlementCondition {
+                source: SettlementSource::ConditionalPayment(first),
+                require_success,
+            }),
+        ]
+    };
+    let strict = ctx
+        .client
+        .create_payment(&payer, &recipient, &ctx.token_id, &1, &nodes(true));
+    let lenient = ctx
+        .client
+        .create_payment(&payer, &recipient, &ctx.token_id, &1, &nodes(false));
+
+    ctx.client.cancel_payment(&payer, &first);
+    assert_eq!(ctx.client.check_conditions(&strict).get(0), Some(false));
+    assert_eq!(ctx.client.check_conditions(&lenient).get(0), Some(true));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn ext_neg_payment_settled_unknown_payment() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::PaymentSettled(SettlementCondition {
+                source: SettlementSource::ConditionalPayment(99),
+                require_success: false,
+            }),
+        ],
+    );
+}
+
+#[test]
+fn ext_payment_settled_reads_escrow_status() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let recipient = Address::generate(&ctx.env);
+    let escrow = ctx.env.register(MockEscrow, ());
+    let escrow_client = MockEscrowClient::new(&ctx.env, &escrow);
+    escrow_client.set_status(&7, &EscrowStatus::Active);
+
+    let id = ctx.client.create_payment(
+        &payer,
+        &recipient,
+        &ctx.token_id,
+        &AMOUNT,
+        &vec![
+            &ctx.env,
+            Condition::PaymentSettled(SettlementCondition {
+                source: SettlementSource::Escrow(escrow.clone(), 7),
+                require_success: true,
+            }),
+        ],
+    );
+    assert_eq!(ctx.client.check_conditions(&id).get(0), Some(false));
+
+    escrow_client.set_status(&7, &EscrowStatus::Reversed);
+    assert_eq!(ctx.client.check_conditions(&id).get(0), Some(false));
+
+    escrow_client.set_status(&7, &EscrowStatus::Released);
+    assert_eq!(ctx.client.execute_payment(&id), AMOUNT);
+}
+
+// ── split_*: multi-recipient settlement ──────────────────────────────────────
+
+fn leg(recipient: &Address, share: SplitShare) -> SplitLeg {
+    SplitLeg {
+        recipient: recipient.clone(),
+        share,
+    }
+}
+
+fn time_after_tree(env: &Env, after: u64) -> Vec<ConditionNode> {
+    vec![env, ConditionNode::Leaf(Condition::TimeAfter(after))]
+}
+
+#[test]
+fn split_happy_marketplace_release() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let payer = ctx.funded_payer(AMOUNT);
+    let seller = Address::generate(&ctx.env);
+    let platform = Address::generate(&ctx.env);
+    let courier = Address::generate(&ctx.env);
+
+    // Platform takes 2.5%, the courier a flat 1_500, the seller the rest.
+    let id = ctx.client.create_split_payment(
+        &payer,
+        &seller,
+        &ctx.token_id,
+        &AMOUNT,
+        &time_after_tree(&ctx.env, 1_000),
+        &vec![
+            &ctx.env,
+            leg(&platform, SplitShare::Bps(250)),
+            leg(&courier, SplitShare::Fixed(1_500)),
+        ],
+    );
+    assert_eq!(ctx.client.get_payment(&id).splits.len(), 2);
+
+    ctx.set_timestamp(1_000);
+    assert_eq!(ctx.client.execute_payment(&id), AMOUNT);
+
+    assert_eq!(ctx.balance(&platform), 250);
+    assert_eq!(ctx.balance(&courier), 1_500);
+    assert_eq!(ctx.balance(&seller), AMOUNT - 250 - 1_500);
+    assert_eq!(ctx.balance(&ctx.client.address), 0);
+}
+
+#[test]
+fn split_edge_bps_rounding_dust_goes_to_remainder() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let payer = ctx.funded_payer(1_001);
+    let remainder = Address::generate(&ctx.env);
+    let a = Address::generate(&ctx.env);
+    let b = Address::generate(&ctx.env);
+
+    let id = ctx.client.create_split_payment(
+        &payer,
+        &remainder,
+        &ctx.token_id,
+        &1_001,
+        &time_after_tree(&ctx.env, 1),
+        &vec![
+            &ctx.env,
+            leg(&a, SplitShare::Bps(5_000)),
+            leg(&b, SplitShare::Bps(5_000)),
+        ],
+    );
+
+    ctx.set_timestamp(1);
+    ctx.client.execute_payment(&id);
+    assert_eq!(ctx.balance(&a), 500);
+    assert_eq!(ctx.balance(&b), 500);
+    assert_eq!(ctx.balance(&remainder), 1);
+}
+
+#[test]
+fn split_edge_failed_leg_reverts_every_payout() {
+    let ctx = Ctx::new();
+    ctx.set_timestamp(0);
+    let payer = ctx.funded_payer(AMOUNT);
+    let seller = Address::generate(&ctx.env);
+    let platform = Address::generate(&ctx.env);
+    let courier = Address::generate(&ctx.env);
+
+    let id = ctx.client.create_split_payment(
+        &payer,
+        &seller,
+        &ctx.token_id,
+        &AMOUNT,
+        &time_after_tree(&ctx.env, 1_000),
+        &vec![
+            &ctx.env,
+            leg(&platform, SplitShare::Bps(250)),
+            leg(&courier, SplitShare::Fixed(1_500)),
+        ],
+    );
+
+    // The courier cannot receive the token, so its leg fails after the
+    // platform leg was already transferred.
+    let issuer = token::StellarAssetClient::new(&ctx.env, &ctx.token_id);
+    issuer.set_authorized(&courier, &false);
+
+    ctx.set_timestamp(1_000);
+    assert!(ctx.client.try_execute_payment(&id).is_err());
+    assert_eq!(ctx.balance(&platform), 0);
+    assert_eq!(ctx.balance(&courier), 0);
+    assert_eq!(ctx.balance(&seller), 0);
+    assert_eq!(ctx.balance(&ctx.client.address), AMOUNT);
+    assert_eq!(ctx.client.get_payment(&id).status, PaymentStatus::Pending);
+
+    // Once every leg can settle, the whole split pays out.
+    issuer.set_authorized(&courier, &true);
+    assert_eq!(ctx.client.execute_payment(&id), AMOUNT);
+    assert_eq!(ctx.balance(&platform), 250);
+    assert_eq!(ctx.balance(&courier), 1_500);
+    assert_eq!(ctx.balance(&seller), AMOUNT - 250 - 1_500);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn split_neg_legs_exceed_amount() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let remainder = Address::generate(&ctx.env);
+    ctx.client.create_split_payment(
+        &payer,
+        &remainder,
+        &ctx.token_id,
+        &AMOUNT,
+        &time_after_tree(&ctx.env, 1_000),
+        &vec![
+            &ctx.env,
+            leg(&Address::generate(&ctx.env), SplitShare::Bps(6_000)),
+            leg(&Address::generate(&ctx.env), SplitShare::Fixed(AMOUNT / 2)),
+        ],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn split_neg_zero_share() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let remainder = Address::generate(&ctx.env);
+    ctx.client.create_split_payment(
+        &payer,
+        &remainder,
+        &ctx.token_id,
+        &AMOUNT,
+        &time_after_tree(&ctx.env, 1_000),
+        &vec![
+            &ctx.env,
+            leg(&Address::generate(&ctx.env), SplitShare::Fixed(0)),
+        ],
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn split_neg_too_many_legs() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let remainder = Address::generate(&ctx.env);
+    let mut legs = Vec::new(&ctx.env);
+    for _ in 0..=MAX_SPLIT_LEGS {
+        legs.push_back(leg(&Address::generate(&ctx.env), SplitShare::Fixed(1)));
+    }
+    ctx.client.create_split_payment(
+        &payer,
+        &remainder,
+        &ctx.token_id,
+        &AMOUNT,
+        &time_after_tree(&ctx.env, 1_000),
+        &legs,
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #13)")]
+fn split_neg_leg_pays_payer() {
+    let ctx = Ctx::new();
+    let payer = ctx.funded_payer(AMOUNT);
+    let remainder = Address::generate(&ctx.env);
+    ctx.client.create_split_payment(
+        &payer,
+        &remainder,
+        &ctx.token_id,
+        &AMOUNT,
+        &time_after_tree(&ctx.env, 1_000),
+        &vec![&ctx.env, leg(&payer, SplitShare::Bps(100))],
+    );
+}
@@ -7,12 +7,15 @@
 mod types;
 mod validation;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, Env, Vec};
+use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, BytesN, Env, Vec};
 
 pub use crate::types::{
-    BatchReleaseResult, BatchReversalResult, DataKey, Escrow, EscrowEvents, EscrowStatus,
-    ReleaseRequest, ReleaseResult, ReversalRequest, ReversalResult, MAX_BATCH_SIZE,
+    BatchReleaseResult, BatchReversalResult, DataKey, Dispute, DisputeRuling, Escrow, EscrowEvents,
+    EscrowStatus, Milestone, MilestoneRequest, MilestoneStatus, ReleaseRequest, ReleaseResult,
+    ReversalRequest, ReversalResult, SweepResult, BPS_DENOMINATOR, DEFAULT_DISPUTE_RESPONSE_WINDOW,
+    MAX_ARBITER_FEE_BPS, MAX_BATCH_SIZE, MAX_MILESTONES,
 };
+use crate::validation::validate_milestone;
 use crate::validation::validate_release;
 use crate::validation::validate_reversal;
 
@@ -34,6 +37,24 @@ pub enum EscrowError {
     EscrowNotFound = 6,
     /// Contract already initialized
     AlreadyInitialized = 7,
+    /// Escrow is not in a state that allows this dispute action
+    InvalidEscrowState = 8,
+    /// Escrow has no arbiter to rule on a dispute
+    NoArbiter = 9,
+    /// No dispute exists for the escrow
+    DisputeNotFound = 10,
+    /// Counterparty response window is still open
+    ResponseWindowOpen = 11,
+    /// Response window has already closed
+    ResponseWindowClosed = 12,
+    /// Basis points value is out of range
+    InvalidBasisPoints = 13,
+    /// Milestone is missing, out of order or past its deadline
+    InvalidMilestone = 14,
+    /// Token is not on the accepted asset allowlist
+    TokenNotAllowed = 15,
+    /// Escrow deadline and grace period have not yet passed
+    NotExpired = 16,
 }
 
 impl From<EscrowError> for soroban_sdk::Error {
@@ -47,14 +68,14 @@ pub struct EscrowContract;
 
 #[contractimpl]
 impl EscrowContract {
-    /// Initializes the contract with an admin address and token.
+    /// Initializes the contract with an admin address and the first accepted token.
     pub fn initialize(env: Env, admin: Address, token: Address) {
         if env.storage().instance().has(&DataKey::Admin) {
             panic_with_error!(&env, EscrowError::AlreadyInitialized);
         }
 
         env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage().instance().set(&DataKey::Token, &token);
+        Self::allow_token(&env, &token);
         env.storage().instance().set(&DataKey::EscrowCounter, &0u64);
         env.storage()
             .instance()
@@ -84,6 +105,7 @@ impl EscrowContract {
         depositor: Address,
         recipient: Address,
         arbiter: Option<Address>,
+        token: Address,
         amount: i128,
         deadline: u64,
     ) -> u64 {
@@ -95,63 +117,77 @@ impl EscrowContract {
             panic_with_error!(&env, EscrowError::InvalidAmount);
         }
 
-        // Get token and transfer funds to contract
-        let token: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Token)
-            .unwrap_or_else(|| panic_with_error!(&env, EscrowError::NotInitialized));
-        let token_client = token::Client::new(&env, &token);
-
-        // Transfer funds from depositor to this contract
-        token_client.transfer(&depositor, &env.current_contract_address(), &amount);
-
-        // Get and increment escrow counter with overflow protection
-        let current_counter: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::EscrowCounter)
-            .unwrap_or(0);
-        let escrow_id: u64 = current_counter
-            .checked_add(1)
-            .unwrap_or_else(|| panic_with_error!(&env, EscrowError::InvalidAmount));
-        env.storage()
-            .instance()
-            .set(&DataKey::EscrowCounter, &escrow_id);
-
-        // Create escrow record
         let escrow = Escrow {
-            escrow_id,
-            depositor: depositor.clone(),
-            recipient: recipient.clone(),
-            arbiter: arbiter.clone(),
-            token: token.clone(),
+            escrow_id: 0,
+            depositor,
+            recipient,
+            arbiter,
+            token,
             amount,
             status: EscrowStatus::Active,
             created_at: env.ledger().sequence() as u64,
             deadline,
+            milestones: Vec::new(&env),
+            released_amount: 0,
+            grace_period: 0,
         };
+        Self::open_escrow(&env, escrow)
+    }
 
-        // Store escrow
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+    /// Creates a new escrow paid out in ordered milestone tranches.
+    ///
+    /// The escrowed amount is the sum of all milestone amounts. Each tranche
+    /// is paid to the recipient when the depositor or arbiter approves it.
+    pub fn create_milestone_escrow(
+        env: Env,
+        depositor: Address,
+        recipient: Address,
+        arbiter: Option<Address>,
+        token: Address,
+        milestones: Vec<MilestoneRequest>,
+        deadline: u64,
+    ) -> u64 {
+        depositor.require_auth();
 
-        // Update user escrows list
-        let mut user_escrows: Vec<u64> = env
-            .storage()
-            .persistent()
-            .get(&DataKey::UserEscrows(depositor.clone()))
-            .unwrap_or(Vec::new(&env));
-        user_escrows.push_back(escrow_id);
-        env.storage()
-            .persistent()
-            .set(&DataKey::UserEscrows(depositor.clone()), &user_escrows);
+        if milestones.is_empty() {
+            panic_with_error!(&env, EscrowError::EmptyBatch);
+        }
+        if milestones.len() > MAX_MILESTONES {
+            panic_with_error!(&env, EscrowError::BatchTooLarge);
+        }
 
-        // Emit event
-        EscrowEvents::escrow_created(&env, escrow_id, &depositor, &recipient, &arbiter, amount);
+        let mut amount: i128 = 0;
+        let mut stored: Vec<Milestone> = Vec::new(&env);
+        for request in milestones.iter() {
+            if request.amount <= 0 {
+                panic_with_error!(&env, EscrowError::InvalidAmount);
+            }
+            amount = amount
+                .checked_add(request.amount)
+                .unwrap_or_else(|| panic_with_error!(&env, EscrowError::InvalidAmount));
+            stored.push_back(Milestone {
+                amount: request.amount,
+                description_hash: request.description_hash,
+                deadline: request.deadline,
+                status: MilestoneStatus::Pending,
+            });
+        }
 
-        escrow_id
+        let escrow = Escrow {
+            escrow_id: 0,
+            depositor,
+            recipient,
+            arbiter,
+            token,
+            amount,
+            status: EscrowStatus::Active,
+            created_at: env.ledger().sequence() as u64,
+            deadline,
+            milestones: stored,
+            released_amount: 0,
+            grace_period: 0,
+        };
+        Self::open_escrow(&env, escrow)
     }
 
     /// Batch reverses multiple escrows.
@@ -191,18 +227,12 @@ impl EscrowContract {
             .unwrap_or(0)
             + 1;
 
-        // Get admin and token for validation
+        // Get admin for validation
         let admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
             .unwrap_or_else(|| panic_with_error!(&env, EscrowError::NotInitialized));
-        let token: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Token)
-            .unwrap_or_else(|| panic_with_error!(&env, EscrowError::NotInitialized));
-        let token_client = token::Client::new(&env, &token);
 
         let current_ledger = env.ledger().sequence() as u64;
 
@@ -248,71 +278,20 @@ impl EscrowContract {
 
             // Get the escrow (safe to unwrap as validation passed)
             let mut escrow = escrow_opt.clone().unwrap();
-
-            // Transfer funds back to depositor
-            token_client.transfer(
-                &env.current_contract_address(),
-                &escrow.depositor,
-                &escrow.amount,
-            );
-
-            // Update escrow status
-            escrow.status = EscrowStatus::Reversed;
-            env.storage()
-                .persistent()
-                .set(&DataKey::Escrow(escrow.escrow_id), &escrow);
+            let amount = Self::reverse_escrow(&env, &mut escrow, batch_id);
 
             // Record success
             results.push_back(ReversalResult::Success(
                 escrow.escrow_id,
                 escrow.depositor.clone(),
-                escrow.amount,
+                amount,
             ));
             successful_count += 1;
-            total_reversed = total_reversed
-                .checked_add(escrow.amount)
-                .unwrap_or(total_reversed);
-
-            // Emit success event
-            EscrowEvents::reversal_success(
-                &env,
-                batch_id,
-                escrow.escrow_id,
-                &escrow.depositor,
-                escrow.amount,
-            );
+            total_reversed = total_reversed.checked_add(amount).unwrap_or(total_reversed);
         }
 
         // Update storage statistics
-        let total_batches: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalReversalBatches)
-            .unwrap_or(0);
-        let total_escrows_reversed: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalEscrowsReversed)
-            .unwrap_or(0);
-        let total_amount_reversed: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalAmountReversed)
-            .unwrap_or(0);
-
-        env.storage()
-            .instance()
-            .set(&DataKey::TotalReversalBatches, &(total_batches + 1));
-        env.storage().instance().set(
-            &DataKey::TotalEscrowsReversed,
-            &(total_escrows_reversed + successful_count as u64),
-        );
-        env.storage().instance().set(
-            &DataKey::TotalAmountReversed,
-            &total_amount_reversed
-                .checked_add(total_reversed)
-                .unwrap_or(i128::MAX),
-        );
+        Self::record_reversals(&env, 1, successful_count);
 
         // Emit batch completed event
         EscrowEvents::batch_reversal_completed(
@@ -337,6 +316,7 @@ impl EscrowContract {
     ///
     /// Caller must be authenticated. Each request is validated individually:
     /// admin may release any escrow, depositor may release their own.
+    /// Requests naming a milestone release only that tranche.
     pub fn batch_release_escrows(
         env: Env,
         caller: Address,
@@ -367,12 +347,6 @@ impl EscrowContract {
             .instance()
             .get(&DataKey::Admin)
             .unwrap_or_else(|| panic_with_error!(&env, EscrowError::NotInitialized));
-        let token: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Token)
-            .unwrap_or_else(|| panic_with_error!(&env, EscrowError::NotInitialized));
-        let token_client = token::Client::new(&env, &token);
 
         // Emit batch started event
         EscrowEvents::batch_release_started(&env, batch_id, request_count);
@@ -382,6 +356,7 @@ impl EscrowContract {
         let mut successful_count: u32 = 0;
         let mut failed_count: u32 = 0;
         let mut total_released: i128 = 0;
+        let mut escrows_completed: u32 = 0;
 
         // First pass: validate
         let mut validated_requests: Vec<(ReleaseRequest, Option<Escrow>, bool, u32)> =
@@ -393,7 +368,14 @@ impl EscrowContract {
                 .persistent()
                 .get(&DataKey::Escrow(request.escrow_id));
 
-            let validation_result = validate_release(escrow_opt.as_ref(), &caller, &admin);
+            let current_ledger = env.ledger().sequence() as u64;
+            let validation_result = validate_release(escrow_opt.as_ref(), &caller, &admin)
+                .and_then(|()| match (request.milestone, escrow_opt.as_ref()) {
+                    (Some(index), Some(escrow)) => {
+                        validate_milestone(escrow, index, current_ledger).map(|_| ())
+                    }
+                    _ => Ok(()),
+                });
 
             let (is_valid, error_code) = match validation_result {
                 Ok(()) => (true, 0u32),
@@ -414,35 +396,42 @@ impl EscrowContract {
 
             let mut escrow = escrow_opt.clone().unwrap();
 
-            // Transfer to recipient
-            token_client.transfer(
-                &env.current_contract_address(),
-                &escrow.recipient,
-                &escrow.amount,
-            );
-
-            // Update status
-            escrow.status = EscrowStatus::Released;
-            env.storage()
-                .persistent()
-                .set(&DataKey::Escrow(escrow.escrow_id), &escrow);
+            // Pay either the named milestone or the whole remaining amount
+            let amount = match request.milestone {
+                Some(index) => {
+                    let milestone = escrow.milestones.get(index).unwrap();
+                    let amount = milestone.amount;
+                    Self::release_milestone(&env, &mut escrow, index, milestone);
+                    amount
+                }
+                None => {
+                    let amount = escrow.remaining_amount();
+                    Self::release_funds(&env, &escrow.token, &escrow.recipient, amount);
+                    escrow.mark_fully_released();
+                    env.storage()
+                        .persistent()
+                        .set(&DataKey::Escrow(escrow.escrow_id), &escrow);
+                    amount
+                }
+            };
+            if escrow.status == EscrowStatus::Released {
+                escrows_completed += 1;
+            }
 
             results.push_back(ReleaseResult::Success(
                 escrow.escrow_id,
                 escrow.recipient.clone(),
-                escrow.amount,
+                amount,
             ));
             successful_count += 1;
-            total_released = total_released
-                .checked_add(escrow.amount)
-                .unwrap_or(total_released);
+            total_released = total_released.checked_add(amount).unwrap_or(total_released);
 
             EscrowEvents::release_success(
                 &env,
                 batch_id,
                 escrow.escrow_id,
                 &escrow.recipient,
-                escrow.amount,
+                amount,
             );
         }
 
@@ -457,24 +446,12 @@ impl EscrowContract {
             .instance()
             .get(&DataKey::TotalEscrowsReleased)
             .unwrap_or(0);
-        let total_amount_released: i128 = env
-            .storage()
-            .instance()
-            .get(&DataKey::TotalAmountReleased)
-            .unwrap_or(0);
-
         env.storage()
             .instance()
             .set(&DataKey::TotalReleaseBatches, &(total_batches + 1));
         env.storage().instance().set(
             &DataKey::TotalEscrowsReleased,
-            &(total_escrows_released + successful_count as u64),
-        );
-        env.storage().instance().set(
-            &DataKey::TotalAmountReleased,
-            &total_amount_released
-                .checked_add(total_released)
-                .unwrap_or(i128::MAX),
+            &(total_escrows_released + escrows_completed as u64),
         );
 
         EscrowEvents::batch_release_completed(
@@ -495,6 +472,139 @@ impl EscrowContract {
         }
     }
 
+    /// Refunds an expired escrow to its depositor.
+    ///
+    /// Available once the ledger passes the escrow deadline plus its grace
+    /// period. Emits the standard reversal event with batch ID 0.
+    pub fn claim_expired(env: Env, depositor: Address, escrow_id: u64) -> i128 {
+        depositor.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, EscrowError::EscrowNotFound));
+
+        if depositor != escrow.depositor {
+            panic_with_error!(&env, EscrowError::Unauthorized);
+        }
+        if escrow.status != EscrowStatus::Active {
+            panic_with_error!(&env, EscrowError::InvalidEscrowState);
+        }
+        if !escrow.is_expired(env.ledger().sequence() as u64) {
+            panic_with_error!(&env, EscrowError::NotExpired);
+        }
+
+        let amount = Self::reverse_escrow(&env, &mut escrow, 0);
+        Self::record_reversals(&env, 0, 1);
+
+        amount
+    }
+
+    /// Reverses expired escrows, scanning up to `limit` IDs after `cursor`.
+    ///
+    /// Callable by anyone since funds only ever return to depositors. Keepers
+    /// resume from `next_cursor` until `done` is true. A sweep that reverses
+    /// nothing emits no batch events and is not counted as a batch.
+    pub fn sweep_expired(env: Env, cursor: u64, limit: u32) -> SweepResult {
+        if limit == 0 {
+            panic_with_error!(&env, EscrowError::EmptyBatch);
+        }
+        if limit > MAX_BATCH_SIZE {
+            panic_with_error!(&env, EscrowError::BatchTooLarge);
+        }
+
+        let escrow_count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EscrowCounter)
+            .unwrap_or(0);
+        let end = cursor.saturating_add(limit as u64).min(escrow_count);
+
+        let batch_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalReversalBatches)
+            .unwrap_or(0)
+            + 1;
+        let current_ledger = env.ledger().sequence() as u64;
+        let scanned = end.saturating_sub(cursor) as u32;
+
+        let mut results: Vec<ReversalResult> = Vec::new(&env);
+        let mut total_reversed: i128 = 0;
+
+        for escrow_id in (cursor + 1)..=end {
+            let escrow_opt: Option<Escrow> =
+                env.storage().persistent().get(&DataKey::Escrow(escrow_id));
+            let mut escrow = match escrow_opt {
+                Some(escrow)
+                    if escrow.status == EscrowStatus::Active
+                        && escrow.is_expired(current_ledger) =>
+                {
+                    escrow
+                }
+                _ => continue,
+            };
+
+            // Only open a batch once there is something to reverse
+            if results.is_empty() {
+                EscrowEvents::batch_reversal_started(&env, batch_id, scanned);
+            }
+            let amount = Self::reverse_escrow(&env, &mut escrow, batch_id);
+            results.push_back(ReversalResult::Success(
+                escrow.escrow_id,
+                escrow.depositor.clone(),
+                amount,
+            ));
+            total_reversed = total_reversed.checked_add(amount).unwrap_or(total_reversed);
+        }
+
+        let reversed = results.len();
+        if reversed > 0 {
+            Self::record_reversals(&env, 1, reversed);
+            EscrowEvents::batch_reversal_completed(&env, batch_id, reversed, 0, total_reversed);
+        }
+
+        SweepResult {
+            batch_id: if reversed > 0 { batch_id } else { 0 },
+            scanned,
+            reversed,
+            total_reversed,
+            next_cursor: end.max(cursor),
+            done: end >= escrow_count,
+            results,
+        }
+    }
+
+    /// Extends the grace period after the deadline during which an escrow
+    /// cannot be claimed as expired and the recipient can still be paid.
+    ///
+    /// Only the depositor may set it, and it can only be lengthened.
+    pub fn set_grace_period(env: Env, depositor: Address, escrow_id: u64, grace_period: u64) {
+        depositor.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, EscrowError::EscrowNotFound));
+
+        if depositor != escrow.depositor {
+            panic_with_error!(&env, EscrowError::Unauthorized);
+        }
+        if escrow.status != EscrowStatus::Active {
+            panic_with_error!(&env, EscrowError::InvalidEscrowState);
+        }
+        if grace_period < escrow.grace_period {
+            panic_with_error!(&env, EscrowError::InvalidAmount);
+        }
+
+        escrow.grace_period = grace_period;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow_id), &escrow);
+    }
+
     /// Releases an escrow to the recipient.
     ///
     /// Can only be called by admin or depositor.
@@ -531,23 +641,334 @@ impl EscrowContract {
             panic_with_error!(&env, EscrowError::Unauthorized);
         }
 
-        // Transfer funds to recipient
-        let token_client = token::Client::new(&env, &escrow.token);
-        token_client.transfer(
-            &env.current_contract_address(),
-            &escrow.recipient,
-            &escrow.amount,
-        );
+        // Transfer the remaining funds to recipient
+        let amount = escrow.remaining_amount();
+        Self::release_funds(&env, &escrow.token, &escrow.recipient, amount);
 
         // Update escrow status
         let mut updated_escrow = escrow.clone();
-        updated_escrow.status = EscrowStatus::Released;
+        updated_escrow.mark_fully_released();
         env.storage()
             .persistent()
             .set(&DataKey::Escrow(escrow_id), &updated_escrow);
 
         // Emit event
-        EscrowEvents::escrow_released(&env, escrow_id, &escrow.recipient, escrow.amount);
+        EscrowEvents::escrow_released(&env, escrow_id, &escrow.recipient, amount);
+    }
+
+    /// Approves the next pending milestone and pays its tranche to the recipient.
+    ///
+    /// Can only be called by the depositor or arbiter. Milestones are approved
+    /// in order; once the last one is paid the escrow becomes `Released`.
+    pub fn approve_milestone(env: Env, caller: Address, escrow_id: u64, milestone: u32) {
+        caller.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, EscrowError::EscrowNotFound));
+
+        let is_depositor = caller == escrow.depositor;
+        let is_arbiter = escrow.arbiter.as_ref() == Some(&caller);
+        if !is_depositor && !is_arbiter {
+            panic_with_error!(&env, EscrowError::Unauthorized);
+        }
+        if escrow.status != EscrowStatus::Active {
+            panic_with_error!(&env, EscrowError::InvalidEscrowState);
+        }
+
+        let current_ledger = env.ledger().sequence() as u64;
+        let approved = validate_milestone(&escrow, milestone, current_ledger)
+            .unwrap_or_else(|_| panic_with_error!(&env, EscrowError::InvalidMilestone));
+
+        Self::release_milestone(&env, &mut escrow, milestone, approved);
+    }
+
+    /// Opens a dispute on an active escrow.
+    ///
+    /// Either the depositor or the recipient may open a dispute, which freezes
+    /// the escrow in `Disputed` status until the arbiter rules. The escrow must
+    /// have an arbiter assigned.
+    pub fn open_dispute(env: Env, caller: Address, escrow_id: u64, evidence_hash: BytesN<32>) {
+        caller.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, EscrowError::EscrowNotFound));
+
+        if caller != escrow.depositor && caller != escrow.recipient {
+            panic_with_error!(&env, EscrowError::Unauthorized);
+        }
+        if escrow.status != EscrowStatus::Active {
+            panic_with_error!(&env, EscrowError::InvalidEscrowState);
+        }
+        if escrow.arbiter.is_none() {
+            panic_with_error!(&env, EscrowError::NoArbiter);
+        }
+
+        let current_ledger = env.ledger().sequence() as u64;
+        let response_deadline =
+            current_ledger.saturating_add(Self::get_dispute_response_window(env.clone()));
+
+        let dispute = Dispute {
+            escrow_id,
+            opened_by: caller.clone(),
+            evidence_hash: evidence_hash.clone(),
+            response_hash: None,
+            opened_at: current_ledger,
+            response_deadline,
+        };
+
+        escrow.status = EscrowStatus::Disputed;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow_id), &escrow);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Dispute(escrow_id), &dispute);
+
+        EscrowEvents::dispute_opened(&env, escrow_id, &caller, &evidence_hash, response_deadline);
+    }
+
+    /// Submits the counterparty's evidence for an open dispute.
+    ///
+    /// Only the party that did not open the dispute may respond, and only
+    /// before the response window closes.
+    pub fn respond_to_dispute(
+        env: Env,
+        caller: Address,
+        escrow_id: u64,
+        response_hash: BytesN<32>,
+    ) {
+        caller.require_auth();
+
+        let escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, EscrowError::EscrowNotFound));
+        if escrow.status != EscrowStatus::Disputed {
+            panic_with_error!(&env, EscrowError::InvalidEscrowState);
+        }
+
+        let mut dispute: Dispute = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Dispute(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, EscrowError::DisputeNotFound));
+
+        let is_party = caller == escrow.depositor || caller == escrow.recipient;
+        if !is_party || caller == dispute.opened_by || dispute.response_hash.is_some() {
+            panic_with_error!(&env, EscrowError::Unauthorized);
+        }
+        if env.ledger().sequence() as u64 > dispute.response_deadline {
+            panic_with_error!(&env, EscrowError::ResponseWindowClosed);
+        }
+
+        dispute.response_hash = Some(response_hash.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Dispute(escrow_id), &dispute);
+
+        EscrowEvents::dispute_responded(&env, escrow_id, &caller, &response_hash);
+    }
+
+    /// Settles a disputed escrow according to the arbiter's ruling.
+    ///
+    /// The arbiter fee is taken from the escrowed amount first; the remainder
+    /// is split with `depositor_bps` going back to the depositor and the rest
+    /// to the recipient. The arbiter may rule once the counterparty has
+    /// responded or the response window has elapsed.
+    pub fn resolve_dispute(
+        env: Env,
+        arbiter: Address,
+        escrow_id: u64,
+        depositor_bps: u32,
+    ) -> DisputeRuling {
+        arbiter.require_auth();
+
+        if depositor_bps > BPS_DENOMINATOR {
+            panic_with_error!(&env, EscrowError::InvalidBasisPoints);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, EscrowError::EscrowNotFound));
+        if escrow.status != EscrowStatus::Disputed {
+            panic_with_error!(&env, EscrowError::InvalidEscrowState);
+        }
+        if escrow.arbiter.as_ref() != Some(&arbiter) {
+            panic_with_error!(&env, EscrowError::Unauthorized);
+        }
+
+        let dispute: Dispute = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Dispute(escrow_id))
+            .unwrap_or_else(|| panic_with_error!(&env, EscrowError::DisputeNotFound));
+
+        let current_ledger = env.ledger().sequence() as u64;
+        if dispute.response_hash.is_none() && current_ledger <= dispute.response_deadline {
+            panic_with_error!(&env, EscrowError::ResponseWindowOpen);
+        }
+
+        // Compute the split; rounding dust goes to the recipient
+        let fee_bps = Self::get_arbiter_fee_bps(env.clone());
+        let disputed_amount = escrow.remaining_amount();
+        let arbiter_fee = disputed_amount * fee_bps as i128 / BPS_DENOMINATOR as i128;
+        let distributable = disputed_amount - arbiter_fee;
+        let depositor_amount = distributable * depositor_bps as i128 / BPS_DENOMINATOR as i128;
+        let recipient_amount = distributable - depositor_amount;
+
+        // The depositor's share counts as reversed, everything else as released
+        if arbiter_fee > 0 {
+            Self::release_funds(&env, &escrow.token, &arbiter, arbiter_fee);
+        }
+        if depositor_amount > 0 {
+            Self::refund_funds(&env, &escrow.token, &escrow.depositor, depositor_amount);
+        }
+        if recipient_amount > 0 {
+            Self::release_funds(&env, &escrow.token, &escrow.recipient, recipient_amount);
+        }
+
+        let ruling = DisputeRuling {
+            depositor_bps,
+            depositor_amount,
+            recipient_amount,
+            arbiter_fee,
+            ruled_at: current_ledger,
+        };
+
+        escrow.status = EscrowStatus::Resolved;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow_id), &escrow);
+        env.storage()
+            .persistent()
+            .set(&DataKey::DisputeRuling(escrow_id), &ruling);
+
+        let total_resolved: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalDisputesResolved)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalDisputesResolved, &(total_resolved + 1));
+
+        EscrowEvents::dispute_resolved(&env, escrow_id, &arbiter, &ruling);
+
+        ruling
+    }
+
+    /// Updates the dispute response window (in ledgers) and arbiter fee.
+    pub fn set_dispute_config(
+        env: Env,
+        admin: Address,
+        response_window: u64,
+        arbiter_fee_bps: u32,
+    ) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        if arbiter_fee_bps > MAX_ARBITER_FEE_BPS {
+            panic_with_error!(&env, EscrowError::InvalidBasisPoints);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::DisputeResponseWindow, &response_window);
+        env.storage()
+            .instance()
+            .set(&DataKey::ArbiterFeeBps, &arbiter_fee_bps);
+    }
+
+    /// Returns the dispute record for an escrow, if any.
+    pub fn get_dispute(env: Env, escrow_id: u64) -> Option<Dispute> {
+        env.storage().persistent().get(&DataKey::Dispute(escrow_id))
+    }
+
+    /// Returns the arbiter ruling for a resolved dispute, if any.
+    pub fn get_dispute_ruling(env: Env, escrow_id: u64) -> Option<DisputeRuling> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DisputeRuling(escrow_id))
+    }
+
+    /// Returns the dispute response window in ledgers.
+    pub fn get_dispute_response_window(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::DisputeResponseWindow)
+            .unwrap_or(DEFAULT_DISPUTE_RESPONSE_WINDOW)
+    }
+
+    /// Returns the arbiter fee in basis points.
+    pub fn get_arbiter_fee_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::ArbiterFeeBps)
+            .unwrap_or(0)
+    }
+
+    /// Returns the total number of disputes resolved by arbiter ruling.
+    pub fn get_total_disputes_resolved(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalDisputesResolved)
+            .unwrap_or(0)
+    }
+
+    /// Adds a token to the list of assets accepted for new escrows.
+    pub fn add_allowed_token(env: Env, admin: Address, token: Address) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+        Self::allow_token(&env, &token);
+    }
+
+    /// Removes a token from the allowlist.
+    ///
+    /// Existing escrows in that token can still be released or reversed.
+    pub fn remove_allowed_token(env: Env, admin: Address, token: Address) {
+        admin.require_auth();
+        Self::require_admin(&env, &admin);
+
+        env.storage()
+            .instance()
+            .remove(&DataKey::AllowedToken(token.clone()));
+
+        let tokens: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AllowedTokens)
+            .unwrap_or(Vec::new(&env));
+        let mut remaining: Vec<Address> = Vec::new(&env);
+        for allowed in tokens.iter() {
+            if allowed != token {
+                remaining.push_back(allowed);
+            }
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AllowedTokens, &remaining);
+    }
+
+    /// Returns true if new escrows may be created in the given token.
+    pub fn is_token_allowed(env: Env, token: Address) -> bool {
+        env.storage().instance().has(&DataKey::AllowedToken(token))
+    }
+
+    /// Returns all tokens currently accepted for new escrows.
+    pub fn get_allowed_tokens(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::AllowedTokens)
+            .unwrap_or(Vec::new(&env))
     }
 
     /// Returns an escrow by ID.
@@ -594,7 +1015,7 @@ impl EscrowContract {
             .unwrap_or(0)
     }
 
-    /// Returns the total amount reversed.
+    /// Returns the total amount reversed, summed across all tokens.
     pub fn get_total_amount_reversed(env: Env) -> i128 {
         env.storage()
             .instance()
@@ -602,6 +1023,14 @@ impl EscrowContract {
             .unwrap_or(0)
     }
 
+    /// Returns the total amount reversed in a specific token.
+    pub fn get_token_amount_reversed(env: Env, token: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TokenAmountReversed(token))
+            .unwrap_or(0)
+    }
+
     /// Returns the total number of release batches processed.
     pub fn get_total_release_batches(env: Env) -> u64 {
         env.storage()
@@ -618,7 +1047,7 @@ impl EscrowContract {
             .unwrap_or(0)
     }
 
-    /// Returns the total amount released.
+    /// Returns the total amount released, summed across all tokens.
     pub fn get_total_amount_released(env: Env) -> i128 {
         env.storage()
             .instance()
@@ -626,6 +1055,14 @@ impl EscrowContract {
             .unwrap_or(0)
     }
 
+    /// Returns the total amount released in a specific token.
+    pub fn get_token_amount_released(env: Env, token: Address) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TokenAmountReleased(token))
+            .unwrap_or(0)
+    }
+
     /// Returns the escrow counter (total escrows created).
     pub fn get_escrow_counter(env: Env) -> u64 {
         env.storage()
@@ -634,6 +1071,171 @@ impl EscrowContract {
             .unwrap_or(0)
     }
 
+    // Internal helper to take custody of funds and store a new escrow
+    fn open_escrow(env: &Env, mut escrow: Escrow) -> u64 {
+        if !Self::is_token_allowed(env.clone(), escrow.token.clone()) {
+            panic_with_error!(env, EscrowError::TokenNotAllowed);
+        }
+
+        // Transfer funds from depositor to this contract
+        let token_client = token::Client::new(env, &escrow.token);
+        token_client.transfer(
+            &escrow.depositor,
+            &env.current_contract_address(),
+            &escrow.amount,
+        );
+
+        // Get and increment escrow counter with overflow protection
+        let current_counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EscrowCounter)
+            .unwrap_or(0);
+        let escrow_id: u64 = current_counter
+            .checked_add(1)
+            .unwrap_or_else(|| panic_with_error!(env, EscrowError::InvalidAmount));
+        env.storage()
+            .instance()
+            .set(&DataKey::EscrowCounter, &escrow_id);
+
+        // Store escrow
+        escrow.escrow_id = escrow_id;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow_id), &escrow);
+
+        // Update user escrows list
+        let mut user_escrows: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::UserEscrows(escrow.depositor.clone()))
+            .unwrap_or(Vec::new(env));
+        user_escrows.push_back(escrow_id);
+        env.storage().persistent().set(
+            &DataKey::UserEscrows(escrow.depositor.clone()),
+            &user_escrows,
+        );
+
+        // Emit event
+        EscrowEvents::escrow_created(
+            env,
+            escrow_id,
+            &escrow.depositor,
+            &escrow.recipient,
+            &escrow.arbiter,
+            escrow.amount,
+        );
+
+        escrow_id
+    }
+
+    // Internal helper to add a token to the allowlist
+    fn allow_token(env: &Env, token: &Address) {
+        let key = DataKey::AllowedToken(token.clone());
+        if env.storage().instance().has(&key) {
+            return;
+        }
+        env.storage().instance().set(&key, &true);
+
+        let mut tokens: Vec<Address> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AllowedTokens)
+            .unwrap_or(Vec::new(env));
+        tokens.push_back(token.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::AllowedTokens, &tokens);
+    }
+
+    // Internal helper to refund the unreleased funds of a validated escrow
+    fn reverse_escrow(env: &Env, escrow: &mut Escrow, batch_id: u64) -> i128 {
+        // Transfer the unreleased funds back to depositor in the escrow's token
+        let amount = escrow.remaining_amount();
+        Self::refund_funds(env, &escrow.token, &escrow.depositor, amount);
+
+        // Update escrow status
+        escrow.status = EscrowStatus::Reversed;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow.escrow_id), escrow);
+
+        EscrowEvents::reversal_success(env, batch_id, escrow.escrow_id, &escrow.depositor, amount);
+
+        amount
+    }
+
+    // Internal helper to update the reversal batch and escrow counts
+    fn record_reversals(env: &Env, batches: u64, escrows: u32) {
+        let total_batches: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalReversalBatches)
+            .unwrap_or(0);
+        let total_escrows_reversed: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalEscrowsReversed)
+            .unwrap_or(0);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalReversalBatches, &(total_batches + batches));
+        env.storage().instance().set(
+            &DataKey::TotalEscrowsReversed,
+            &(total_escrows_reversed + escrows as u64),
+        );
+    }
+
+    // Internal helper to pay escrowed funds out and count them as released
+    fn release_funds(env: &Env, token: &Address, to: &Address, amount: i128) {
+        token::Client::new(env, token).transfer(&env.current_contract_address(), to, &amount);
+        Self::add_total(env, DataKey::TokenAmountReleased(token.clone()), amount);
+        Self::add_total(env, DataKey::TotalAmountReleased, amount);
+    }
+
+    // Internal helper to return escrowed funds to the depositor and count them as reversed
+    fn refund_funds(env: &Env, token: &Address, depositor: &Address, amount: i128) {
+        token::Client::new(env, token).transfer(
+            &env.current_contract_address(),
+            depositor,
+            &amount,
+        );
+        Self::add_total(env, DataKey::TokenAmountReversed(token.clone()), amount);
+        Self::add_total(env, DataKey::TotalAmountReversed, amount);
+    }
+
+    // Internal helper to accumulate an amount statistic
+    fn add_total(env: &Env, key: DataKey, amount: i128) {
+        let total: i128 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&key, &total.checked_add(amount).unwrap_or(i128::MAX));
+    }
+
+    // Internal helper to pay out a validated milestone and persist the escrow
+    fn release_milestone(env: &Env, escrow: &mut Escrow, index: u32, mut milestone: Milestone) {
+        Self::release_funds(env, &escrow.token, &escrow.recipient, milestone.amount);
+
+        milestone.status = MilestoneStatus::Released;
+        escrow.milestones.set(index, milestone.clone());
+        escrow.released_amount += milestone.amount;
+        if escrow.next_pending_milestone().is_none() {
+            escrow.status = EscrowStatus::Released;
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow.escrow_id), escrow);
+
+        EscrowEvents::milestone_released(
+            env,
+            escrow.escrow_id,
+            index,
+            &escrow.recipient,
+            milestone.amount,
+        );
+    }
+
     // Internal helper to verify admin
     fn require_admin(env: &Env, caller: &Address) {
         let admin: Address = env
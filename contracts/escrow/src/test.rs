@@ -2,10 +2,13 @@
 
 #![cfg(test)]
 
-use crate::{EscrowContract, EscrowContractClient, EscrowStatus, ReversalRequest, ReversalResult};
+use crate::{
+    EscrowContract, EscrowContractClient, EscrowStatus, MilestoneRequest, MilestoneStatus,
+    ReleaseRequest, ReleaseResult, ReversalRequest, ReversalResult, MAX_ARBITER_FEE_BPS,
+};
 use soroban_sdk::{
     testutils::{Address as _, Events as _, Ledger},
-    token, Address, Env, Vec,
+    token, Address, BytesN, Env, Vec,
 };
 
 /// Creates a test environment with the contract deployed and initialized.
@@ -66,7 +69,64 @@ fn create_test_escrow(
     token_admin.mint(depositor, &amount);
 
     // Create escrow
-    client.create_escrow(depositor, recipient, &None, &amount, &deadline)
+    client.create_escrow(
+        depositor,
+        recipient,
+        &None,
+        &token_admin.address,
+        &amount,
+        &deadline,
+    )
+}
+
+/// Helper to create an escrow with an arbiter and return its ID.
+fn create_arbitrated_escrow(
+    client: &EscrowContractClient,
+    token_admin: &token::StellarAssetClient,
+    depositor: &Address,
+    recipient: &Address,
+    arbiter: &Address,
+    amount: i128,
+) -> u64 {
+    token_admin.mint(depositor, &amount);
+    client.create_escrow(
+        depositor,
+        recipient,
+        &Some(arbiter.clone()),
+        &token_admin.address,
+        &amount,
+        &20000,
+    )
+}
+
+/// Helper to create a milestone escrow with the given tranche amounts.
+fn create_milestone_test_escrow(
+    env: &Env,
+    client: &EscrowContractClient,
+    token_admin: &token::StellarAssetClient,
+    depositor: &Address,
+    recipient: &Address,
+    amounts: &[i128],
+) -> u64 {
+    let mut milestones: Vec<MilestoneRequest> = Vec::new(env);
+    let mut total: i128 = 0;
+    for (i, amount) in amounts.iter().enumerate() {
+        milestones.push_back(MilestoneRequest {
+            amount: *amount,
+            description_hash: BytesN::from_array(env, &[i as u8; 32]),
+            deadline: None,
+        });
+        total += amount;
+    }
+    token_admin.mint(depositor, &total);
+    client.create_milestone_escrow(
+        depositor,
+        recipient,
+        &None,
+        &token_admin.address,
+        &milestones,
+        &20000,
+    )
 }
 
 // ============================================
@@ -170,13 +230,13 @@ fn test_create_multiple_escrows() {
 #[test]
 #[should_panic]
 fn test_create_escrow_invalid_amount() {
-    let (env, _admin, _token, _token_client, _token_admin, client) = setup_test_env();
+    let (env, _admin, token, _token_client, _token_admin, client) = setup_test_env();
 
     let depositor = Address::generate(&env);
     let recipient = Address::generate(&env);
 
     // Should panic due to invalid amount
-    client.create_escrow(&depositor, &recipient, &None, &0, &20000);
+    client.create_escrow(&depositor, &recipient, &None, &token, &0, &20000);
 }
 
 // ============================================
@@ -873,3 +933,862 @@ fn test_set_admin_unauthorized() {
     // Should panic due to unauthorized caller
     client.set_admin(&unauthorized, &new_admin);
 }
+
+// ============================================
+// Dispute Tests
+// ============================================
+
+#[test]
+fn test_open_dispute() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let escrow_id = create_arbitrated_escrow(
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        &arbiter,
+        10_000,
+    );
+
+    let evidence = BytesN::from_array(&env, &[1u8; 32]);
+    client.open_dispute(&recipient, &escrow_id, &evidence);
+
+    assert_eq!(
+        client.get_escrow(&escrow_id).unwrap().status,
+        EscrowStatus::Disputed
+    );
+    let dispute = client.get_dispute(&escrow_id).unwrap();
+    assert_eq!(dispute.opened_by, recipient);
+    assert_eq!(dispute.evidence_hash, evidence);
+    assert_eq!(dispute.response_hash, None);
+    assert_eq!(
+        dispute.response_deadline,
+        12345 + client.get_dispute_response_window()
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_open_dispute_without_arbiter() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        10_000,
+        20000,
+    );
+
+    client.open_dispute(
+        &depositor,
+        &escrow_id,
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_open_dispute_by_third_party() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let escrow_id = create_arbitrated_escrow(
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        &arbiter,
+        10_000,
+    );
+
+    let outsider = Address::generate(&env);
+    client.open_dispute(&outsider, &escrow_id, &BytesN::from_array(&env, &[1u8; 32]));
+}
+
+#[test]
+fn test_disputed_escrow_cannot_be_reversed() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let escrow_id = create_arbitrated_escrow(
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        &arbiter,
+        10_000,
+    );
+    client.open_dispute(
+        &depositor,
+        &escrow_id,
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+
+    let mut requests: Vec<ReversalRequest> = Vec::new(&env);
+    requests.push_back(create_reversal_request(escrow_id));
+    let result = client.batch_reverse_escrows(&admin, &requests);
+
+    assert_eq!(result.failed, 1);
+    match result.results.get(0).unwrap() {
+        ReversalResult::Failure(id, error_code) => {
+            assert_eq!(id, escrow_id);
+            assert_eq!(error_code, 5); // ESCROW_DISPUTED
+        }
+        _ => panic!("Expected failure"),
+    }
+}
+
+#[test]
+fn test_resolve_dispute_split_with_fee() {
+    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let escrow_id = create_arbitrated_escrow(
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        &arbiter,
+        10_000,
+    );
+
+    // 2% arbiter fee
+    client.set_dispute_config(&admin, &100, &200);
+
+    client.open_dispute(
+        &recipient,
+        &escrow_id,
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+    client.respond_to_dispute(
+        &depositor,
+        &escrow_id,
+        &BytesN::from_array(&env, &[2u8; 32]),
+    );
+
+    // 30% back to the depositor, 70% to the recipient
+    let ruling = client.resolve_dispute(&arbiter, &escrow_id, &3_000);
+
+    assert_eq!(ruling.arbiter_fee, 200);
+    assert_eq!(ruling.depositor_amount, 2_940);
+    assert_eq!(ruling.recipient_amount, 6_860);
+    assert_eq!(token_client.balance(&arbiter), 200);
+    assert_eq!(token_client.balance(&depositor), 2_940);
+    assert_eq!(token_client.balance(&recipient), 6_860);
+    assert_eq!(token_client.balance(&client.address), 0);
+
+    assert_eq!(
+        client.get_escrow(&escrow_id).unwrap().status,
+        EscrowStatus::Resolved
+    );
+    assert_eq!(
+        client.get_dispute_ruling(&escrow_id).unwrap().depositor_bps,
+        3_000
+    );
+    assert_eq!(client.get_total_disputes_resolved(), 1);
+}
+
+#[test]
+#[should_panic]
+fn test_resolve_dispute_before_response_window_closes() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let escrow_id = create_arbitrated_escrow(
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        &arbiter,
+        10_000,
+    );
+
+    client.open_dispute(
+        &recipient,
+        &escrow_id,
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+
+    // No response yet and the window is still open
+    client.resolve_dispute(&arbiter, &escrow_id, &5_000);
+}
+
+#[test]
+fn test_resolve_dispute_after_response_window_elapses() {
+    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let escrow_id = create_arbitrated_escrow(
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        &arbiter,
+        10_000,
+    );
+
+    client.set_dispute_config(&admin, &100, &0);
+    client.open_dispute(
+        &depositor,
+        &escrow_id,
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12345 + 101;
+    });
+
+    // Full refund to the depositor
+    client.resolve_dispute(&arbiter, &escrow_id, &10_000);
+    assert_eq!(token_client.balance(&depositor), 10_000);
+    assert_eq!(token_client.balance(&recipient), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_resolve_dispute_by_non_arbiter() {
+    let (env, admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    let escrow_id = create_arbitrated_escrow(
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        &arbiter,
+        10_000,
+    );
+
+    client.open_dispute(
+        &recipient,
+        &escrow_id,
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+    client.respond_to_dispute(
+        &depositor,
+        &escrow_id,
+        &BytesN::from_array(&env, &[2u8; 32]),
+    );
+
+    client.resolve_dispute(&admin, &escrow_id, &5_000);
+}
+
+#[test]
+#[should_panic]
+fn test_set_dispute_config_fee_too_high() {
+    let (_env, admin, _token, _token_client, _token_admin, client) = setup_test_env();
+
+    client.set_dispute_config(&admin, &100, &(MAX_ARBITER_FEE_BPS + 1));
+}
+
+// ============================================
+// Milestone Tests
+// ============================================
+
+#[test]
+fn test_create_milestone_escrow() {
+    let (env, _admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_milestone_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        &[1_000, 2_000, 3_000],
+    );
+
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.amount, 6_000);
+    assert_eq!(escrow.released_amount, 0);
+    assert_eq!(escrow.milestones.len(), 3);
+    for milestone in escrow.milestones.iter() {
+        assert_eq!(milestone.status, MilestoneStatus::Pending);
+    }
+    assert_eq!(token_client.balance(&client.address), 6_000);
+}
+
+#[test]
+fn test_approve_milestones_in_order() {
+    let (env, _admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_milestone_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        &[1_000, 2_000],
+    );
+
+    client.approve_milestone(&depositor, &escrow_id, &0);
+
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.status, EscrowStatus::Active);
+    assert_eq!(escrow.released_amount, 1_000);
+    assert_eq!(
+        escrow.milestones.get(0).unwrap().status,
+        MilestoneStatus::Released
+    );
+    assert_eq!(
+        escrow.milestones.get(1).unwrap().status,
+        MilestoneStatus::Pending
+    );
+    assert_eq!(token_client.balance(&recipient), 1_000);
+
+    client.approve_milestone(&depositor, &escrow_id, &1);
+
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(token_client.balance(&recipient), 3_000);
+}
+
+#[test]
+#[should_panic]
+fn test_approve_milestone_out_of_order() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_milestone_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        &[1_000, 2_000],
+    );
+
+    client.approve_milestone(&depositor, &escrow_id, &1);
+}
+
+#[test]
+#[should_panic]
+fn test_approve_milestone_by_recipient() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_milestone_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        &[1_000],
+    );
+
+    client.approve_milestone(&recipient, &escrow_id, &0);
+}
+
+#[test]
+#[should_panic]
+fn test_approve_milestone_after_deadline() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let mut milestones: Vec<MilestoneRequest> = Vec::new(&env);
+    milestones.push_back(MilestoneRequest {
+        amount: 1_000,
+        description_hash: BytesN::from_array(&env, &[0u8; 32]),
+        deadline: Some(12_400),
+    });
+    token_admin.mint(&depositor, &1_000);
+    let escrow_id = client.create_milestone_escrow(
+        &depositor,
+        &recipient,
+        &None,
+        &token_admin.address,
+        &milestones,
+        &20000,
+    );
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12_401;
+    });
+
+    client.approve_milestone(&depositor, &escrow_id, &0);
+}
+
+#[test]
+fn test_batch_release_named_milestone() {
+    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient1 = Address::generate(&env);
+    let recipient2 = Address::generate(&env);
+    let escrow_id1 = create_milestone_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient1,
+        &[1_000, 2_000],
+    );
+    let escrow_id2 = create_milestone_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient2,
+        &[500, 500],
+    );
+
+    let mut requests: Vec<ReleaseRequest> = Vec::new(&env);
+    requests.push_back(ReleaseRequest {
+        escrow_id: escrow_id1,
+        milestone: Some(0),
+    });
+    requests.push_back(ReleaseRequest {
+        escrow_id: escrow_id2,
+        milestone: Some(0),
+    });
+    // Milestone 1 of escrow 1 is not next once milestone 0 is validated
+    requests.push_back(ReleaseRequest {
+        escrow_id: escrow_id1,
+        milestone: Some(1),
+    });
+
+    let result = client.batch_release_escrows(&admin, &requests);
+
+    assert_eq!(result.successful, 2);
+    assert_eq!(result.failed, 1);
+    assert_eq!(result.total_released, 1_500);
+    match result.results.get(2).unwrap() {
+        ReleaseResult::Failure(id, error_code) => {
+            assert_eq!(id, escrow_id1);
+            assert_eq!(error_code, 7); // INVALID_MILESTONE
+        }
+        _ => panic!("Expected failure"),
+    }
+    assert_eq!(token_client.balance(&recipient1), 1_000);
+    assert_eq!(token_client.balance(&recipient2), 500);
+    assert_eq!(client.get_total_escrows_released(), 0);
+}
+
+#[test]
+fn test_reverse_partially_released_milestone_escrow() {
+    let (env, admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_milestone_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        &[1_000, 2_000],
+    );
+    client.approve_milestone(&depositor, &escrow_id, &0);
+
+    let mut requests: Vec<ReversalRequest> = Vec::new(&env);
+    requests.push_back(create_reversal_request(escrow_id));
+    let result = client.batch_reverse_escrows(&admin, &requests);
+
+    // Only the unreleased tranche goes back to the depositor
+    assert_eq!(result.total_reversed, 2_000);
+    assert_eq!(token_client.balance(&depositor), 2_000);
+    assert_eq!(token_client.balance(&recipient), 1_000);
+}
+
+// ============================================
+// Multi-Token Tests
+// ============================================
+
+/// Helper to deploy an additional Stellar asset for multi-token tests.
+fn create_extra_token(env: &Env) -> (Address, token::StellarAssetClient<'static>) {
+    let issuer = Address::generate(env);
+    let stellar_asset = env.register_stellar_asset_contract_v2(issuer);
+    let token_id = stellar_asset.address();
+    (
+        token_id.clone(),
+        token::StellarAssetClient::new(env, &token_id),
+    )
+}
+
+#[test]
+fn test_initial_token_is_allowed() {
+    let (_env, _admin, token, _token_client, _token_admin, client) = setup_test_env();
+
+    assert!(client.is_token_allowed(&token));
+    assert_eq!(client.get_allowed_tokens().len(), 1);
+}
+
+#[test]
+#[should_panic]
+fn test_create_escrow_with_unlisted_token() {
+    let (env, _admin, _token, _token_client, _token_admin, client) = setup_test_env();
+
+    let (other_token, other_admin) = create_extra_token(&env);
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    other_admin.mint(&depositor, &1_000);
+
+    client.create_escrow(&depositor, &recipient, &None, &other_token, &1_000, &20000);
+}
+
+#[test]
+fn test_add_and_remove_allowed_token() {
+    let (env, admin, _token, _token_client, _token_admin, client) = setup_test_env();
+
+    let (other_token, _other_admin) = create_extra_token(&env);
+    client.add_allowed_token(&admin, &other_token);
+    assert!(client.is_token_allowed(&other_token));
+    assert_eq!(client.get_allowed_tokens().len(), 2);
+
+    client.remove_allowed_token(&admin, &other_token);
+    assert!(!client.is_token_allowed(&other_token));
+    assert_eq!(client.get_allowed_tokens().len(), 1);
+}
+
+#[test]
+#[should_panic]
+fn test_add_allowed_token_unauthorized() {
+    let (env, _admin, _token, _token_client, _token_admin, client) = setup_test_env();
+
+    let (other_token, _other_admin) = create_extra_token(&env);
+    let outsider = Address::generate(&env);
+    client.add_allowed_token(&outsider, &other_token);
+}
+
+#[test]
+fn test_batch_release_and_reverse_mixed_tokens() {
+    let (env, admin, token, token_client, token_admin, client) = setup_test_env();
+
+    let (other_token, other_admin) = create_extra_token(&env);
+    let other_client = token::Client::new(&env, &other_token);
+    client.add_allowed_token(&admin, &other_token);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    token_admin.mint(&depositor, &3_000);
+    other_admin.mint(&depositor, &7_000);
+
+    let id1 = client.create_escrow(&depositor, &recipient, &None, &token, &1_000, &20000);
+    let id2 = client.create_escrow(&depositor, &recipient, &None, &other_token, &5_000, &20000);
+    let id3 = client.create_escrow(&depositor, &recipient, &None, &token, &2_000, &20000);
+    let id4 = client.create_escrow(&depositor, &recipient, &None, &other_token, &2_000, &20000);
+
+    let mut releases: Vec<ReleaseRequest> = Vec::new(&env);
+    releases.push_back(ReleaseRequest {
+        escrow_id: id1,
+        milestone: None,
+    });
+    releases.push_back(ReleaseRequest {
+        escrow_id: id2,
+        milestone: None,
+    });
+    let result = client.batch_release_escrows(&admin, &releases);
+    assert_eq!(result.successful, 2);
+
+    let mut reversals: Vec<ReversalRequest> = Vec::new(&env);
+    reversals.push_back(create_reversal_request(id3));
+    reversals.push_back(create_reversal_request(id4));
+    let result = client.batch_reverse_escrows(&admin, &reversals);
+    assert_eq!(result.successful, 2);
+
+    assert_eq!(token_client.balance(&recipient), 1_000);
+    assert_eq!(other_client.balance(&recipient), 5_000);
+    assert_eq!(token_client.balance(&depositor), 2_000);
+    assert_eq!(other_client.balance(&depositor), 2_000);
+
+    assert_eq!(client.get_token_amount_released(&token), 1_000);
+    assert_eq!(client.get_token_amount_released(&other_token), 5_000);
+    assert_eq!(client.get_token_amount_reversed(&token), 2_000);
+    assert_eq!(client.get_token_amount_reversed(&other_token), 2_000);
+}
+
+#[test]
+fn test_single_milestone_and_dispute_payouts_update_token_totals() {
+    let (env, admin, token, _token_client, token_admin, client) = setup_test_env();
+
+    let (other_token, other_admin) = create_extra_token(&env);
+    client.add_allowed_token(&admin, &other_token);
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+
+    // Single release in the default token
+    token_admin.mint(&depositor, &1_000);
+    let released = client.create_escrow(&depositor, &recipient, &None, &token, &1_000, &20000);
+    client.release_escrow(&depositor, &released);
+
+    // One milestone tranche in the default token
+    let milestones = create_milestone_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        &[300, 700],
+    );
+    client.approve_milestone(&depositor, &milestones, &0);
+
+    // Dispute in the other token: 2% fee, then 30% back to the depositor
+    other_admin.mint(&depositor, &10_000);
+    let disputed = client.create_escrow(
+        &depositor,
+        &recipient,
+        &Some(arbiter.clone()),
+        &other_token,
+        &10_000,
+        &20000,
+    );
+    client.set_dispute_config(&admin, &100, &200);
+    client.open_dispute(&recipient, &disputed, &BytesN::from_array(&env, &[1u8; 32]));
+    client.respond_to_dispute(&depositor, &disputed, &BytesN::from_array(&env, &[2u8; 32]));
+    client.resolve_dispute(&arbiter, &disputed, &3_000);
+
+    assert_eq!(client.get_token_amount_released(&token), 1_300);
+    assert_eq!(client.get_token_amount_released(&other_token), 200 + 6_860);
+    assert_eq!(client.get_token_amount_reversed(&other_token), 2_940);
+    assert_eq!(client.get_total_amount_released(), 1_300 + 200 + 6_860);
+    assert_eq!(client.get_total_amount_reversed(), 2_940);
+}
+
+// ============================================
+// Expiry Tests
+// ============================================
+
+#[test]
+fn test_claim_expired() {
+    let (env, _admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        10_000,
+        13000,
+    );
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 13001;
+    });
+
+    let refunded = client.claim_expired(&depositor, &escrow_id);
+
+    assert_eq!(refunded, 10_000);
+    assert_eq!(token_client.balance(&depositor), 10_000);
+    assert_eq!(
+        client.get_escrow(&escrow_id).unwrap().status,
+        EscrowStatus::Reversed
+    );
+    assert_eq!(client.get_total_escrows_reversed(), 1);
+    assert_eq!(client.get_total_amount_reversed(), 10_000);
+    assert_eq!(client.get_total_reversal_batches(), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_claim_expired_before_deadline() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        10_000,
+        13000,
+    );
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 13000;
+    });
+
+    client.claim_expired(&depositor, &escrow_id);
+}
+
+#[test]
+#[should_panic]
+fn test_claim_expired_within_grace_period() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        10_000,
+        13000,
+    );
+    client.set_grace_period(&depositor, &escrow_id, &500);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 13400;
+    });
+
+    client.claim_expired(&depositor, &escrow_id);
+}
+
+#[test]
+fn test_release_during_grace_period() {
+    let (env, _admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        10_000,
+        13000,
+    );
+    client.set_grace_period(&depositor, &escrow_id, &500);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 13400;
+    });
+
+    // Past the deadline but within grace: recipient can still be paid
+    client.release_escrow(&depositor, &escrow_id);
+    assert_eq!(token_client.balance(&recipient), 10_000);
+}
+
+#[test]
+#[should_panic]
+fn test_grace_period_cannot_be_shortened() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let escrow_id = create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        10_000,
+        13000,
+    );
+    client.set_grace_period(&depositor, &escrow_id, &500);
+    client.set_grace_period(&depositor, &escrow_id, &100);
+}
+
+#[test]
+fn test_sweep_expired_with_cursor() {
+    let (env, _admin, _token, token_client, token_admin, client) = setup_test_env();
+
+    let recipient = Address::generate(&env);
+    let depositor1 = Address::generate(&env);
+    let depositor2 = Address::generate(&env);
+    let depositor3 = Address::generate(&env);
+
+    // Escrows 1 and 3 expire at 15000, escrow 2 at 30000
+    create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor1,
+        &recipient,
+        1_000,
+        15000,
+    );
+    create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor2,
+        &recipient,
+        2_000,
+        30000,
+    );
+    create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor3,
+        &recipient,
+        3_000,
+        15000,
+    );
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 16000;
+    });
+
+    let first = client.sweep_expired(&0, &2);
+    assert_eq!(first.scanned, 2);
+    assert_eq!(first.reversed, 1);
+    assert_eq!(first.next_cursor, 2);
+    assert!(!first.done);
+
+    let second = client.sweep_expired(&first.next_cursor, &2);
+    assert_eq!(second.scanned, 1);
+    assert_eq!(second.reversed, 1);
+    assert!(second.done);
+
+    assert_eq!(token_client.balance(&depositor1), 1_000);
+    assert_eq!(token_client.balance(&depositor2), 0);
+    assert_eq!(token_client.balance(&depositor3), 3_000);
+    assert_eq!(client.get_total_escrows_reversed(), 2);
+    assert_eq!(client.get_total_amount_reversed(), 4_000);
+    assert_eq!(client.get_total_reversal_batches(), 2);
+}
+
+#[test]
+fn test_sweep_expired_without_expired_escrows_records_no_batch() {
+    let (env, _admin, _token, _token_client, token_admin, client) = setup_test_env();
+
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    create_test_escrow(
+        &env,
+        &client,
+        &token_admin,
+        &depositor,
+        &recipient,
+        1_000,
+        30000,
+    );
+
+    let result = client.sweep_expired(&0, &10);
+    assert!(env.events().all().is_empty());
+
+    assert_eq!(result.scanned, 1);
+    assert_eq!(result.reversed, 0);
+    assert_eq!(result.batch_id, 0);
+    assert!(result.done);
+    assert_eq!(client.get_total_reversal_batches(), 0);
+}
@@ -1,10 +1,22 @@
 //! Data types and events for the escrow contract.
 
-use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Vec};
 
 /// Maximum number of escrows in a single batch operation.
 pub const MAX_BATCH_SIZE: u32 = 100;
 
+/// Denominator for basis-point calculations (100% = 10_000 bps).
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Maximum arbiter fee that can be configured (10%).
+pub const MAX_ARBITER_FEE_BPS: u32 = 1_000;
+
+/// Maximum number of milestones on a single escrow.
+pub const MAX_MILESTONES: u32 = 20;
+
+/// Default number of ledgers the counterparty has to respond to a dispute (~1 day).
+pub const DEFAULT_DISPUTE_RESPONSE_WINDOW: u64 = 17_280;
+
 /// Escrow status enum.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[contracttype]
@@ -15,6 +27,41 @@ pub enum EscrowStatus {
     Released,
     /// Funds returned to depositor (reversed)
     Reversed,
+    /// Funds frozen pending an arbiter ruling
+    Disputed,
+    /// Funds split between the parties by an arbiter ruling
+    Resolved,
+}
+
+/// Milestone status enum.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum MilestoneStatus {
+    /// Tranche still locked, awaiting approval
+    Pending,
+    /// Tranche paid out to the recipient
+    Released,
+}
+
+/// A single tranche of a milestone escrow.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct Milestone {
+    pub amount: i128,
+    /// Hash of the off-chain milestone description
+    pub description_hash: BytesN<32>,
+    /// Last ledger at which the milestone may be approved, if any
+    pub deadline: Option<u64>,
+    pub status: MilestoneStatus,
+}
+
+/// Milestone definition supplied when creating a milestone escrow.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct MilestoneRequest {
+    pub amount: i128,
+    pub description_hash: BytesN<32>,
+    pub deadline: Option<u64>,
 }
 
 /// An escrow record.
@@ -30,6 +77,70 @@ pub struct Escrow {
     pub status: EscrowStatus,
     pub created_at: u64,
     pub deadline: u64,
+    /// Ordered tranches; empty for single-payout escrows
+    pub milestones: Vec<Milestone>,
+    /// Amount already paid out through milestone approvals
+    pub released_amount: i128,
+    /// Ledgers after `deadline` before the depositor may reclaim the funds
+    pub grace_period: u64,
+}
+
+impl Escrow {
+    /// Returns the amount still held by the contract for this escrow.
+    pub fn remaining_amount(&self) -> i128 {
+        self.amount - self.released_amount
+    }
+
+    /// Returns the index of the next milestone awaiting approval, if any.
+    pub fn next_pending_milestone(&self) -> Option<u32> {
+        self.milestones
+            .iter()
+            .position(|m| m.status == MilestoneStatus::Pending)
+            .map(|i| i as u32)
+    }
+
+    /// Returns true once the deadline and grace period have both passed.
+    pub fn is_expired(&self, current_ledger: u64) -> bool {
+        current_ledger > self.deadline.saturating_add(self.grace_period)
+    }
+
+    /// Marks every outstanding milestone as paid once the remainder is released.
+    pub fn mark_fully_released(&mut self) {
+        for i in 0..self.milestones.len() {
+            let mut milestone = self.milestones.get(i).unwrap();
+            milestone.status = MilestoneStatus::Released;
+            self.milestones.set(i, milestone);
+        }
+        self.released_amount = self.amount;
+        self.status = EscrowStatus::Released;
+    }
+}
+
+/// Outcome of an arbiter ruling on a dispute.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct DisputeRuling {
+    /// Share of the post-fee amount awarded to the depositor, in basis points
+    pub depositor_bps: u32,
+    pub depositor_amount: i128,
+    pub recipient_amount: i128,
+    pub arbiter_fee: i128,
+    pub ruled_at: u64,
+}
+
+/// A dispute raised against an escrow by the depositor or recipient.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct Dispute {
+    pub escrow_id: u64,
+    pub opened_by: Address,
+    /// Hash of the off-chain evidence submitted by the opening party
+    pub evidence_hash: BytesN<32>,
+    /// Hash of the counterparty's evidence, if they responded
+    pub response_hash: Option<BytesN<32>>,
+    pub opened_at: u64,
+    /// Ledger after which the arbiter may rule without a response
+    pub response_deadline: u64,
 }
 
 /// Request to reverse an escrow.
@@ -44,6 +155,8 @@ pub struct ReversalRequest {
 #[contracttype]
 pub struct ReleaseRequest {
     pub escrow_id: u64,
+    /// Milestone to release; `None` releases the whole remaining amount
+    pub milestone: Option<u32>,
 }
 
 /// Result of a single escrow reversal.
@@ -68,6 +181,24 @@ pub struct BatchReversalResult {
     pub results: Vec<ReversalResult>,
 }
 
+/// Result of a keeper sweep over expired escrows.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct SweepResult {
+    /// Reversal batch ID, or 0 when nothing was reversed
+    pub batch_id: u64,
+    /// Number of escrow IDs examined
+    pub scanned: u32,
+    /// Number of expired escrows reversed
+    pub reversed: u32,
+    pub total_reversed: i128,
+    /// Cursor to pass to the next sweep call
+    pub next_cursor: u64,
+    /// True when the sweep has reached the latest escrow ID
+    pub done: bool,
+    pub results: Vec<ReversalResult>,
+}
+
 /// Result of a single escrow release.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -96,8 +227,10 @@ pub struct BatchReleaseResult {
 pub enum DataKey {
     /// Admin address
     Admin,
-    /// Token address used for escrows
-    Token,
+    /// Whether a token is accepted for new escrows
+    AllowedToken(Address),
+    /// List of accepted token addresses
+    AllowedTokens,
     /// Individual escrow by ID
     Escrow(u64),
     /// List of escrow IDs for a user (depositor)
@@ -116,6 +249,20 @@ pub enum DataKey {
     TotalEscrowsReleased,
     /// Total amount released
     TotalAmountReleased,
+    /// Total amount reversed per token
+    TokenAmountReversed(Address),
+    /// Total amount released per token
+    TokenAmountReleased(Address),
+    /// Dispute record by escrow ID
+    Dispute(u64),
+    /// Arbiter ruling by escrow ID
+    DisputeRuling(u64),
+    /// Number of ledgers the counterparty has to respond to a dispute
+    DisputeResponseWindow,
+    /// Fee paid to the arbiter on a ruling, in basis points
+    ArbiterFeeBps,
+    /// Total number of disputes resolved by arbiter ruling
+    TotalDisputesResolved,
 }
 
 /// Event emitters for escrow operations.
@@ -226,4 +373,66 @@ impl EscrowEvents {
         env.events()
             .publish(topics, (successful, failed, total_released));
     }
+
+    /// Emitted when a milestone tranche is paid out to the recipient.
+    pub fn milestone_released(
+        env: &Env,
+        escrow_id: u64,
+        milestone: u32,
+        recipient: &Address,
+        amount: i128,
+    ) {
+        let topics = (symbol_short!("escrow"), symbol_short!("ms_rel"), escrow_id);
+        env.events()
+            .publish(topics, (milestone, recipient.clone(), amount));
+    }
+
+    /// Emitted when a party opens a dispute on an escrow.
+    pub fn dispute_opened(
+        env: &Env,
+        escrow_id: u64,
+        opened_by: &Address,
+        evidence_hash: &BytesN<32>,
+        response_deadline: u64,
+    ) {
+        let topics = (symbol_short!("escrow"), symbol_short!("disp_open"));
+        env.events().publish(
+            topics,
+            (
+                escrow_id,
+                opened_by.clone(),
+                evidence_hash.clone(),
+                response_deadline,
+            ),
+        );
+    }
+
+    /// Emitted when the counterparty responds to a dispute.
+    pub fn dispute_responded(
+        env: &Env,
+        escrow_id: u64,
+        responder: &Address,
+        response_hash: &BytesN<32>,
+    ) {
+        let topics = (symbol_short!("escrow"), symbol_short!("disp_resp"));
+        env.events().publish(
+            topics,
+            (escrow_id, responder.clone(), response_hash.clone()),
+        );
+    }
+
+    /// Emitted when the arbiter rules on a dispute.
+    pub fn dispute_resolved(env: &Env, escrow_id: u64, arbiter: &Address, ruling: &DisputeRuling) {
+        let topics = (symbol_short!("escrow"), symbol_short!("disp_res"));
+        env.events().publish(
+            topics,
+            (
+                escrow_id,
+                arbiter.clone(),
+                ruling.depositor_amount,
+                ruling.recipient_amount,
+                ruling.arbiter_fee,
+            ),
+        );
+    }
 }
@@ -1,6 +1,6 @@
 //! Validation utilities for escrow reversals.
 
-use crate::types::{Escrow, EscrowStatus};
+use crate::types::{Escrow, EscrowStatus, Milestone};
 use soroban_sdk::Address;
 
 /// Error codes for reversal validation.
@@ -16,6 +16,14 @@ pub mod ErrorCode {
     pub const UNAUTHORIZED: u32 = 3;
     /// Deadline not yet reached (for time-based reversals)
     pub const DEADLINE_NOT_REACHED: u32 = 4;
+    /// Escrow is frozen by an open dispute
+    pub const ESCROW_DISPUTED: u32 = 5;
+    /// Escrow already settled by an arbiter ruling
+    pub const ALREADY_RESOLVED: u32 = 6;
+    /// Milestone does not exist or is not the next one pending
+    pub const INVALID_MILESTONE: u32 = 7;
+    /// Milestone approval deadline has passed
+    pub const MILESTONE_EXPIRED: u32 = 8;
 }
 
 /// Validation error types for reversals.
@@ -31,6 +39,14 @@ pub enum ValidationError {
     Unauthorized,
     /// Deadline has not been reached yet
     DeadlineNotReached,
+    /// Escrow is under dispute
+    EscrowDisputed,
+    /// Escrow has already been resolved by an arbiter
+    AlreadyResolved,
+    /// Milestone is missing or out of order
+    InvalidMilestone,
+    /// Milestone approval deadline has passed
+    MilestoneExpired,
 }
 
 impl ValidationError {
@@ -42,6 +58,10 @@ impl ValidationError {
             ValidationError::AlreadyReversed => ErrorCode::ALREADY_REVERSED,
             ValidationError::Unauthorized => ErrorCode::UNAUTHORIZED,
             ValidationError::DeadlineNotReached => ErrorCode::DEADLINE_NOT_REACHED,
+            ValidationError::EscrowDisputed => ErrorCode::ESCROW_DISPUTED,
+            ValidationError::AlreadyResolved => ErrorCode::ALREADY_RESOLVED,
+            ValidationError::InvalidMilestone => ErrorCode::INVALID_MILESTONE,
+            ValidationError::MilestoneExpired => ErrorCode::MILESTONE_EXPIRED,
         }
     }
 }
@@ -72,6 +92,8 @@ pub fn validate_reversal(
     match escrow.status {
         EscrowStatus::Released => return Err(ValidationError::AlreadyReleased),
         EscrowStatus::Reversed => return Err(ValidationError::AlreadyReversed),
+        EscrowStatus::Disputed => return Err(ValidationError::EscrowDisputed),
+        EscrowStatus::Resolved => return Err(ValidationError::AlreadyResolved),
         EscrowStatus::Active => {}
     }
 
@@ -109,6 +131,8 @@ pub fn validate_release(
     match escrow.status {
         EscrowStatus::Released => return Err(ValidationError::AlreadyReleased),
         EscrowStatus::Reversed => return Err(ValidationError::AlreadyReversed),
+        EscrowStatus::Disputed => return Err(ValidationError::EscrowDisputed),
+        EscrowStatus::Resolved => return Err(ValidationError::AlreadyResolved),
         EscrowStatus::Active => {}
     }
 
@@ -127,10 +151,38 @@ pub fn validate_release(
     Ok(())
 }
 
+/// Validates whether a milestone of an escrow can be released.
+///
+/// Milestones are released strictly in order, so `index` must be the first
+/// pending milestone and its deadline, if any, must not have passed.
+pub fn validate_milestone(
+    escrow: &Escrow,
+    index: u32,
+    current_ledger: u64,
+) -> Result<Milestone, ValidationError> {
+    if escrow.next_pending_milestone() != Some(index) {
+        return Err(ValidationError::InvalidMilestone);
+    }
+
+    let milestone = escrow
+        .milestones
+        .get(index)
+        .ok_or(ValidationError::InvalidMilestone)?;
+
+    if let Some(deadline) = milestone.deadline {
+        if current_ledger > deadline {
+            return Err(ValidationError::MilestoneExpired);
+        }
+    }
+
+    Ok(milestone)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Env};
+    use crate::types::MilestoneStatus;
+    use soroban_sdk::{testutils::Address as _, BytesN, Env, Vec};
 
     fn create_test_escrow(env: &Env, status: EscrowStatus) -> Escrow {
         Escrow {
@@ -143,6 +195,18 @@ mod tests {
             status,
             created_at: 100,
             deadline: 200,
+            milestones: Vec::new(env),
+            released_amount: 0,
+            grace_period: 0,
+        }
+    }
+
+    fn create_milestone(env: &Env, amount: i128, deadline: Option<u64>) -> Milestone {
+        Milestone {
+            amount,
+            description_hash: BytesN::from_array(env, &[0u8; 32]),
+            deadline,
+            status: MilestoneStatus::Pending,
         }
     }
 
@@ -249,6 +313,69 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_validate_reversal_disputed_escrow() {
+        let env = Env::default();
+        let escrow = create_test_escrow(&env, EscrowStatus::Disputed);
+        let admin = Address::generate(&env);
+
+        // Even the admin cannot reverse an escrow frozen by a dispute
+        let result = validate_reversal(Some(&escrow), &admin, &admin, false, 100);
+        assert_eq!(result, Err(ValidationError::EscrowDisputed));
+    }
+
+    #[test]
+    fn test_validate_release_resolved_escrow() {
+        let env = Env::default();
+        let escrow = create_test_escrow(&env, EscrowStatus::Resolved);
+        let admin = Address::generate(&env);
+
+        let result = validate_release(Some(&escrow), &admin, &admin);
+        assert_eq!(result, Err(ValidationError::AlreadyResolved));
+    }
+
+    #[test]
+    fn test_validate_milestone_in_order() {
+        let env = Env::default();
+        let mut escrow = create_test_escrow(&env, EscrowStatus::Active);
+        escrow
+            .milestones
+            .push_back(create_milestone(&env, 400, None));
+        escrow
+            .milestones
+            .push_back(create_milestone(&env, 600, None));
+
+        assert_eq!(
+            validate_milestone(&escrow, 1, 100).unwrap_err(),
+            ValidationError::InvalidMilestone
+        );
+        assert_eq!(validate_milestone(&escrow, 0, 100).unwrap().amount, 400);
+
+        let mut first = escrow.milestones.get(0).unwrap();
+        first.status = MilestoneStatus::Released;
+        escrow.milestones.set(0, first);
+        assert_eq!(validate_milestone(&escrow, 1, 100).unwrap().amount, 600);
+        assert_eq!(
+            validate_milestone(&escrow, 2, 100).unwrap_err(),
+            ValidationError::InvalidMilestone
+        );
+    }
+
+    #[test]
+    fn test_validate_milestone_expired() {
+        let env = Env::default();
+        let mut escrow = create_test_escrow(&env, EscrowStatus::Active);
+        escrow
+            .milestones
+            .push_back(create_milestone(&env, 1000, Some(150)));
+
+        assert!(validate_milestone(&escrow, 0, 150).is_ok());
+        assert_eq!(
+            validate_milestone(&escrow, 0, 151).unwrap_err(),
+            ValidationError::MilestoneExpired
+        );
+    }
+
     #[test]
     fn test_error_code_conversion() {
         assert_eq!(
@@ -271,5 +398,21 @@ mod tests {
             ValidationError::DeadlineNotReached.to_error_code(),
             ErrorCode::DEADLINE_NOT_REACHED
         );
+        assert_eq!(
+            ValidationError::EscrowDisputed.to_error_code(),
+            ErrorCode::ESCROW_DISPUTED
+        );
+        assert_eq!(
+            ValidationError::AlreadyResolved.to_error_code(),
+            ErrorCode::ALREADY_RESOLVED
+        );
+        assert_eq!(
+            ValidationError::InvalidMilestone.to_error_code(),
+            ErrorCode::INVALID_MILESTONE
+        );
+        assert_eq!(
+            ValidationError::MilestoneExpired.to_error_code(),
+            ErrorCode::MILESTONE_EXPIRED
+        );
     }
 }
@@ -12,6 +12,12 @@
 //! - **Event Emission**: Emits events for balance updates and batch processing
 //! - **Error Handling**: Gracefully handles invalid inputs with detailed error codes
 //! - **Partial Failure Support**: Invalid updates don't affect valid ones
+//! - **Token Custody**: Deposit and withdraw real Stellar Asset Contract tokens
+//!   for registered currencies, with a reconciliation view against holdings
+//! - **Internal Transfers & FX**: Move balances between users without token
+//!   transfers, and exchange currencies at rates from a pluggable rate source
+//! - **Portfolio Valuation**: Value all of a user's balances in a base currency,
+//!   rejecting stale rates
 //!
 //! ## Optimization Strategies
 //!
@@ -25,11 +31,13 @@
 mod types;
 mod validation;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, Address, Env, Symbol, Vec};
+use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, Env, Symbol, Vec};
 
 pub use crate::types::{
     BalanceUpdateRequest, BalanceUpdateResult, BatchBalanceMetrics, BatchBalanceResult,
-    CurrencyBalance, DataKey, ErrorCode, WalletEvents, MAX_BATCH_SIZE,
+    CurrencyBalance, CurrencyReconciliation, DataKey, ErrorCode, ExchangeConfig, ExchangeRate,
+    PortfolioHolding, PortfolioValuation, RateSource, RateSourceClient, ValuationConfig,
+    WalletEvents, BPS_DENOMINATOR, MAX_BATCH_SIZE, MAX_SPREAD_BPS, RATE_SCALE,
 };
 use crate::validation::{validate_and_compute_balance, validate_balance_request};
 
@@ -47,6 +55,30 @@ pub enum WalletError {
     EmptyBatch = 4,
     /// Batch exceeds maximum size
     BatchTooLarge = 5,
+    /// Currency or token is not registered
+    CurrencyNotSupported = 6,
+    /// Amount must be positive
+    InvalidAmount = 7,
+    /// Balance too low for the operation
+    InsufficientBalance = 8,
+    /// Currency or token is already registered to a different mapping
+    CurrencyAlreadyRegistered = 9,
+    /// Exchange has not been configured
+    ExchangeNotConfigured = 10,
+    /// Exchange output is below the caller's minimum
+    SlippageExceeded = 11,
+    /// Liquidity provider cannot cover the exchange output
+    InsufficientLiquidity = 12,
+    /// Exchange configuration or quoted rate is invalid
+    InvalidExchangeConfig = 13,
+    /// Source and destination currencies are the same
+    SameCurrency = 14,
+    /// Portfolio valuation has not been configured
+    ValuationNotConfigured = 15,
+    /// Rate is older than the configured maximum age
+    StaleRate = 16,
+    /// Currency already holds internal balances not backed by tokens
+    CurrencyHasBalances = 17,
 }
 
 impl From<WalletError> for soroban_sdk::Error {
@@ -85,6 +117,9 @@ impl MultiCurrencyWalletContract {
     /// This is the main entry point for batch balance updates. It validates all requests,
     /// updates balances, emits events, and handles partial failures gracefully.
     ///
+    /// Requests for currencies backed by a registered token fail with
+    /// `TOKEN_BACKED_CURRENCY`; those balances only change through custody.
+    ///
     /// # Arguments
     /// * `env` - The contract environment
     /// * `caller` - The address calling this function (must be admin)
@@ -147,8 +182,17 @@ impl MultiCurrencyWalletContract {
 
         // Process each request
         for request in requests.iter() {
-            // Validate the request
-            match validate_balance_request(&request) {
+            // Validate the request. Balances in token-backed currencies are
+            // withdrawable, so they may only change through deposits,
+            // withdrawals, transfers and exchanges.
+            let validation = validate_balance_request(&request).and_then(|()| {
+                if Self::get_currency_token(env.clone(), request.currency.clone()).is_some() {
+                    Err(ErrorCode::TOKEN_BACKED_CURRENCY)
+                } else {
+                    Ok(())
+                }
+            });
+            match validation {
                 Ok(()) => {
                     // Validate and compute new balance
                     match validate_and_compute_balance(
@@ -159,6 +203,18 @@ impl MultiCurrencyWalletContract {
                         request.amount,
                     ) {
                         Ok(new_balance) => {
+                            // Keep the currency total in step with the change
+                            let old_balance = Self::get_balance(
+                                env.clone(),
+                                request.user.clone(),
+                                request.currency.clone(),
+                            );
+                            Self::adjust_total_balance(
+                                &env,
+                                &request.currency,
+                                new_balance - old_balance,
+                            );
+
                             // Update succeeded - create the balance record
                             let balance = CurrencyBalance {
                                 user: request.user.clone(),
@@ -174,6 +230,7 @@ impl MultiCurrencyWalletContract {
                                 &DataKey::Balance(request.user.clone(), request.currency.clone()),
                                 &balance,
                             );
+                            Self::track_currency(&env, &request.user, &request.currency);
 
                             // Track unique users
                             if !contains_address(&unique_users, &request.user) {
@@ -288,6 +345,386 @@ impl MultiCurrencyWalletContract {
         }
     }
 
+    /// Maps a currency symbol to the token contract that backs it.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The address calling this function (must be admin)
+    /// * `currency` - The currency symbol (e.g., "USDC")
+    /// * `token` - The Stellar Asset Contract address for the currency
+    ///
+    /// # Errors
+    /// * `CurrencyAlreadyRegistered` - If either side is already mapped elsewhere
+    /// * `CurrencyHasBalances` - If admin-set balances exist in the currency,
+    ///   since they would become withdrawable without backing tokens
+    pub fn register_currency(env: Env, caller: Address, currency: Symbol, token: Address) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        let existing_token: Option<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CurrencyToken(currency.clone()));
+        let existing_currency: Option<Symbol> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TokenCurrency(token.clone()));
+        if existing_token.is_some() || existing_currency.is_some() {
+            panic_with_error!(&env, WalletError::CurrencyAlreadyRegistered);
+        }
+        if Self::get_total_balance(env.clone(), currency.clone()) != 0 {
+            panic_with_error!(&env, WalletError::CurrencyHasBalances);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::CurrencyToken(currency.clone()), &token);
+        env.storage()
+            .persistent()
+            .set(&DataKey::TokenCurrency(token.clone()), &currency);
+
+        let mut currencies = Self::get_supported_currencies(env.clone());
+        currencies.push_back(currency.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::SupportedCurrencies, &currencies);
+
+        WalletEvents::currency_registered(&env, &currency, &token);
+    }
+
+    /// Deposits tokens into the wallet, crediting the user's balance in the
+    /// token's registered currency.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `user` - The depositing user (must authorize)
+    /// * `token` - The token contract address
+    /// * `amount` - Amount to deposit (in smallest unit)
+    ///
+    /// # Returns
+    /// * `i128` - The user's new balance
+    pub fn deposit(env: Env, user: Address, token: Address, amount: i128) -> i128 {
+        user.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, WalletError::InvalidAmount);
+        }
+        let currency = Self::currency_for_token(&env, &token);
+
+        token::Client::new(&env, &token).transfer(&user, &env.current_contract_address(), &amount);
+        let balance = Self::credit(&env, &user, &currency, amount);
+
+        WalletEvents::deposited(&env, &user, &currency, amount, balance);
+        balance
+    }
+
+    /// Withdraws tokens from the wallet, debiting the user's balance in the
+    /// token's registered currency.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `user` - The withdrawing user (must authorize)
+    /// * `token` - The token contract address
+    /// * `amount` - Amount to withdraw (in smallest unit)
+    ///
+    /// # Returns
+    /// * `i128` - The user's new balance
+    pub fn withdraw(env: Env, user: Address, token: Address, amount: i128) -> i128 {
+        user.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, WalletError::InvalidAmount);
+        }
+        let currency = Self::currency_for_token(&env, &token);
+
+        let balance = Self::debit(&env, &user, &currency, amount);
+        token::Client::new(&env, &token).transfer(&env.current_contract_address(), &user, &amount);
+
+        WalletEvents::withdrawn(&env, &user, &currency, amount, balance);
+        balance
+    }
+
+    /// Transfers an internal balance to another user without moving tokens.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `from` - The sending user (must authorize)
+    /// * `to` - The receiving user
+    /// * `currency` - A registered currency symbol
+    /// * `amount` - Amount to transfer (in smallest unit)
+    pub fn transfer(env: Env, from: Address, to: Address, currency: Symbol, amount: i128) {
+        from.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, WalletError::InvalidAmount);
+        }
+        Self::require_supported(&env, &currency);
+
+        Self::debit(&env, &from, &currency, amount);
+        Self::credit(&env, &to, &currency, amount);
+
+        WalletEvents::transferred(&env, &from, &to, &currency, amount);
+    }
+
+    /// Configures in-wallet exchange.
+    ///
+    /// The liquidity provider must authorize the configuration, since users
+    /// then swap against its balances at the configured rate source.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The address calling this function (must be admin)
+    /// * `config` - Rate source, liquidity provider and spread fee
+    ///
+    /// # Errors
+    /// * `InvalidExchangeConfig` - If the spread exceeds `MAX_SPREAD_BPS`
+    pub fn set_exchange_config(env: Env, caller: Address, config: ExchangeConfig) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+        config.liquidity_provider.require_auth();
+        if config.spread_bps > MAX_SPREAD_BPS {
+            panic_with_error!(&env, WalletError::InvalidExchangeConfig);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ExchangeConfig, &config);
+    }
+
+    /// Returns the exchange configuration, if set.
+    pub fn get_exchange_config(env: Env) -> Option<ExchangeConfig> {
+        env.storage().instance().get(&DataKey::ExchangeConfig)
+    }
+
+    /// Exchanges part of a user's balance in one currency for another.
+    ///
+    /// The amount is converted at the rate source's current rate, the spread
+    /// fee is deducted from the converted amount, and the user's input is
+    /// swapped against the liquidity provider's internal balances, so token
+    /// holdings stay reconciled.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `user` - The exchanging user (must authorize)
+    /// * `from_currency` - Currency to sell
+    /// * `to_currency` - Currency to buy
+    /// * `amount` - Amount of `from_currency` to sell
+    /// * `min_out` - Minimum acceptable amount of `to_currency` after fees
+    ///
+    /// # Returns
+    /// * `i128` - Amount of `to_currency` credited to the user
+    ///
+    /// # Errors
+    /// * `StaleRate` - If the rate is older than the configured maximum age
+    /// * `SlippageExceeded` - If the output is below `min_out`
+    /// * `InsufficientLiquidity` - If the liquidity provider cannot cover the output
+    pub fn exchange(
+        env: Env,
+        user: Address,
+        from_currency: Symbol,
+        to_currency: Symbol,
+        amount: i128,
+        min_out: i128,
+    ) -> i128 {
+        user.require_auth();
+        if amount <= 0 {
+            panic_with_error!(&env, WalletError::InvalidAmount);
+        }
+        if from_currency == to_currency {
+            panic_with_error!(&env, WalletError::SameCurrency);
+        }
+        Self::require_supported(&env, &from_currency);
+        Self::require_supported(&env, &to_currency);
+
+        let config = Self::get_exchange_config(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, WalletError::ExchangeNotConfigured));
+        let quote =
+            RateSourceClient::new(&env, &config.rate_source).get_rate(&from_currency, &to_currency);
+        if quote.rate <= 0 {
+            panic_with_error!(&env, WalletError::InvalidExchangeConfig);
+        }
+        if env.ledger().timestamp().saturating_sub(quote.timestamp) > config.max_rate_age {
+            panic_with_error!(&env, WalletError::StaleRate);
+        }
+
+        let gross = amount
+            .checked_mul(quote.rate)
+            .unwrap_or_else(|| panic_with_error!(&env, WalletError::InvalidAmount))
+            / RATE_SCALE;
+        let fee = gross * config.spread_bps as i128 / BPS_DENOMINATOR;
+        let amount_out = gross - fee;
+        if amount_out <= 0 || amount_out < min_out {
+            panic_with_error!(&env, WalletError::SlippageExceeded);
+        }
+
+        let provider = config.liquidity_provider;
+        if Self::get_balance(env.clone(), provider.clone(), to_currency.clone()) < amount_out {
+            panic_with_error!(&env, WalletError::InsufficientLiquidity);
+        }
+
+        Self::debit(&env, &user, &from_currency, amount);
+        Self::credit(&env, &provider, &from_currency, amount);
+        Self::debit(&env, &provider, &to_currency, amount_out);
+        Self::credit(&env, &user, &to_currency, amount_out);
+
+        WalletEvents::exchanged(
+            &env,
+            &user,
+            &from_currency,
+            &to_currency,
+            amount,
+            amount_out,
+            fee,
+            quote.rate,
+        );
+        amount_out
+    }
+
+    /// Configures the price source used for portfolio valuation.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The address calling this function (must be admin)
+    /// * `config` - Price source and maximum rate age
+    pub fn set_valuation_config(env: Env, caller: Address, config: ValuationConfig) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ValuationConfig, &config);
+    }
+
+    /// Returns the valuation configuration, if set.
+    pub fn get_valuation_config(env: Env) -> Option<ValuationConfig> {
+        env.storage().instance().get(&DataKey::ValuationConfig)
+    }
+
+    /// Values all of a user's balances in a base currency.
+    ///
+    /// Each non-zero balance is converted at the price source's rate to
+    /// `base_currency`; balances already in the base currency use a rate of 1.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `user` - The user's address
+    /// * `base_currency` - The currency to value the portfolio in
+    ///
+    /// # Returns
+    /// * `PortfolioValuation` - Total value with a per-currency breakdown
+    ///
+    /// # Errors
+    /// * `ValuationNotConfigured` - If no price source is configured
+    /// * `StaleRate` - If any rate is older than the configured maximum age
+    pub fn get_portfolio_value(
+        env: Env,
+        user: Address,
+        base_currency: Symbol,
+    ) -> PortfolioValuation {
+        let config = Self::get_valuation_config(env.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, WalletError::ValuationNotConfigured));
+        let prices = RateSourceClient::new(&env, &config.price_source);
+        let now = env.ledger().timestamp();
+
+        let mut holdings = Vec::new(&env);
+        let mut total_value: i128 = 0;
+        for currency in Self::get_user_currencies(env.clone(), user.clone()).iter() {
+            let balance = Self::get_balance(env.clone(), user.clone(), currency.clone());
+            if balance == 0 {
+                continue;
+            }
+
+            let quote = if currency == base_currency {
+                ExchangeRate {
+                    rate: RATE_SCALE,
+                    timestamp: now,
+                }
+            } else {
+                prices.get_rate(&currency, &base_currency)
+            };
+            if quote.rate <= 0 {
+                panic_with_error!(&env, WalletError::InvalidExchangeConfig);
+            }
+            if now.saturating_sub(quote.timestamp) > config.max_rate_age {
+                panic_with_error!(&env, WalletError::StaleRate);
+            }
+
+            let value = balance
+                .checked_mul(quote.rate)
+                .unwrap_or_else(|| panic_with_error!(&env, WalletError::InvalidAmount))
+                / RATE_SCALE;
+            total_value += value;
+            holdings.push_back(PortfolioHolding {
+                currency,
+                balance,
+                rate: quote.rate,
+                rate_timestamp: quote.timestamp,
+                value,
+            });
+        }
+
+        PortfolioValuation {
+            user,
+            base_currency,
+            total_value,
+            holdings,
+            valued_at: now,
+        }
+    }
+
+    /// Returns every currency a user has held a balance in.
+    pub fn get_user_currencies(env: Env, user: Address) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::UserCurrencies(user))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Returns the token contract registered for a currency, if any.
+    pub fn get_currency_token(env: Env, currency: Symbol) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CurrencyToken(currency))
+    }
+
+    /// Returns all registered currency symbols.
+    pub fn get_supported_currencies(env: Env) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SupportedCurrencies)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Returns the sum of all users' internal balances in a currency.
+    pub fn get_total_balance(env: Env, currency: Symbol) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TotalBalance(currency))
+            .unwrap_or(0)
+    }
+
+    /// Compares internal balances with the contract's token holdings for
+    /// every registered currency.
+    ///
+    /// Balances in registered currencies only change with matching token
+    /// movements, so a non-zero `difference` means tokens were sent to the
+    /// contract directly.
+    pub fn get_reconciliation(env: Env) -> Vec<CurrencyReconciliation> {
+        let mut report = Vec::new(&env);
+        for currency in Self::get_supported_currencies(env.clone()).iter() {
+            let token = Self::get_currency_token(env.clone(), currency.clone())
+                .unwrap_or_else(|| panic_with_error!(&env, WalletError::CurrencyNotSupported));
+            let total_balances = Self::get_total_balance(env.clone(), currency.clone());
+            let token_holdings =
+                token::Client::new(&env, &token).balance(&env.current_contract_address());
+            report.push_back(CurrencyReconciliation {
+                currency,
+                token,
+                total_balances,
+                token_holdings,
+                difference: token_holdings - total_balances,
+            });
+        }
+        report
+    }
+
     /// Retrieves a user's balance for a specific currency.
     ///
     /// # Arguments
@@ -364,6 +801,79 @@ impl MultiCurrencyWalletContract {
             .unwrap_or(0)
     }
 
+    // Internal helper resolving a token to its registered currency
+    fn currency_for_token(env: &Env, token: &Address) -> Symbol {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TokenCurrency(token.clone()))
+            .unwrap_or_else(|| panic_with_error!(env, WalletError::CurrencyNotSupported))
+    }
+
+    // Internal helper requiring a currency to be registered
+    fn require_supported(env: &Env, currency: &Symbol) {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::CurrencyToken(currency.clone()))
+        {
+            panic_with_error!(env, WalletError::CurrencyNotSupported);
+        }
+    }
+
+    // Internal helper adding to a user's balance; returns the new balance
+    fn credit(env: &Env, user: &Address, currency: &Symbol, amount: i128) -> i128 {
+        let balance = Self::get_balance(env.clone(), user.clone(), currency.clone())
+            .checked_add(amount)
+            .unwrap_or_else(|| panic_with_error!(env, WalletError::InvalidAmount));
+        Self::store_balance(env, user, currency, balance);
+        Self::adjust_total_balance(env, currency, amount);
+        balance
+    }
+
+    // Internal helper subtracting from a user's balance; returns the new balance
+    fn debit(env: &Env, user: &Address, currency: &Symbol, amount: i128) -> i128 {
+        let balance = Self::get_balance(env.clone(), user.clone(), currency.clone());
+        if balance < amount {
+            panic_with_error!(env, WalletError::InsufficientBalance);
+        }
+        Self::store_balance(env, user, currency, balance - amount);
+        Self::adjust_total_balance(env, currency, -amount);
+        balance - amount
+    }
+
+    // Internal helper writing a user's balance record
+    fn store_balance(env: &Env, user: &Address, currency: &Symbol, balance: i128) {
+        let record = CurrencyBalance {
+            user: user.clone(),
+            currency: currency.clone(),
+            balance,
+            updated_at: env.ledger().sequence() as u64,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Balance(user.clone(), currency.clone()), &record);
+        Self::track_currency(env, user, currency);
+    }
+
+    // Internal helper indexing the currencies a user holds
+    fn track_currency(env: &Env, user: &Address, currency: &Symbol) {
+        let mut currencies = Self::get_user_currencies(env.clone(), user.clone());
+        if !currencies.contains(currency) {
+            currencies.push_back(currency.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::UserCurrencies(user.clone()), &currencies);
+        }
+    }
+
+    // Internal helper tracking the sum of internal balances per currency
+    fn adjust_total_balance(env: &Env, currency: &Symbol, delta: i128) {
+        let total = Self::get_total_balance(env.clone(), currency.clone()) + delta;
+        env.storage()
+            .persistent()
+            .set(&DataKey::TotalBalance(currency.clone()), &total);
+    }
+
     // Internal helper to verify admin
     fn require_admin(env: &Env, caller: &Address) {
         let admin: Address = env
@@ -2,8 +2,15 @@
 
 #![cfg(test)]
 
-use crate::{MultiCurrencyWalletContract, MultiCurrencyWalletContractClient};
-use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, Symbol, Vec};
+use crate::{
+    ExchangeConfig, ExchangeRate, MultiCurrencyWalletContract, MultiCurrencyWalletContractClient,
+    ValuationConfig, WalletError,
+};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke},
+    token, Address, Env, IntoVal, Symbol, Vec,
+};
 
 use crate::types::{BalanceUpdateRequest, BalanceUpdateResult, ErrorCode};
 
@@ -11,6 +18,9 @@ use crate::types::{BalanceUpdateRequest, BalanceUpdateResult, ErrorCode};
 fn setup_test_contract() -> (Env, Address, MultiCurrencyWalletContractClient<'static>) {
     let env = Env::default();
     env.mock_all_auths();
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12345;
+    });
 
     let contract_id = env.register(MultiCurrencyWalletContract, ());
     let client = MultiCurrencyWalletContractClient::new(&env, &contract_id);
@@ -393,7 +403,7 @@ fn test_get_balance_details() {
     assert_eq!(details.user, user);
     assert_eq!(details.currency, symbol_short!("USDC"));
     assert_eq!(details.balance, 1000_000_000);
-    assert!(details.updated_at > 0);
+    assert_eq!(details.updated_at, 12345);
 }
 
 #[test]
@@ -636,3 +646,398 @@ fn test_minimum_valid_balance() {
     assert_eq!(result.failed, 0);
     assert_eq!(client.get_balance(&user, &symbol_short!("USDC")), 1);
 }
+
+// ========== Token Custody Tests ==========
+
+/// Registers a new Stellar Asset Contract as `currency` and mints `amount` to `user`.
+fn register_token(
+    env: &Env,
+    admin: &Address,
+    client: &MultiCurrencyWalletContractClient<'static>,
+    currency: Symbol,
+    user: &Address,
+    amount: i128,
+) -> token::Client<'static> {
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    token::StellarAssetClient::new(env, &token_id).mint(user, &amount);
+    client.register_currency(admin, &currency, &token_id);
+    token::Client::new(env, &token_id)
+}
+
+#[test]
+fn test_deposit_and_withdraw_move_tokens() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let usdc = register_token(&env, &admin, &client, symbol_short!("USDC"), &user, 1_000);
+
+    assert_eq!(client.deposit(&user, &usdc.address, &600), 600);
+    assert_eq!(client.get_balance(&user, &symbol_short!("USDC")), 600);
+    assert_eq!(usdc.balance(&client.address), 600);
+
+    assert_eq!(client.withdraw(&user, &usdc.address, &250), 350);
+    assert_eq!(usdc.balance(&user), 650);
+    assert_eq!(client.get_total_balance(&symbol_short!("USDC")), 350);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_withdraw_more_than_balance_fails() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let usdc = register_token(&env, &admin, &client, symbol_short!("USDC"), &user, 1_000);
+
+    client.deposit(&user, &usdc.address, &100);
+    client.withdraw(&user, &usdc.address, &101);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_deposit_unregistered_token_fails() {
+    let (env, _admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let token_id = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+
+    client.deposit(&user, &token_id, &100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")]
+fn test_register_currency_twice_fails() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let usdc = register_token(&env, &admin, &client, symbol_short!("USDC"), &user, 0);
+
+    client.register_currency(&admin, &symbol_short!("USDC2"), &usdc.address);
+}
+
+#[test]
+fn test_reconciliation_reports_holdings_per_currency() {
+    let (env, admin, client) = setup_test_contract();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let usdc = register_token(&env, &admin, &client, symbol_short!("USDC"), &alice, 1_000);
+    let eurc = register_token(&env, &admin, &client, symbol_short!("EURC"), &bob, 1_000);
+    token::StellarAssetClient::new(&env, &usdc.address).mint(&bob, &1_000);
+
+    client.deposit(&alice, &usdc.address, &300);
+    client.deposit(&bob, &usdc.address, &200);
+    client.deposit(&bob, &eurc.address, &400);
+
+    let report = client.get_reconciliation();
+    assert_eq!(report.len(), 2);
+    for line in report.iter() {
+        assert_eq!(line.difference, 0);
+    }
+    assert_eq!(report.get(0).unwrap().total_balances, 500);
+    assert_eq!(report.get(1).unwrap().token_holdings, 400);
+}
+
+#[test]
+fn test_admin_cannot_edit_token_backed_balances() {
+    let (env, admin, client) = setup_test_contract();
+    let alice = Address::generate(&env);
+    let usdc = register_token(&env, &admin, &client, symbol_short!("USDC"), &alice, 1_000);
+    client.deposit(&alice, &usdc.address, &300);
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &alice,
+        symbol_short!("USDC"),
+        50,
+        symbol_short!("add"),
+    ));
+    let result = client.batch_update_balances(&admin, &requests);
+
+    assert_eq!(result.failed, 1);
+    match result.results.get(0).unwrap() {
+        BalanceUpdateResult::Failure(_, _, code) => {
+            assert_eq!(code, ErrorCode::TOKEN_BACKED_CURRENCY)
+        }
+        _ => panic!("expected the update to fail"),
+    }
+    assert_eq!(client.get_balance(&alice, &symbol_short!("USDC")), 300);
+    assert_eq!(client.get_reconciliation().get(0).unwrap().difference, 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_register_currency_with_admin_balances_fails() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut requests: Vec<BalanceUpdateRequest> = Vec::new(&env);
+    requests.push_back(create_valid_request(
+        &env,
+        &user,
+        symbol_short!("USDC"),
+        50,
+        symbol_short!("set"),
+    ));
+    client.batch_update_balances(&admin, &requests);
+
+    register_token(&env, &admin, &client, symbol_short!("USDC"), &user, 0);
+}
+
+// ========== Transfer and Exchange Tests ==========
+
+/// Rate source returning whatever rates the test sets.
+#[contract]
+pub struct MockRateSource;
+
+#[contractimpl]
+impl MockRateSource {
+    pub fn set_rate(env: Env, base: Symbol, quote: Symbol, rate: i128, timestamp: u64) {
+        env.storage()
+            .instance()
+            .set(&(base, quote), &ExchangeRate { rate, timestamp });
+    }
+
+    pub fn get_rate(env: Env, base: Symbol, quote: Symbol) -> ExchangeRate {
+        env.storage().instance().get(&(base, quote)).unwrap()
+    }
+}
+
+/// Registers USDC and NGN, funds `user` with USDC and a liquidity provider
+/// with NGN, and configures exchange at 1 USDC = 1,500 NGN with a 1% spread.
+/// The rate is published at the current ledger time and stays fresh for 300s.
+fn setup_exchange(
+    env: &Env,
+    admin: &Address,
+    client: &MultiCurrencyWalletContractClient<'static>,
+    user: &Address,
+) -> (Address, MockRateSourceClient<'static>) {
+    let provider = Address::generate(env);
+    let usdc = register_token(env, admin, client, symbol_short!("USDC"), user, 1_000);
+    let ngn = register_token(
+        env,
+        admin,
+        client,
+        symbol_short!("NGN"),
+        &provider,
+        1_000_000,
+    );
+    client.deposit(user, &usdc.address, &1_000);
+    client.deposit(&provider, &ngn.address, &1_000_000);
+
+    let rates = MockRateSourceClient::new(env, &env.register(MockRateSource, ()));
+    rates.set_rate(
+        &symbol_short!("USDC"),
+        &symbol_short!("NGN"),
+        &(1_500 * crate::RATE_SCALE),
+        &env.ledger().timestamp(),
+    );
+    client.set_exchange_config(
+        admin,
+        &ExchangeConfig {
+            rate_source: rates.address.clone(),
+            liquidity_provider: provider.clone(),
+            spread_bps: 100,
+            max_rate_age: 300,
+        },
+    );
+    (provider, rates)
+}
+
+#[test]
+fn test_internal_transfer() {
+    let (env, admin, client) = setup_test_contract();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let usdc = register_token(&env, &admin, &client, symbol_short!("USDC"), &alice, 1_000);
+    client.deposit(&alice, &usdc.address, &1_000);
+
+    client.transfer(&alice, &bob, &symbol_short!("USDC"), &400);
+
+    assert_eq!(client.get_balance(&alice, &symbol_short!("USDC")), 600);
+    assert_eq!(client.get_balance(&bob, &symbol_short!("USDC")), 400);
+    // No tokens moved
+    assert_eq!(usdc.balance(&client.address), 1_000);
+    assert_eq!(client.get_reconciliation().get(0).unwrap().difference, 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_internal_transfer_insufficient_balance_fails() {
+    let (env, admin, client) = setup_test_contract();
+    let alice = Address::generate(&env);
+    register_token(&env, &admin, &client, symbol_short!("USDC"), &alice, 0);
+
+    client.transfer(&alice, &Address::generate(&env), &symbol_short!("USDC"), &1);
+}
+
+#[test]
+fn test_exchange_applies_rate_and_spread() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let (provider, _rates) = setup_exchange(&env, &admin, &client, &user);
+
+    // 100 USDC -> 150,000 NGN less 1% spread
+    let out = client.exchange(
+        &user,
+        &symbol_short!("USDC"),
+        &symbol_short!("NGN"),
+        &100,
+        &148_500,
+    );
+
+    assert_eq!(out, 148_500);
+    assert_eq!(client.get_balance(&user, &symbol_short!("USDC")), 900);
+    assert_eq!(client.get_balance(&user, &symbol_short!("NGN")), 148_500);
+    assert_eq!(client.get_balance(&provider, &symbol_short!("USDC")), 100);
+    assert_eq!(
+        client.get_balance(&provider, &symbol_short!("NGN")),
+        851_500
+    );
+    for line in client.get_reconciliation().iter() {
+        assert_eq!(line.difference, 0);
+    }
+}
+
+#[test]
+fn test_exchange_slippage_protection() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    setup_exchange(&env, &admin, &client, &user);
+
+    let result = client.try_exchange(
+        &user,
+        &symbol_short!("USDC"),
+        &symbol_short!("NGN"),
+        &100,
+        &148_501,
+    );
+    assert_eq!(result, Err(Ok(WalletError::SlippageExceeded.into())));
+}
+
+#[test]
+fn test_exchange_config_requires_provider_consent() {
+    let (env, admin, client) = setup_test_contract();
+    let provider = Address::generate(&env);
+    let config = ExchangeConfig {
+        rate_source: Address::generate(&env),
+        liquidity_provider: provider.clone(),
+        spread_bps: 100,
+        max_rate_age: 300,
+    };
+
+    // Only the admin signs
+    env.mock_auths(&[MockAuth {
+        address: &admin,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "set_exchange_config",
+            args: (admin.clone(), config.clone()).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_set_exchange_config(&admin, &config).is_err());
+    assert_eq!(client.get_exchange_config(), None);
+
+    env.mock_all_auths();
+    client.set_exchange_config(&admin, &config);
+    assert!(env.auths().iter().any(|(signer, _)| *signer == provider));
+}
+
+#[test]
+fn test_exchange_rejects_stale_rate() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    setup_exchange(&env, &admin, &client, &user);
+    env.ledger().set_timestamp(301);
+
+    let result = client.try_exchange(
+        &user,
+        &symbol_short!("USDC"),
+        &symbol_short!("NGN"),
+        &100,
+        &0,
+    );
+    assert_eq!(result, Err(Ok(WalletError::StaleRate.into())));
+    assert_eq!(client.get_balance(&user, &symbol_short!("USDC")), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #12)")]
+fn test_exchange_insufficient_liquidity_fails() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    setup_exchange(&env, &admin, &client, &user);
+
+    client.exchange(
+        &user,
+        &symbol_short!("USDC"),
+        &symbol_short!("NGN"),
+        &700,
+        &0,
+    );
+}
+
+// ========== Portfolio Valuation Tests ==========
+
+#[test]
+fn test_portfolio_value_in_base_currency() {
+    let (env, admin, client) = setup_test_contract();
+    env.ledger().set_timestamp(10_000);
+    let user = Address::generate(&env);
+    let (_provider, rates) = setup_exchange(&env, &admin, &client, &user);
+    client.exchange(
+        &user,
+        &symbol_short!("USDC"),
+        &symbol_short!("NGN"),
+        &100,
+        &0,
+    );
+
+    // 1 NGN = 0.000666 USDC, quoted a minute ago
+    rates.set_rate(
+        &symbol_short!("NGN"),
+        &symbol_short!("USDC"),
+        &6_660,
+        &9_940,
+    );
+    client.set_valuation_config(
+        &admin,
+        &ValuationConfig {
+            price_source: rates.address.clone(),
+            max_rate_age: 300,
+        },
+    );
+
+    let valuation = client.get_portfolio_value(&user, &symbol_short!("USDC"));
+    assert_eq!(valuation.holdings.len(), 2);
+
+    let usdc = valuation.holdings.get(0).unwrap();
+    assert_eq!(usdc.currency, symbol_short!("USDC"));
+    assert_eq!(usdc.value, 900);
+    assert_eq!(usdc.rate, crate::RATE_SCALE);
+
+    let ngn = valuation.holdings.get(1).unwrap();
+    assert_eq!(ngn.balance, 148_500);
+    assert_eq!(ngn.rate_timestamp, 9_940);
+    assert_eq!(ngn.value, 98);
+    assert_eq!(valuation.total_value, 998);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #16)")]
+fn test_portfolio_value_rejects_stale_rate() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let (_provider, rates) = setup_exchange(&env, &admin, &client, &user);
+    env.ledger().set_timestamp(10_000);
+    client.set_valuation_config(
+        &admin,
+        &ValuationConfig {
+            price_source: rates.address.clone(),
+            max_rate_age: 300,
+        },
+    );
+
+    // USDC -> NGN rate was published at time 0
+    client.get_portfolio_value(&user, &symbol_short!("NGN"));
+}
@@ -1,6 +1,6 @@
 //! Data types and events for batch multi-currency wallet operations.
 
-use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+use soroban_sdk::{contractclient, contracttype, symbol_short, Address, Env, Symbol, Vec};
 
 /// Maximum number of balance updates in a single batch for optimization.
 pub const MAX_BATCH_SIZE: u32 = 100;
@@ -11,6 +11,46 @@ pub const MIN_BALANCE: i128 = 1;
 /// Maximum balance value (preventing overflow)
 pub const MAX_BALANCE: i128 = i128::MAX;
 
+/// Fixed-point scale of exchange rates (7 decimals, matching Stellar amounts).
+pub const RATE_SCALE: i128 = 10_000_000;
+
+/// Basis points denominator for exchange fees.
+pub const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Maximum exchange spread fee (10%).
+pub const MAX_SPREAD_BPS: u32 = 1_000;
+
+/// An exchange rate quoted by a rate source.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ExchangeRate {
+    /// Units of the quote currency per unit of the base currency, scaled by `RATE_SCALE`
+    pub rate: i128,
+    /// Ledger timestamp when the rate was published
+    pub timestamp: u64,
+}
+
+/// Interface a rate-source contract must implement to price exchanges.
+#[contractclient(name = "RateSourceClient")]
+pub trait RateSource {
+    /// Returns the rate to convert `base` into `quote`.
+    fn get_rate(env: Env, base: Symbol, quote: Symbol) -> ExchangeRate;
+}
+
+/// Configuration for in-wallet currency exchange.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ExchangeConfig {
+    /// Contract implementing `RateSource`
+    pub rate_source: Address,
+    /// Account whose internal balances provide exchange liquidity and earn the spread
+    pub liquidity_provider: Address,
+    /// Spread fee taken from the converted amount, in basis points
+    pub spread_bps: u32,
+    /// Maximum age of a rate, in seconds, before it is rejected as stale
+    pub max_rate_age: u64,
+}
+
 /// Represents a balance update request for a user in a specific currency.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -39,6 +79,64 @@ pub struct CurrencyBalance {
     pub updated_at: u64,
 }
 
+/// Configuration for valuing portfolios in a base currency.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ValuationConfig {
+    /// Contract implementing `RateSource`
+    pub price_source: Address,
+    /// Maximum age of a rate, in seconds, before it is rejected as stale
+    pub max_rate_age: u64,
+}
+
+/// One currency's contribution to a portfolio valuation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PortfolioHolding {
+    /// Currency identifier
+    pub currency: Symbol,
+    /// User's balance in the currency
+    pub balance: i128,
+    /// Rate to the base currency used, scaled by `RATE_SCALE`
+    pub rate: i128,
+    /// Timestamp of the rate
+    pub rate_timestamp: u64,
+    /// Balance converted into the base currency
+    pub value: i128,
+}
+
+/// A user's balances valued in a single base currency.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PortfolioValuation {
+    /// User's address
+    pub user: Address,
+    /// Currency the portfolio is valued in
+    pub base_currency: Symbol,
+    /// Sum of all holding values
+    pub total_value: i128,
+    /// Per-currency breakdown of non-zero balances
+    pub holdings: Vec<PortfolioHolding>,
+    /// Ledger timestamp of the valuation
+    pub valued_at: u64,
+}
+
+/// Internal balances versus actual token holdings for one currency.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CurrencyReconciliation {
+    /// Currency identifier
+    pub currency: Symbol,
+    /// Token contract backing the currency
+    pub token: Address,
+    /// Sum of all users' internal balances
+    pub total_balances: i128,
+    /// Tokens held by the contract
+    pub token_holdings: i128,
+    /// `token_holdings - total_balances`; zero when fully reconciled
+    pub difference: i128,
+}
+
 /// Result of processing a single balance update.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -97,6 +195,20 @@ pub enum DataKey {
     TotalBalancesUpdated,
     /// Total batches processed lifetime
     TotalBatchesProcessed,
+    /// Token contract registered for a currency symbol
+    CurrencyToken(Symbol),
+    /// Currency symbol registered for a token contract
+    TokenCurrency(Address),
+    /// All registered currency symbols
+    SupportedCurrencies,
+    /// Sum of all users' internal balances in a currency
+    TotalBalance(Symbol),
+    /// In-wallet exchange configuration
+    ExchangeConfig,
+    /// Portfolio valuation configuration
+    ValuationConfig,
+    /// Currencies a user has held a balance in
+    UserCurrencies(Address),
 }
 
 /// Error codes for balance update validation.
@@ -113,6 +225,8 @@ pub mod ErrorCode {
     pub const INSUFFICIENT_BALANCE: u32 = 4;
     /// Arithmetic overflow
     pub const ARITHMETIC_OVERFLOW: u32 = 5;
+    /// Currency is backed by a registered token and only changes through custody
+    pub const TOKEN_BACKED_CURRENCY: u32 = 6;
 }
 
 /// Events emitted by the multi-currency wallet contract.
@@ -157,6 +271,55 @@ impl WalletEvents {
         env.events().publish(topics, (successful, failed));
     }
 
+    /// Event emitted when a currency is mapped to a token contract.
+    pub fn currency_registered(env: &Env, currency: &Symbol, token: &Address) {
+        let topics = (symbol_short!("currency"), symbol_short!("register"));
+        env.events()
+            .publish(topics, (currency.clone(), token.clone()));
+    }
+
+    /// Event emitted when a user deposits tokens into the wallet.
+    pub fn deposited(env: &Env, user: &Address, currency: &Symbol, amount: i128, balance: i128) {
+        let topics = (symbol_short!("balance"), symbol_short!("deposit"));
+        env.events()
+            .publish(topics, (user.clone(), currency.clone(), amount, balance));
+    }
+
+    /// Event emitted when a user withdraws tokens from the wallet.
+    pub fn withdrawn(env: &Env, user: &Address, currency: &Symbol, amount: i128, balance: i128) {
+        let topics = (symbol_short!("balance"), symbol_short!("withdraw"));
+        env.events()
+            .publish(topics, (user.clone(), currency.clone(), amount, balance));
+    }
+
+    /// Event emitted when a user transfers an internal balance to another user.
+    pub fn transferred(env: &Env, from: &Address, to: &Address, currency: &Symbol, amount: i128) {
+        let topics = (symbol_short!("balance"), symbol_short!("transfer"));
+        env.events()
+            .publish(topics, (from.clone(), to.clone(), currency.clone(), amount));
+    }
+
+    /// Event emitted when a user exchanges one currency for another.
+    #[allow(clippy::too_many_arguments)]
+    pub fn exchanged(
+        env: &Env,
+        user: &Address,
+        from_currency: &Symbol,
+        to_currency: &Symbol,
+        amount_in: i128,
+        amount_out: i128,
+        fee: i128,
+        rate: i128,
+    ) {
+        let topics = (
+            symbol_short!("exchange"),
+            from_currency.clone(),
+            to_currency.clone(),
+        );
+        env.events()
+            .publish(topics, (user.clone(), amount_in, amount_out, fee, rate));
+    }
+
     /// Event emitted for large balance updates (>= 1,000,000 units).
     pub fn large_balance_update(
         env: &Env,
@@ -1,8 +1,10 @@
 //! Validation logic for balance update requests.
 
-use soroban_sdk::{Address, Env, Symbol};
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
 
-use crate::types::{BalanceUpdateRequest, DataKey, ErrorCode, MAX_BALANCE, MIN_BALANCE};
+use crate::types::{
+    BalanceUpdateRequest, CurrencyBalance, DataKey, ErrorCode, MAX_BALANCE, MIN_BALANCE,
+};
 
 /// Validates a balance update request.
 ///
@@ -95,6 +97,7 @@ pub fn validate_and_compute_balance(
         .storage()
         .persistent()
         .get(&DataKey::Balance(user.clone(), currency.clone()))
+        .map(|b: CurrencyBalance| b.balance)
         .unwrap_or(0);
 
     // Compute new balance based on operation
@@ -115,19 +118,18 @@ pub fn validate_and_compute_balance(
 
 /// Computes new balance based on operation.
 fn compute_new_balance(current: i128, operation: &Symbol, amount: i128) -> Result<i128, u32> {
-    // Note: In production, use proper symbol comparison
-    // For now, we'll use symbol_short! macro patterns
-    let op_str = operation.to_string();
-
-    match op_str.as_str() {
-        "set" => Ok(amount),
-        "add" => current
+    if *operation == symbol_short!("set") {
+        Ok(amount)
+    } else if *operation == symbol_short!("add") {
+        current
             .checked_add(amount)
-            .ok_or(ErrorCode::ARITHMETIC_OVERFLOW),
-        "subtract" => current
+            .ok_or(ErrorCode::ARITHMETIC_OVERFLOW)
+    } else if *operation == symbol_short!("subtract") {
+        current
             .checked_sub(amount)
-            .ok_or(ErrorCode::ARITHMETIC_OVERFLOW),
-        _ => Err(ErrorCode::INVALID_OPERATION),
+            .ok_or(ErrorCode::ARITHMETIC_OVERFLOW)
+    } else {
+        Err(ErrorCode::INVALID_OPERATION)
     }
 }
 
@@ -4,8 +4,12 @@
 mod test;
 mod types;
 
-use crate::types::{DataKey, RecurringPayment};
-use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, Env};
+use crate::types::{
+    BatchExecutionResult, DataKey, ExecutionResult, ExecutionStatus, MissedPolicy, PaymentSchedule,
+    PlanConfig, RecurringPayment, Subscription, SubscriptionPlan, SubscriptionStatus,
+    DUE_BUCKET_SECONDS, MAX_BATCH_SIZE,
+};
+use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, Env, Vec};
 
 #[contract]
 pub struct RecurringPaymentContract;
@@ -14,8 +18,13 @@ pub struct RecurringPaymentContract;
 impl RecurringPaymentContract {
     /// Creates a new recurring payment schedule.
     ///
+    /// The schedule runs until canceled and executes once per call when
+    /// slots were missed. Use `create_scheduled_payment` for end conditions
+    /// or a different missed-interval policy.
+    ///
     /// # Arguments
-    /// * `sender`     - The address funding the payments (must authorize)
+    /// * `sender`     - The address funding the payments (must authorize; keeper
+    ///                  batches also need this contract approved to spend `token`)
     /// * `recipient`  - The address that receives each payment
     /// * `token`      - The token contract address
     /// * `amount`     - Amount transferred on each execution (must be > 0)
@@ -32,15 +41,48 @@ impl RecurringPaymentContract {
         amount: i128,
         interval: u64,
         start_time: u64,
+    ) -> u64 {
+        let schedule = PaymentSchedule {
+            interval,
+            start_time,
+            max_executions: 0,
+            end_time: 0,
+            missed_policy: MissedPolicy::ExecuteOnce,
+        };
+        Self::create_scheduled_payment(env, sender, recipient, token, amount, schedule)
+    }
+
+    /// Creates a new recurring payment schedule with end conditions.
+    ///
+    /// # Arguments
+    /// * `sender`    - The address funding the payments (must authorize; keeper
+    ///                 batches also need this contract approved to spend `token`)
+    /// * `recipient` - The address that receives each payment
+    /// * `token`     - The token contract address
+    /// * `amount`    - Amount transferred per slot (must be > 0)
+    /// * `schedule`  - Interval, start time, end conditions and missed-interval policy
+    ///
+    /// # Returns
+    /// The unique payment ID assigned to this schedule.
+    pub fn create_scheduled_payment(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        token: Address,
+        amount: i128,
+        schedule: PaymentSchedule,
     ) -> u64 {
         sender.require_auth();
 
         if amount <= 0 {
             panic!("Amount must be positive");
         }
-        if interval == 0 {
+        if schedule.interval == 0 {
             panic!("Interval must be positive");
         }
+        if schedule.end_time != 0 && schedule.end_time <= schedule.start_time {
+            panic!("End time must be after start time");
+        }
 
         let mut count: u64 = env
             .storage()
@@ -54,15 +96,23 @@ impl RecurringPaymentContract {
             recipient,
             token,
             amount,
-            interval,
-            next_execution: start_time,
+            interval: schedule.interval,
+            next_execution: schedule.start_time,
             active: true,
+            paused: false,
+            max_executions: schedule.max_executions,
+            end_time: schedule.end_time,
+            missed_policy: schedule.missed_policy,
+            execution_count: 0,
+            last_execution: 0,
+            keeper_tip: 0,
         };
 
         env.storage()
             .instance()
             .set(&DataKey::Payment(count), &payment);
         env.storage().instance().set(&DataKey::PaymentCount, &count);
+        index_payment(&env, count, payment.next_execution);
 
         env.events().publish(
             (symbol_short!("recur"), symbol_short!("created"), count),
@@ -72,10 +122,17 @@ impl RecurringPaymentContract {
         count
     }
 
+    /// Executes every slot that is due according to the payment's
+    /// missed-interval policy and advances to the next future slot.
+    ///
+    /// The schedule completes once `max_executions` is reached or its next
+    /// slot falls at or after `end_time`. Tokens are transferred with the
+    /// sender's authorization.
+    ///
     /// # Arguments
     /// * `payment_id` - The ID returned by `create_payment`
     pub fn execute_payment(env: Env, payment_id: u64) {
-        let mut payment: RecurringPayment = env
+        let payment: RecurringPayment = env
             .storage()
             .instance()
             .get(&DataKey::Payment(payment_id))
@@ -84,43 +141,199 @@ impl RecurringPaymentContract {
         if !payment.active {
             panic!("Payment is not active");
         }
-
-        let current_time = env.ledger().timestamp();
-        if current_time < payment.next_execution {
+        if payment.paused {
+            panic!("Payment is paused");
+        }
+        if env.ledger().timestamp() < payment.next_execution {
             panic!("Too early for next execution");
         }
 
-        // Transfer tokens from sender to recipient.
-        let token_client = token::Client::new(&env, &payment.token);
-        token_client.transfer(&payment.sender, &payment.recipient, &payment.amount);
+        process_payment(&env, payment_id, payment, None);
+    }
+
+    /// Lists active payments that are due at `now`, ordered by due bucket.
+    ///
+    /// # Arguments
+    /// * `now`    - Timestamp to evaluate against
+    /// * `cursor` - Number of due payments to skip
+    /// * `limit`  - Maximum number of IDs to return
+    pub fn get_due_payments(env: Env, now: u64, cursor: u32, limit: u32) -> Vec<u64> {
+        due_payments(&env, now, cursor, limit)
+    }
+
+    /// Executes due payments for a keeper without aborting on failures.
+    ///
+    /// Each payment reports its own outcome. Payments whose transfer fails
+    /// stay due and are counted into `next_cursor` so later calls move past
+    /// them. Senders are not present to authorize, so tokens are pulled with
+    /// the allowance they gave this contract. The keeper receives each
+    /// executed payment's tip from its sender.
+    ///
+    /// # Arguments
+    /// * `keeper` - The address running the batch (must authorize)
+    /// * `cursor` - Number of due payments to skip
+    /// * `limit`  - Maximum number of payments to process (capped at `MAX_BATCH_SIZE`)
+    pub fn execute_due_batch(
+        env: Env,
+        keeper: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> BatchExecutionResult {
+        keeper.require_auth();
+
+        let now = env.ledger().timestamp();
+        let due = due_payments(&env, now, cursor, limit.min(MAX_BATCH_SIZE));
+
+        let mut results = Vec::new(&env);
+        let mut executed: u32 = 0;
+        let mut failed: u32 = 0;
+        for payment_id in due.iter() {
+            let payment: RecurringPayment = env
+                .storage()
+                .instance()
+                .get(&DataKey::Payment(payment_id))
+                .expect("Payment not found");
+
+            let result = process_payment(&env, payment_id, payment, Some(&keeper));
+            match result.status {
+                ExecutionStatus::Failed => failed += 1,
+                _ => executed += 1,
+            }
+            results.push_back(result);
+        }
 
-        // Update next execution time
-        payment.next_execution += payment.interval;
+        env.events().publish(
+            (symbol_short!("recur"), symbol_short!("batch"), keeper),
+            (executed, failed),
+        );
+
+        BatchExecutionResult {
+            results,
+            executed,
+            failed,
+            next_cursor: cursor + failed,
+        }
+    }
+
+    /// Sets the tip paid to keepers for each batch execution. Only the
+    /// original sender may change it.
+    ///
+    /// # Arguments
+    /// * `payment_id` - The ID returned by `create_payment`
+    /// * `tip`        - Amount paid from the sender per execution (0 = none)
+    pub fn set_keeper_tip(env: Env, payment_id: u64, tip: i128) {
+        let mut payment: RecurringPayment = env
+            .storage()
+            .instance()
+            .get(&DataKey::Payment(payment_id))
+            .expect("Payment not found");
+
+        payment.sender.require_auth();
 
-        // If the execution was delayed, we might want to skip or catch up.
-        // For simplicity, we just add the interval to the scheduled time.
-        // If current_time is way past next_execution, catch up.
-        if payment.next_execution <= current_time {
-            // Option 1: Catch up to the next interval in the future
-            // (current_time - scheduled) / interval * interval + scheduled + interval
-            let intervals_passed = (current_time - payment.next_execution) / payment.interval;
-            payment.next_execution += (intervals_passed + 1) * payment.interval;
+        if tip < 0 {
+            panic!("Tip must not be negative");
         }
 
+        payment.keeper_tip = tip;
         env.storage()
             .instance()
             .set(&DataKey::Payment(payment_id), &payment);
 
         env.events().publish(
-            (
-                symbol_short!("recur"),
-                symbol_short!("executed"),
-                payment_id,
-            ),
-            (payment.amount, payment.next_execution),
+            (symbol_short!("recur"), symbol_short!("tip"), payment_id),
+            tip,
         );
     }
 
+    /// Pauses a recurring payment. Only the original sender may pause.
+    ///
+    /// # Arguments
+    /// * `payment_id` - The ID returned by `create_payment`
+    pub fn pause_payment(env: Env, payment_id: u64) {
+        let mut payment: RecurringPayment = env
+            .storage()
+            .instance()
+            .get(&DataKey::Payment(payment_id))
+            .expect("Payment not found");
+
+        payment.sender.require_auth();
+
+        if !payment.active {
+            panic!("Payment is not active");
+        }
+        if payment.paused {
+            panic!("Payment is already paused");
+        }
+
+        payment.paused = true;
+        env.storage()
+            .instance()
+            .set(&DataKey::Payment(payment_id), &payment);
+        unindex_payment(&env, payment_id, payment.next_execution);
+
+        env.events().publish(
+            (symbol_short!("recur"), symbol_short!("paused"), payment_id),
+            payment.sender,
+        );
+    }
+
+    /// Resumes a paused payment. Only the original sender may resume.
+    ///
+    /// Slots that fell due while paused are not paid; the schedule continues
+    /// from the first slot at or after the current time.
+    ///
+    /// # Arguments
+    /// * `payment_id` - The ID returned by `create_payment`
+    pub fn resume_payment(env: Env, payment_id: u64) {
+        let mut payment: RecurringPayment = env
+            .storage()
+            .instance()
+            .get(&DataKey::Payment(payment_id))
+            .expect("Payment not found");
+
+        payment.sender.require_auth();
+
+        if !payment.active {
+            panic!("Payment is not active");
+        }
+        if !payment.paused {
+            panic!("Payment is not paused");
+        }
+
+        let current_time = env.ledger().timestamp();
+        if payment.next_execution < current_time {
+            let missed = (current_time - payment.next_execution).div_ceil(payment.interval);
+            payment.next_execution += missed * payment.interval;
+        }
+        payment.paused = false;
+
+        let completed = schedule_finished(&payment);
+        if completed {
+            payment.active = false;
+        } else {
+            index_payment(&env, payment_id, payment.next_execution);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Payment(payment_id), &payment);
+
+        env.events().publish(
+            (symbol_short!("recur"), symbol_short!("resumed"), payment_id),
+            payment.next_execution,
+        );
+        if completed {
+            env.events().publish(
+                (
+                    symbol_short!("recur"),
+                    symbol_short!("completed"),
+                    payment_id,
+                ),
+                payment.execution_count,
+            );
+        }
+    }
+
     /// Cancels a recurring payment. Only the original sender may cancel.
     ///
     /// # Arguments
@@ -142,6 +355,9 @@ impl RecurringPaymentContract {
         env.storage()
             .instance()
             .set(&DataKey::Payment(payment_id), &payment);
+        if !payment.paused {
+            unindex_payment(&env, payment_id, payment.next_execution);
+        }
 
         env.events().publish(
             (
@@ -153,6 +369,300 @@ impl RecurringPaymentContract {
         );
     }
 
+    /// Publishes a subscription plan billed to `merchant`.
+    ///
+    /// # Arguments
+    /// * `merchant` - The address receiving each charge (must authorize)
+    /// * `token`    - The token contract address
+    /// * `config`   - Price, interval, trial, intro pricing and grace period
+    ///
+    /// # Returns
+    /// The unique plan ID.
+    pub fn create_plan(env: Env, merchant: Address, token: Address, config: PlanConfig) -> u64 {
+        merchant.require_auth();
+
+        if config.price <= 0 {
+            panic!("Price must be positive");
+        }
+        if config.interval == 0 {
+            panic!("Interval must be positive");
+        }
+        if config.intro_periods > 0 && config.intro_price <= 0 {
+            panic!("Intro price must be positive");
+        }
+
+        let mut count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PlanCount)
+            .unwrap_or(0);
+        count += 1;
+
+        let plan = SubscriptionPlan {
+            merchant: merchant.clone(),
+            token,
+            config,
+            active: true,
+        };
+        env.storage().instance().set(&DataKey::Plan(count), &plan);
+        env.storage().instance().set(&DataKey::PlanCount, &count);
+
+        env.events().publish(
+            (symbol_short!("plan"), symbol_short!("created"), count),
+            merchant,
+        );
+
+        count
+    }
+
+    /// Closes a plan to new subscribers. Existing subscriptions keep billing.
+    ///
+    /// # Arguments
+    /// * `plan_id` - The ID returned by `create_plan`
+    pub fn deactivate_plan(env: Env, plan_id: u64) {
+        let mut plan: SubscriptionPlan = env
+            .storage()
+            .instance()
+            .get(&DataKey::Plan(plan_id))
+            .expect("Plan not found");
+
+        plan.merchant.require_auth();
+
+        if !plan.active {
+            panic!("Plan is not active");
+        }
+
+        plan.active = false;
+        env.storage().instance().set(&DataKey::Plan(plan_id), &plan);
+
+        env.events().publish(
+            (symbol_short!("plan"), symbol_short!("closed"), plan_id),
+            plan.merchant,
+        );
+    }
+
+    /// Subscribes to a plan. The subscriber must approve this contract to
+    /// spend the plan's token; charges are pulled from that allowance.
+    ///
+    /// The first charge is due when the trial period ends, or immediately
+    /// when the plan has no trial.
+    ///
+    /// # Arguments
+    /// * `subscriber` - The address paying for the subscription (must authorize)
+    /// * `plan_id`    - The ID returned by `create_plan`
+    ///
+    /// # Returns
+    /// The unique subscription ID.
+    pub fn subscribe(env: Env, subscriber: Address, plan_id: u64) -> u64 {
+        subscriber.require_auth();
+
+        let plan: SubscriptionPlan = env
+            .storage()
+            .instance()
+            .get(&DataKey::Plan(plan_id))
+            .expect("Plan not found");
+        if !plan.active {
+            panic!("Plan is not active");
+        }
+
+        let mut count: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::SubscriptionCount)
+            .unwrap_or(0);
+        count += 1;
+
+        let now = env.ledger().timestamp();
+        let status = if plan.config.trial_period > 0 {
+            SubscriptionStatus::Trialing
+        } else {
+            SubscriptionStatus::Active
+        };
+        let subscription = Subscription {
+            plan_id,
+            subscriber: subscriber.clone(),
+            status,
+            next_charge: now + plan.config.trial_period,
+            charges_made: 0,
+            last_charge: 0,
+            grace_ends: 0,
+        };
+        env.storage()
+            .instance()
+            .set(&DataKey::Subscription(count), &subscription);
+        env.storage()
+            .instance()
+            .set(&DataKey::SubscriptionCount, &count);
+
+        env.events().publish(
+            (symbol_short!("sub"), symbol_short!("started"), count),
+            (subscriber, plan_id),
+        );
+
+        count
+    }
+
+    /// Pulls the current charge of a subscription into the merchant's
+    /// account. May be called by the merchant or any keeper.
+    ///
+    /// A failed pull does not panic: the subscription enters its grace
+    /// period, and becomes past due once the grace period has passed. A
+    /// later successful pull makes it active again.
+    ///
+    /// # Arguments
+    /// * `subscription_id` - The ID returned by `subscribe`
+    ///
+    /// # Returns
+    /// The subscription status after the charge attempt.
+    pub fn charge_subscription(env: Env, subscription_id: u64) -> SubscriptionStatus {
+        let mut subscription: Subscription = env
+            .storage()
+            .instance()
+            .get(&DataKey::Subscription(subscription_id))
+            .expect("Subscription not found");
+
+        if subscription.status == SubscriptionStatus::Canceled {
+            panic!("Subscription is canceled");
+        }
+
+        let now = env.ledger().timestamp();
+        if now < subscription.next_charge {
+            panic!("Too early for next charge");
+        }
+
+        let plan: SubscriptionPlan = env
+            .storage()
+            .instance()
+            .get(&DataKey::Plan(subscription.plan_id))
+            .expect("Plan not found");
+        let amount = if subscription.charges_made < plan.config.intro_periods {
+            plan.config.intro_price
+        } else {
+            plan.config.price
+        };
+
+        let token_client = token::Client::new(&env, &plan.token);
+        let charged = token_client
+            .try_transfer_from(
+                &env.current_contract_address(),
+                &subscription.subscriber,
+                &plan.merchant,
+                &amount,
+            )
+            .is_ok();
+
+        if charged {
+            subscription.status = SubscriptionStatus::Active;
+            subscription.charges_made += 1;
+            subscription.last_charge = now;
+            subscription.grace_ends = 0;
+            // Billing resumes from now rather than back-charging missed periods.
+            subscription.next_charge = if subscription.next_charge + plan.config.interval <= now {
+                now + plan.config.interval
+            } else {
+                subscription.next_charge + plan.config.interval
+            };
+
+            env.events().publish(
+                (
+                    symbol_short!("sub"),
+                    symbol_short!("charged"),
+                    subscription_id,
+                ),
+                (amount, subscription.next_charge),
+            );
+        } else {
+            match subscription.status {
+                SubscriptionStatus::Grace | SubscriptionStatus::PastDue => {}
+                _ => {
+                    subscription.status = SubscriptionStatus::Grace;
+                    subscription.grace_ends = now + plan.config.grace_period;
+                }
+            }
+            if now >= subscription.grace_ends {
+                subscription.status = SubscriptionStatus::PastDue;
+            }
+
+            env.events().publish(
+                (
+                    symbol_short!("sub"),
+                    symbol_short!("failed"),
+                    subscription_id,
+                ),
+                (amount, subscription.status),
+            );
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Subscription(subscription_id), &subscription);
+
+        subscription.status
+    }
+
+    /// Cancels a subscription. No further charges can be pulled.
+    ///
+    /// # Arguments
+    /// * `caller`          - The subscriber or the plan's merchant (must authorize)
+    /// * `subscription_id` - The ID returned by `subscribe`
+    pub fn cancel_subscription(env: Env, caller: Address, subscription_id: u64) {
+        caller.require_auth();
+
+        let mut subscription: Subscription = env
+            .storage()
+            .instance()
+            .get(&DataKey::Subscription(subscription_id))
+            .expect("Subscription not found");
+        let plan: SubscriptionPlan = env
+            .storage()
+            .instance()
+            .get(&DataKey::Plan(subscription.plan_id))
+            .expect("Plan not found");
+
+        if caller != subscription.subscriber && caller != plan.merchant {
+            panic!("Not authorized to cancel");
+        }
+        if subscription.status == SubscriptionStatus::Canceled {
+            panic!("Subscription is already canceled");
+        }
+
+        subscription.status = SubscriptionStatus::Canceled;
+        env.storage()
+            .instance()
+            .set(&DataKey::Subscription(subscription_id), &subscription);
+
+        env.events().publish(
+            (
+                symbol_short!("sub"),
+                symbol_short!("canceled"),
+                subscription_id,
+            ),
+            caller,
+        );
+    }
+
+    /// Returns the details of a subscription plan.
+    ///
+    /// # Arguments
+    /// * `plan_id` - The ID returned by `create_plan`
+    pub fn get_plan(env: Env, plan_id: u64) -> SubscriptionPlan {
+        env.storage()
+            .instance()
+            .get(&DataKey::Plan(plan_id))
+            .expect("Plan not found")
+    }
+
+    /// Returns the details of a subscription.
+    ///
+    /// # Arguments
+    /// * `subscription_id` - The ID returned by `subscribe`
+    pub fn get_subscription(env: Env, subscription_id: u64) -> Subscription {
+        env.storage()
+            .instance()
+            .get(&DataKey::Subscription(subscription_id))
+            .expect("Subscription not found")
+    }
+
     /// Returns the full details of a payment schedule.
     ///
     /// # Arguments
@@ -164,3 +674,243 @@ impl RecurringPaymentContract {
             .expect("Payment not found")
     }
 }
+
+/// Settles every slot of a due payment according to its missed-interval
+/// policy and advances the schedule.
+///
+/// Without a keeper the sender authorizes the transfer directly and any
+/// failure panics. For keeper batches tokens are pulled with the sender's
+/// allowance; when the payment transfer fails the schedule is left
+/// untouched and `Failed` is returned. A failed keeper tip does not undo
+/// the payment.
+fn process_payment(
+    env: &Env,
+    payment_id: u64,
+    mut payment: RecurringPayment,
+    keeper: Option<&Address>,
+) -> ExecutionResult {
+    let current_time = env.ledger().timestamp();
+
+    // Slots that have become due, including the scheduled one.
+    let due = (current_time - payment.next_execution) / payment.interval + 1;
+    let slots = match payment.missed_policy {
+        MissedPolicy::ExecuteOnce => 1,
+        MissedPolicy::CatchUp => due,
+        // Only the latest slot is still current; earlier ones are forfeited
+        MissedPolicy::Skip => 1,
+    }
+    .min(remaining_slots(&payment));
+
+    let paid = payment
+        .amount
+        .checked_mul(slots as i128)
+        .expect("Payment amount overflow");
+    let mut tip = 0;
+    if slots > 0 {
+        // Transfer tokens from sender to recipient.
+        let token_client = token::Client::new(env, &payment.token);
+        let spender = env.current_contract_address();
+        let transferred = match keeper {
+            Some(_) => token_client
+                .try_transfer_from(&spender, &payment.sender, &payment.recipient, &paid)
+                .is_ok(),
+            None => {
+                token_client.transfer(&payment.sender, &payment.recipient, &paid);
+                true
+            }
+        };
+        if !transferred {
+            env.events().publish(
+                (symbol_short!("recur"), symbol_short!("failed"), payment_id),
+                paid,
+            );
+            return ExecutionResult {
+                payment_id,
+                status: ExecutionStatus::Failed,
+                amount: 0,
+                tip: 0,
+            };
+        }
+
+        if let Some(keeper) = keeper {
+            if payment.keeper_tip > 0
+                && token_client
+                    .try_transfer_from(&spender, &payment.sender, keeper, &payment.keeper_tip)
+                    .is_ok()
+            {
+                tip = payment.keeper_tip;
+            }
+        }
+
+        payment.execution_count = payment
+            .execution_count
+            .saturating_add(slots.min(u32::MAX as u64) as u32);
+        payment.last_execution = current_time;
+    }
+
+    unindex_payment(env, payment_id, payment.next_execution);
+    payment.next_execution += due * payment.interval;
+    let completed = schedule_finished(&payment);
+    if completed {
+        payment.active = false;
+    } else {
+        index_payment(env, payment_id, payment.next_execution);
+    }
+
+    env.storage()
+        .instance()
+        .set(&DataKey::Payment(payment_id), &payment);
+
+    if slots > 0 {
+        env.events().publish(
+            (
+                symbol_short!("recur"),
+                symbol_short!("executed"),
+                payment_id,
+            ),
+            (paid, payment.next_execution),
+        );
+    } else {
+        env.events().publish(
+            (symbol_short!("recur"), symbol_short!("skipped"), payment_id),
+            (due, payment.next_execution),
+        );
+    }
+    if completed {
+        env.events().publish(
+            (
+                symbol_short!("recur"),
+                symbol_short!("completed"),
+                payment_id,
+            ),
+            payment.execution_count,
+        );
+    }
+
+    ExecutionResult {
+        payment_id,
+        status: if slots > 0 {
+            ExecutionStatus::Executed
+        } else {
+            ExecutionStatus::Skipped
+        },
+        amount: paid,
+        tip,
+    }
+}
+
+/// Collects up to `limit` due payment IDs after skipping `cursor` of them.
+fn due_payments(env: &Env, now: u64, cursor: u32, limit: u32) -> Vec<u64> {
+    let buckets: Vec<u64> = env
+        .storage()
+        .instance()
+        .get(&DataKey::DueBuckets)
+        .unwrap_or(Vec::new(env));
+
+    let mut ids = Vec::new(env);
+    let mut skipped: u32 = 0;
+    for bucket in buckets.iter() {
+        if bucket * DUE_BUCKET_SECONDS > now || ids.len() >= limit {
+            break;
+        }
+        let entries: Vec<u64> = env
+            .storage()
+            .instance()
+            .get(&DataKey::DueBucket(bucket))
+            .unwrap_or(Vec::new(env));
+        for payment_id in entries.iter() {
+            if ids.len() >= limit {
+                break;
+            }
+            let payment: RecurringPayment = env
+                .storage()
+                .instance()
+                .get(&DataKey::Payment(payment_id))
+                .expect("Payment not found");
+            if payment.next_execution > now {
+                continue;
+            }
+            if skipped < cursor {
+                skipped += 1;
+                continue;
+            }
+            ids.push_back(payment_id);
+        }
+    }
+    ids
+}
+
+/// Adds a payment to the due bucket of its next execution.
+fn index_payment(env: &Env, payment_id: u64, next_execution: u64) {
+    let bucket = next_execution / DUE_BUCKET_SECONDS;
+    let key = DataKey::DueBucket(bucket);
+    let mut entries: Vec<u64> = env.storage().instance().get(&key).unwrap_or(Vec::new(env));
+
+    if entries.is_empty() {
+        let mut buckets: Vec<u64> = env
+            .storage()
+            .instance()
+            .get(&DataKey::DueBuckets)
+            .unwrap_or(Vec::new(env));
+        let position = match buckets.binary_search(bucket) {
+            Ok(position) | Err(position) => position,
+        };
+        buckets.insert(position, bucket);
+        env.storage().instance().set(&DataKey::DueBuckets, &buckets);
+    }
+
+    entries.push_back(payment_id);
+    env.storage().instance().set(&key, &entries);
+}
+
+/// Removes a payment from the due bucket of its next execution.
+fn unindex_payment(env: &Env, payment_id: u64, next_execution: u64) {
+    let bucket = next_execution / DUE_BUCKET_SECONDS;
+    let key = DataKey::DueBucket(bucket);
+    let mut entries: Vec<u64> = env.storage().instance().get(&key).unwrap_or(Vec::new(env));
+
+    if let Some(position) = entries.first_index_of(payment_id) {
+        entries.remove(position);
+    }
+
+    if entries.is_empty() {
+        env.storage().instance().remove(&key);
+        let mut buckets: Vec<u64> = env
+            .storage()
+            .instance()
+            .get(&DataKey::DueBuckets)
+            .unwrap_or(Vec::new(env));
+        if let Ok(position) = buckets.binary_search(bucket) {
+            buckets.remove(position);
+            env.storage().instance().set(&DataKey::DueBuckets, &buckets);
+        }
+    } else {
+        env.storage().instance().set(&key, &entries);
+    }
+}
+
+/// Returns how many more slots the payment may execute before reaching
+/// `max_executions` or `end_time`.
+fn remaining_slots(payment: &RecurringPayment) -> u64 {
+    let by_count = if payment.max_executions == 0 {
+        u64::MAX
+    } else {
+        payment
+            .max_executions
+            .saturating_sub(payment.execution_count) as u64
+    };
+    let by_time = if payment.end_time == 0 {
+        u64::MAX
+    } else {
+        payment
+            .end_time
+            .saturating_sub(payment.next_execution)
+            .div_ceil(payment.interval)
+    };
+    by_count.min(by_time)
+}
+
+/// Returns `true` once the payment has no slots left to execute.
+fn schedule_finished(payment: &RecurringPayment) -> bool {
+    remaining_slots(payment) == 0
+}
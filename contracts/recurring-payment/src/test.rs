@@ -2,17 +2,28 @@
 
 use super::*;
 use soroban_sdk::testutils::{Address as _, Ledger};
-use soroban_sdk::{token, Address, Env};
+use soroban_sdk::{token, vec, Address, Env};
 
 fn create_token_contract<'a>(e: &Env, admin: &Address) -> (Address, token::Client<'a>) {
-    let addr = e.register_stellar_asset_contract(admin.clone());
+    let addr = e
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
     (addr.clone(), token::Client::new(e, &addr))
 }
 
+fn mint(e: &Env, token: &Address, to: &Address, amount: i128) {
+    token::StellarAssetClient::new(e, token).mint(to, &amount);
+}
+
+/// Lets the contract pull `amount` of `token` from `owner`.
+fn approve(e: &Env, token: &Address, owner: &Address, spender: &Address, amount: i128) {
+    token::Client::new(e, token).approve(owner, spender, &amount, &1_000);
+}
+
 #[test]
 fn test_recurring_payment_flow() {
     let env = Env::default();
-    env.mock_all_auths();
+    env.mock_all_auths_allowing_non_root_auth();
 
     let admin = Address::generate(&env);
     let sender = Address::generate(&env);
@@ -23,9 +34,9 @@ fn test_recurring_payment_flow() {
     let interval = 3600u64; // 1 hour
     let start_time = 1000u64;
 
-    token_client.mint(&sender, &5000i128);
+    mint(&env, &token_addr, &sender, 5000);
 
-    let contract_id = env.register_contract(None, RecurringPaymentContract);
+    let contract_id = env.register(RecurringPaymentContract, ());
     let client = RecurringPaymentContractClient::new(&env, &contract_id);
 
     // 1. Create payment
@@ -77,7 +88,7 @@ fn test_create_with_zero_amount() {
     let recipient = Address::generate(&env);
     let token = Address::generate(&env);
 
-    let contract_id = env.register_contract(None, RecurringPaymentContract);
+    let contract_id = env.register(RecurringPaymentContract, ());
     let client = RecurringPaymentContractClient::new(&env, &contract_id);
 
     client.create_payment(&sender, &recipient, &token, &0, &3600, &1000);
@@ -86,7 +97,7 @@ fn test_create_with_zero_amount() {
 #[test]
 fn test_execute_with_delay() {
     let env = Env::default();
-    env.mock_all_auths();
+    env.mock_all_auths_allowing_non_root_auth();
 
     let admin = Address::generate(&env);
     let sender = Address::generate(&env);
@@ -97,9 +108,9 @@ fn test_execute_with_delay() {
     let interval = 3600u64;
     let start_time = 1000u64;
 
-    token_client.mint(&sender, &5000i128);
+    mint(&env, &token_addr, &sender, 5000);
 
-    let contract_id = env.register_contract(None, RecurringPaymentContract);
+    let contract_id = env.register(RecurringPaymentContract, ());
     let client = RecurringPaymentContractClient::new(&env, &contract_id);
 
     client.create_payment(
@@ -120,3 +131,527 @@ fn test_execute_with_delay() {
     assert_eq!(payment.next_execution, start_time + 3 * interval);
     assert_eq!(token_client.balance(&recipient), 1000);
 }
+
+fn setup<'a>(
+    env: &'a Env,
+) -> (
+    Address,
+    Address,
+    Address,
+    token::Client<'a>,
+    RecurringPaymentContractClient<'a>,
+) {
+    // Senders authorize direct executions as part of the outer invocation
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let admin = Address::generate(env);
+    let sender = Address::generate(env);
+    let recipient = Address::generate(env);
+    let (token_addr, token_client) = create_token_contract(env, &admin);
+    mint(env, &token_addr, &sender, 10_000);
+
+    let contract_id = env.register(RecurringPaymentContract, ());
+    let client = RecurringPaymentContractClient::new(env, &contract_id);
+    approve(env, &token_addr, &sender, &contract_id, 10_000);
+    (sender, recipient, token_addr, token_client, client)
+}
+
+fn schedule(max_executions: u32, end_time: u64, missed_policy: MissedPolicy) -> PaymentSchedule {
+    PaymentSchedule {
+        interval: 3600,
+        start_time: 1000,
+        max_executions,
+        end_time,
+        missed_policy,
+    }
+}
+
+#[test]
+fn test_execution_count_and_last_execution_recorded() {
+    let env = Env::default();
+    let (sender, recipient, token_addr, _, client) = setup(&env);
+    let id = client.create_payment(&sender, &recipient, &token_addr, &1000, &3600, &1000);
+
+    let payment = client.get_payment(&id);
+    assert_eq!(payment.execution_count, 0);
+    assert_eq!(payment.missed_policy, MissedPolicy::ExecuteOnce);
+
+    env.ledger().set_timestamp(1010);
+    client.execute_payment(&id);
+    env.ledger().set_timestamp(4700);
+    client.execute_payment(&id);
+
+    let payment = client.get_payment(&id);
+    assert_eq!(payment.execution_count, 2);
+    assert_eq!(payment.last_execution, 4700);
+}
+
+#[test]
+fn test_max_executions_completes_schedule() {
+    let env = Env::default();
+    let (sender, recipient, token_addr, token_client, client) = setup(&env);
+    let id = client.create_scheduled_payment(
+        &sender,
+        &recipient,
+        &token_addr,
+        &1000,
+        &schedule(2, 0, MissedPolicy::ExecuteOnce),
+    );
+
+    env.ledger().set_timestamp(1000);
+    client.execute_payment(&id);
+    assert!(client.get_payment(&id).active);
+
+    env.ledger().set_timestamp(4600);
+    client.execute_payment(&id);
+
+    let payment = client.get_payment(&id);
+    assert!(!payment.active);
+    assert_eq!(payment.execution_count, 2);
+    assert_eq!(token_client.balance(&recipient), 2000);
+}
+
+#[test]
+fn test_end_time_completes_schedule() {
+    let env = Env::default();
+    let (sender, recipient, token_addr, token_client, client) = setup(&env);
+    // Slots at 1000 and 4600 fall before the end time, 8200 does not
+    let id = client.create_scheduled_payment(
+        &sender,
+        &recipient,
+        &token_addr,
+        &1000,
+        &schedule(0, 8000, MissedPolicy::CatchUp),
+    );
+
+    env.ledger().set_timestamp(20_000);
+    client.execute_payment(&id);
+
+    let payment = client.get_payment(&id);
+    assert!(!payment.active);
+    assert_eq!(payment.execution_count, 2);
+    assert_eq!(token_client.balance(&recipient), 2000);
+}
+
+#[test]
+#[should_panic(expected = "End time must be after start time")]
+fn test_end_time_before_start_rejected() {
+    let env = Env::default();
+    let (sender, recipient, token_addr, _, client) = setup(&env);
+    client.create_scheduled_payment(
+        &sender,
+        &recipient,
+        &token_addr,
+        &1000,
+        &schedule(0, 1000, MissedPolicy::ExecuteOnce),
+    );
+}
+
+#[test]
+fn test_catch_up_pays_every_missed_slot() {
+    let env = Env::default();
+    let (sender, recipient, token_addr, token_client, client) = setup(&env);
+    let id = client.create_scheduled_payment(
+        &sender,
+        &recipient,
+        &token_addr,
+        &1000,
+        &schedule(0, 0, MissedPolicy::CatchUp),
+    );
+
+    // Slots at 1000, 4600 and 8200 are due
+    env.ledger().set_timestamp(1000 + 3600 * 2 + 500);
+    client.execute_payment(&id);
+
+    let payment = client.get_payment(&id);
+    assert_eq!(token_client.balance(&recipient), 3000);
+    assert_eq!(payment.execution_count, 3);
+    assert_eq!(payment.next_execution, 1000 + 3600 * 3);
+}
+
+#[test]
+fn test_catch_up_respects_max_executions() {
+    let env = Env::default();
+    let (sender, recipient, token_addr, token_client, client) = setup(&env);
+    let id = client.create_scheduled_payment(
+        &sender,
+        &recipient,
+        &token_addr,
+        &1000,
+        &schedule(2, 0, MissedPolicy::CatchUp),
+    );
+
+    env.ledger().set_timestamp(1000 + 3600 * 4);
+    client.execute_payment(&id);
+
+    assert_eq!(token_client.balance(&recipient), 2000);
+    assert!(!client.get_payment(&id).active);
+}
+
+#[test]
+fn test_skip_policy_forfeits_missed_slots() {
+    let env = Env::default();
+    let (sender, recipient, token_addr, token_client, client) = setup(&env);
+    let id = client.create_scheduled_payment(
+        &sender,
+        &recipient,
+        &token_addr,
+        &1000,
+        &schedule(0, 0, MissedPolicy::Skip),
+    );
+
+    // Within the slot: paid as usual
+    env.ledger().set_timestamp(1500);
+    client.execute_payment(&id);
+    assert_eq!(token_client.balance(&recipient), 1000);
+
+    // The 4600 slot is missed once 8200 is due; 8200 itself is still paid
+    env.ledger().set_timestamp(8300);
+    client.execute_payment(&id);
+
+    let payment = client.get_payment(&id);
+    assert_eq!(token_client.balance(&recipient), 2000);
+    assert_eq!(payment.execution_count, 2);
+    assert_eq!(payment.last_execution, 8300);
+    assert_eq!(payment.next_execution, 11800);
+    assert!(payment.active);
+}
+
+#[test]
+#[should_panic(expected = "Payment is paused")]
+fn test_paused_payment_cannot_execute() {
+    let env = Env::default();
+    let (sender, recipient, token_addr, _, client) = setup(&env);
+    let id = client.create_payment(&sender, &recipient, &token_addr, &1000, &3600, &1000);
+
+    client.pause_payment(&id);
+    assert!(client.get_payment(&id).paused);
+
+    env.ledger().set_timestamp(1000);
+    client.execute_payment(&id);
+}
+
+#[test]
+fn test_resume_skips_slots_missed_while_paused() {
+    let env = Env::default();
+    let (sender, recipient, token_addr, token_client, client) = setup(&env);
+    let id = client.create_scheduled_payment(
+        &sender,
+        &recipient,
+        &token_addr,
+        &1000,
+        &schedule(0, 0, MissedPolicy::CatchUp),
+    );
+
+    client.pause_payment(&id);
+    env.ledger().set_timestamp(1000 + 3600 * 2 + 100);
+    client.resume_payment(&id);
+
+    let payment = client.get_payment(&id);
+    assert!(!payment.paused);
+    assert_eq!(payment.next_execution, 1000 + 3600 * 3);
+
+    env.ledger().set_timestamp(1000 + 3600 * 3);
+    client.execute_payment(&id);
+    assert_eq!(token_client.balance(&recipient), 1000);
+}
+
+#[test]
+fn test_resume_past_end_time_completes_schedule() {
+    let env = Env::default();
+    let (sender, recipient, token_addr, _, client) = setup(&env);
+    let id = client.create_scheduled_payment(
+        &sender,
+        &recipient,
+        &token_addr,
+        &1000,
+        &schedule(0, 5000, MissedPolicy::ExecuteOnce),
+    );
+
+    client.pause_payment(&id);
+    env.ledger().set_timestamp(6000);
+    client.resume_payment(&id);
+
+    assert!(!client.get_payment(&id).active);
+}
+
+#[test]
+#[should_panic(expected = "Payment is not paused")]
+fn test_resume_unpaused_payment_panics() {
+    let env = Env::default();
+    let (sender, recipient, token_addr, _, client) = setup(&env);
+    let id = client.create_payment(&sender, &recipient, &token_addr, &1000, &3600, &1000);
+    client.resume_payment(&id);
+}
+
+#[test]
+fn test_due_payments_listed_in_bucket_order() {
+    let env = Env::default();
+    let (sender, recipient, token_addr, _, client) = setup(&env);
+    let late = client.create_payment(&sender, &recipient, &token_addr, &100, &3600, &9000);
+    let early = client.create_payment(&sender, &recipient, &token_addr, &100, &3600, &1000);
+    let future = client.create_payment(&sender, &recipient, &token_addr, &100, &3600, &50_000);
+    let paused = client.create_payment(&sender, &recipient, &token_addr, &100, &3600, &2000);
+    client.pause_payment(&paused);
+
+    let due = client.get_due_payments(&10_000, &0, &10);
+    assert_eq!(due, vec![&env, early, late]);
+    assert!(!due.contains(future));
+
+    assert_eq!(client.get_due_payments(&10_000, &1, &10), vec![&env, late]);
+    assert_eq!(client.get_due_payments(&10_000, &0, &1), vec![&env, early]);
+    assert!(client.get_due_payments(&500, &0, &10).is_empty());
+}
+
+#[test]
+fn test_execute_due_batch_processes_due_payments() {
+    let env = Env::default();
+    let (sender, recipient, token_addr, token_client, client) = setup(&env);
+    let keeper = Address::generate(&env);
+    let first = client.create_payment(&sender, &recipient, &token_addr, &100, &3600, &1000);
+    let second = client.create_payment(&sender, &recipient, &token_addr, &200, &3600, &2000);
+    client.create_payment(&sender, &recipient, &token_addr, &300, &3600, &90_000);
+    client.set_keeper_tip(&first, &5);
+
+    env.ledger().set_timestamp(2500);
+    let batch = client.execute_due_batch(&keeper, &0, &10);
+
+    assert_eq!(batch.executed, 2);
+    assert_eq!(batch.failed, 0);
+    assert_eq!(batch.next_cursor, 0);
+    let result = batch.results.get(0).unwrap();
+    assert_eq!(result.payment_id, first);
+    assert_eq!(result.status, ExecutionStatus::Executed);
+    assert_eq!(result.tip, 5);
+    assert_eq!(batch.results.get(1).unwrap().payment_id, second);
+
+    assert_eq!(token_client.balance(&recipient), 300);
+    assert_eq!(token_client.balance(&keeper), 5);
+    assert!(client.get_due_payments(&2500, &0, &10).is_empty());
+    assert_eq!(client.get_payment(&first).next_execution, 4600);
+}
+
+#[test]
+fn test_execute_due_batch_reports_failures_without_aborting() {
+    let env = Env::default();
+    let (sender, recipient, token_addr, token_client, client) = setup(&env);
+    let keeper = Address::generate(&env);
+    let poor_sender = Address::generate(&env);
+    let failing = client.create_payment(&poor_sender, &recipient, &token_addr, &100, &3600, &1000);
+    let healthy = client.create_payment(&sender, &recipient, &token_addr, &100, &3600, &1000);
+
+    env.ledger().set_timestamp(1000);
+    let batch = client.execute_due_batch(&keeper, &0, &10);
+
+    assert_eq!(batch.executed, 1);
+    assert_eq!(batch.failed, 1);
+    assert_eq!(batch.next_cursor, 1);
+    assert_eq!(
+        batch.results.get(0).unwrap().status,
+        ExecutionStatus::Failed
+    );
+    assert_eq!(
+        batch.results.get(1).unwrap().status,
+        ExecutionStatus::Executed
+    );
+    assert_eq!(token_client.balance(&recipient), 100);
+
+    // The failed payment stays due and untouched
+    let payment = client.get_payment(&failing);
+    assert_eq!(payment.next_execution, 1000);
+    assert_eq!(payment.execution_count, 0);
+    assert_eq!(client.get_due_payments(&1000, &0, &10), vec![&env, failing]);
+    assert_eq!(client.get_payment(&healthy).execution_count, 1);
+}
+
+#[test]
+fn test_direct_execution_does_not_need_allowance() {
+    let env = Env::default();
+    let (_, recipient, token_addr, token_client, client) = setup(&env);
+    let keeper = Address::generate(&env);
+    let sender = Address::generate(&env);
+    mint(&env, &token_addr, &sender, 1_000);
+    let id = client.create_payment(&sender, &recipient, &token_addr, &100, &3600, &1000);
+
+    // Keeper batches need an allowance the sender never gave
+    env.ledger().set_timestamp(1000);
+    let batch = client.execute_due_batch(&keeper, &0, &10);
+    assert_eq!(batch.failed, 1);
+
+    // The sender's own authorization is enough for direct execution
+    client.execute_payment(&id);
+    assert_eq!(token_client.balance(&recipient), 100);
+    assert_eq!(client.get_payment(&id).execution_count, 1);
+}
+
+#[test]
+fn test_completed_and_canceled_payments_leave_due_index() {
+    let env = Env::default();
+    let (sender, recipient, token_addr, _, client) = setup(&env);
+    let keeper = Address::generate(&env);
+    let once = client.create_scheduled_payment(
+        &sender,
+        &recipient,
+        &token_addr,
+        &100,
+        &schedule(1, 0, MissedPolicy::ExecuteOnce),
+    );
+    let canceled = client.create_payment(&sender, &recipient, &token_addr, &100, &3600, &1000);
+    client.cancel_payment(&canceled);
+
+    env.ledger().set_timestamp(1000);
+    let batch = client.execute_due_batch(&keeper, &0, &10);
+    assert_eq!(batch.executed, 1);
+    assert!(!client.get_payment(&once).active);
+
+    env.ledger().set_timestamp(100_000);
+    assert!(client.get_due_payments(&100_000, &0, &10).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Tip must not be negative")]
+fn test_negative_keeper_tip_rejected() {
+    let env = Env::default();
+    let (sender, recipient, token_addr, _, client) = setup(&env);
+    let id = client.create_payment(&sender, &recipient, &token_addr, &100, &3600, &1000);
+    client.set_keeper_tip(&id, &-1);
+}
+
+fn plan_config(trial_period: u64, intro_periods: u32) -> PlanConfig {
+    PlanConfig {
+        price: 500,
+        interval: 3600,
+        trial_period,
+        intro_price: 100,
+        intro_periods,
+        grace_period: 7200,
+    }
+}
+
+#[test]
+fn test_subscription_trial_and_intro_pricing() {
+    let env = Env::default();
+    let (subscriber, merchant, token_addr, token_client, client) = setup(&env);
+    let plan_id = client.create_plan(&merchant, &token_addr, &plan_config(1000, 1));
+
+    env.ledger().set_timestamp(100);
+    let sub_id = client.subscribe(&subscriber, &plan_id);
+    let subscription = client.get_subscription(&sub_id);
+    assert_eq!(subscription.status, SubscriptionStatus::Trialing);
+    assert_eq!(subscription.next_charge, 1100);
+
+    // First charge after the trial uses the intro price
+    env.ledger().set_timestamp(1100);
+    assert_eq!(
+        client.charge_subscription(&sub_id),
+        SubscriptionStatus::Active
+    );
+    assert_eq!(token_client.balance(&merchant), 100);
+
+    // Then the regular price
+    env.ledger().set_timestamp(4700);
+    client.charge_subscription(&sub_id);
+    assert_eq!(token_client.balance(&merchant), 600);
+
+    let subscription = client.get_subscription(&sub_id);
+    assert_eq!(subscription.charges_made, 2);
+    assert_eq!(subscription.last_charge, 4700);
+    assert_eq!(subscription.next_charge, 8300);
+}
+
+#[test]
+#[should_panic(expected = "Too early for next charge")]
+fn test_late_charge_does_not_allow_immediate_second_charge() {
+    let env = Env::default();
+    let (subscriber, merchant, token_addr, token_client, client) = setup(&env);
+    let plan_id = client.create_plan(&merchant, &token_addr, &plan_config(0, 0));
+    env.ledger().set_timestamp(1000);
+    let sub_id = client.subscribe(&subscriber, &plan_id);
+
+    // Charged more than two intervals late
+    env.ledger().set_timestamp(1000 + 2 * 3600 + 500);
+    client.charge_subscription(&sub_id);
+    assert_eq!(token_client.balance(&merchant), 500);
+    assert_eq!(client.get_subscription(&sub_id).next_charge, 8700 + 3600);
+
+    env.ledger().set_timestamp(8701);
+    client.charge_subscription(&sub_id);
+}
+
+#[test]
+#[should_panic(expected = "Too early for next charge")]
+fn test_charge_during_trial_panics() {
+    let env = Env::default();
+    let (subscriber, merchant, token_addr, _, client) = setup(&env);
+    let plan_id = client.create_plan(&merchant, &token_addr, &plan_config(1000, 0));
+    let sub_id = client.subscribe(&subscriber, &plan_id);
+    client.charge_subscription(&sub_id);
+}
+
+#[test]
+fn test_failed_charge_enters_grace_then_past_due() {
+    let env = Env::default();
+    let (_, merchant, token_addr, token_client, client) = setup(&env);
+    let plan_id = client.create_plan(&merchant, &token_addr, &plan_config(0, 0));
+
+    // Subscriber without balance or allowance
+    let subscriber = Address::generate(&env);
+    env.ledger().set_timestamp(1000);
+    let sub_id = client.subscribe(&subscriber, &plan_id);
+
+    assert_eq!(
+        client.charge_subscription(&sub_id),
+        SubscriptionStatus::Grace
+    );
+    assert_eq!(client.get_subscription(&sub_id).grace_ends, 8200);
+
+    env.ledger().set_timestamp(8200);
+    assert_eq!(
+        client.charge_subscription(&sub_id),
+        SubscriptionStatus::PastDue
+    );
+
+    // Funding the subscriber lets the next retry recover
+    mint(&env, &token_addr, &subscriber, 500);
+    approve(&env, &token_addr, &subscriber, &client.address, 500);
+    assert_eq!(
+        client.charge_subscription(&sub_id),
+        SubscriptionStatus::Active
+    );
+    assert_eq!(token_client.balance(&merchant), 500);
+    assert_eq!(client.get_subscription(&sub_id).grace_ends, 0);
+}
+
+#[test]
+#[should_panic(expected = "Subscription is canceled")]
+fn test_canceled_subscription_cannot_be_charged() {
+    let env = Env::default();
+    let (subscriber, merchant, token_addr, _, client) = setup(&env);
+    let plan_id = client.create_plan(&merchant, &token_addr, &plan_config(0, 0));
+    let sub_id = client.subscribe(&subscriber, &plan_id);
+
+    client.cancel_subscription(&subscriber, &sub_id);
+    client.charge_subscription(&sub_id);
+}
+
+#[test]
+#[should_panic(expected = "Not authorized to cancel")]
+fn test_stranger_cannot_cancel_subscription() {
+    let env = Env::default();
+    let (subscriber, merchant, token_addr, _, client) = setup(&env);
+    let plan_id = client.create_plan(&merchant, &token_addr, &plan_config(0, 0));
+    let sub_id = client.subscribe(&subscriber, &plan_id);
+
+    client.cancel_subscription(&Address::generate(&env), &sub_id);
+}
+
+#[test]
+#[should_panic(expected = "Plan is not active")]
+fn test_deactivated_plan_rejects_subscribers() {
+    let env = Env::default();
+    let (subscriber, merchant, token_addr, _, client) = setup(&env);
+    let plan_id = client.create_plan(&merchant, &token_addr, &plan_config(0, 0));
+
+    client.deactivate_plan(&plan_id);
+    client.subscribe(&subscriber, &plan_id);
+}
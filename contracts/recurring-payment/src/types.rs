@@ -1,10 +1,52 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, Vec};
+
+/// Width of a due-index bucket in seconds.
+pub const DUE_BUCKET_SECONDS: u64 = 3_600;
+
+/// Maximum number of payments processed by one `execute_due_batch` call.
+pub const MAX_BATCH_SIZE: u32 = 50;
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
     Payment(u64),
     PaymentCount,
+    /// Payment IDs whose next execution falls in the bucket.
+    DueBucket(u64),
+    /// Sorted list of non-empty due buckets.
+    DueBuckets,
+    Plan(u64),
+    PlanCount,
+    Subscription(u64),
+    SubscriptionCount,
+}
+
+/// How an execution handles scheduled slots that were missed.
+///
+/// A slot counts as missed once the slot after it has also become due.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MissedPolicy {
+    /// Pay a single amount and realign to the next future slot.
+    ExecuteOnce,
+    /// Pay one amount for every slot that has become due.
+    CatchUp,
+    /// Pay the latest due slot, forfeit the missed ones and realign to the
+    /// next future slot.
+    Skip,
+}
+
+/// Timing and end conditions of a new payment schedule.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentSchedule {
+    pub interval: u64,
+    pub start_time: u64,
+    /// Number of executions after which the schedule completes (0 = unlimited).
+    pub max_executions: u32,
+    /// No slot at or after this timestamp is executed (0 = no end time).
+    pub end_time: u64,
+    pub missed_policy: MissedPolicy,
 }
 
 #[contracttype]
@@ -17,4 +59,90 @@ pub struct RecurringPayment {
     pub interval: u64,
     pub next_execution: u64,
     pub active: bool,
+    pub paused: bool,
+    pub max_executions: u32,
+    pub end_time: u64,
+    pub missed_policy: MissedPolicy,
+    pub execution_count: u32,
+    pub last_execution: u64,
+    /// Paid from the sender to the keeper on each batch execution (0 = none).
+    pub keeper_tip: i128,
+}
+
+/// Outcome of processing one payment in a keeper batch.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExecutionStatus {
+    Executed,
+    Skipped,
+    Failed,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutionResult {
+    pub payment_id: u64,
+    pub status: ExecutionStatus,
+    pub amount: i128,
+    pub tip: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchExecutionResult {
+    pub results: Vec<ExecutionResult>,
+    pub executed: u32,
+    pub failed: u32,
+    /// Cursor to pass to the next call; failed payments stay due and are skipped over.
+    pub next_cursor: u32,
+}
+
+/// Pricing and billing terms of a merchant subscription plan.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanConfig {
+    pub price: i128,
+    pub interval: u64,
+    /// Seconds before the first charge (0 = no trial).
+    pub trial_period: u64,
+    /// Price charged for the first `intro_periods` charges.
+    pub intro_price: i128,
+    /// Number of charges billed at `intro_price` (0 = no intro price).
+    pub intro_periods: u32,
+    /// Seconds a failed charge may be retried before the subscription is past due.
+    pub grace_period: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubscriptionPlan {
+    pub merchant: Address,
+    pub token: Address,
+    pub config: PlanConfig,
+    pub active: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SubscriptionStatus {
+    Trialing,
+    Active,
+    /// A charge failed and can still be retried.
+    Grace,
+    /// The grace period ended without a successful charge.
+    PastDue,
+    Canceled,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Subscription {
+    pub plan_id: u64,
+    pub subscriber: Address,
+    pub status: SubscriptionStatus,
+    pub next_charge: u64,
+    pub charges_made: u32,
+    pub last_charge: u64,
+    /// When a subscription in grace becomes past due (0 when not in grace).
+    pub grace_ends: u64,
 }
@@ -0,0 +1,503 @@
+#![no_std]
+
+mod types;
+mod validation;
+
+use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, Env, Symbol, Vec};
+
+pub use crate::types::{
+    Circle, CircleConfig, CircleStatus, DataKey, PayoutOrder, RoscaEvents, RoundBid,
+    BPS_DENOMINATOR, MAX_CIRCLE_MEMBERS, MIN_CIRCLE_MEMBERS,
+};
+use crate::validation::{validate_amount, validate_members, validate_penalty_bps};
+
+/// Error codes for the rotating savings circle contract.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RoscaError {
+    /// Contract not initialized
+    NotInitialized = 1,
+    /// Caller is not authorized
+    Unauthorized = 2,
+    /// Circle does not exist
+    CircleNotFound = 3,
+    /// Member not found in circle
+    MemberNotFound = 4,
+    /// Invalid amount
+    InvalidAmount = 5,
+    /// Member list is too short, too long or has duplicates
+    InvalidMembers = 6,
+    /// Penalty exceeds 100%
+    InvalidPenalty = 7,
+    /// Round interval must be positive
+    InvalidInterval = 8,
+    /// Circle is not active
+    CircleNotActive = 9,
+    /// Member already contributed this round
+    AlreadyContributed = 10,
+    /// Round interval has not elapsed and contributions are outstanding
+    RoundNotOver = 11,
+    /// Circle does not use bid-based payouts
+    NotBidCircle = 12,
+    /// Member has already received a payout
+    AlreadyReceivedPayout = 13,
+    /// Bid does not beat the current highest bid or exceeds the pot
+    InvalidBid = 14,
+    /// Member has no outstanding debt
+    NoDebt = 15,
+}
+
+impl From<RoscaError> for soroban_sdk::Error {
+    fn from(e: RoscaError) -> Self {
+        soroban_sdk::Error::from_contract_error(e as u32)
+    }
+}
+
+#[contract]
+pub struct RoscaContract;
+
+#[contractimpl]
+impl RoscaContract {
+    /// Initializes the contract with an admin address.
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Contract already initialized");
+        }
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalCirclesCreated, &0u64);
+    }
+
+    /// Creates a new rotating savings circle. The first round starts
+    /// immediately and there is one round per member.
+    pub fn create_circle(
+        env: Env,
+        creator: Address,
+        name: Symbol,
+        members: Vec<Address>,
+        token: Address,
+        config: CircleConfig,
+    ) -> u64 {
+        creator.require_auth();
+
+        validate_members(&members).unwrap_or_else(|_| {
+            panic_with_error!(&env, RoscaError::InvalidMembers);
+        });
+        validate_amount(config.contribution_amount).unwrap_or_else(|_| {
+            panic_with_error!(&env, RoscaError::InvalidAmount);
+        });
+        validate_penalty_bps(config.default_penalty_bps).unwrap_or_else(|_| {
+            panic_with_error!(&env, RoscaError::InvalidPenalty);
+        });
+        if config.round_interval == 0 {
+            panic_with_error!(&env, RoscaError::InvalidInterval);
+        }
+
+        // Get next circle ID and increment counter
+        let circle_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalCirclesCreated)
+            .unwrap_or(0)
+            + 1;
+
+        let now = env.ledger().timestamp();
+        let circle = Circle {
+            id: circle_id,
+            name,
+            creator: creator.clone(),
+            token: token.clone(),
+            members: members.clone(),
+            config,
+            current_round: 0,
+            round_started_at: now,
+            pot: 0,
+            reserve: 0,
+            recipients: Vec::new(&env),
+            status: CircleStatus::Active,
+            created_at: now,
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Circle(circle_id), &circle);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalCirclesCreated, &circle_id);
+
+        // Add members to circle
+        for member in members.iter() {
+            env.storage()
+                .persistent()
+                .set(&DataKey::CircleMember(circle_id, member.clone()), &true);
+        }
+
+        RoscaEvents::circle_created(&env, circle_id, &creator, &members, &token);
+
+        circle_id
+    }
+
+    /// Pays a member's contribution for the current round.
+    pub fn contribute(env: Env, contributor: Address, circle_id: u64) {
+        contributor.require_auth();
+
+        let mut circle = Self::load_active_circle(&env, circle_id);
+        Self::require_member(&env, circle_id, &contributor);
+
+        let key = DataKey::RoundContribution(circle_id, circle.current_round, contributor.clone());
+        if env.storage().persistent().has(&key) {
+            panic_with_error!(&env, RoscaError::AlreadyContributed);
+        }
+
+        let amount = circle.config.contribution_amount;
+        let token_client = token::Client::new(&env, &circle.token);
+        token_client.transfer(&contributor, &env.current_contract_address(), &amount);
+
+        circle.pot += amount;
+        env.storage().persistent().set(&key, &true);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Circle(circle_id), &circle);
+
+        RoscaEvents::contribution_added(
+            &env,
+            circle_id,
+            circle.current_round,
+            &contributor,
+            amount,
+        );
+    }
+
+    /// Bids for the current round's pot in a bid-based circle.
+    ///
+    /// The bid is the amount of the pot the member gives up; the highest
+    /// bid wins when the round closes.
+    pub fn place_bid(env: Env, bidder: Address, circle_id: u64, discount: i128) {
+        bidder.require_auth();
+
+        let circle = Self::load_active_circle(&env, circle_id);
+        if circle.config.payout_order != PayoutOrder::Bid {
+            panic_with_error!(&env, RoscaError::NotBidCircle);
+        }
+        Self::require_member(&env, circle_id, &bidder);
+        if circle.recipients.contains(&bidder) {
+            panic_with_error!(&env, RoscaError::AlreadyReceivedPayout);
+        }
+
+        let full_pot = circle.config.contribution_amount * circle.members.len() as i128;
+        let key = DataKey::RoundBid(circle_id, circle.current_round);
+        let best: Option<RoundBid> = env.storage().persistent().get(&key);
+        let beats_best = best.map(|b| discount > b.discount).unwrap_or(true);
+        if validate_amount(discount).is_err() || discount >= full_pot || !beats_best {
+            panic_with_error!(&env, RoscaError::InvalidBid);
+        }
+
+        env.storage().persistent().set(
+            &key,
+            &RoundBid {
+                bidder: bidder.clone(),
+                discount,
+            },
+        );
+
+        RoscaEvents::bid_placed(&env, circle_id, circle.current_round, &bidder, discount);
+    }
+
+    /// Closes the current round and pays out the pot.
+    ///
+    /// Anyone may call this once the round interval has elapsed, or earlier
+    /// if every member has contributed. Members who have not contributed
+    /// are recorded as defaulted and owe the missed contribution plus the
+    /// circle's penalty. Debt owed by the recipient is withheld from the
+    /// payout and carried into the next round.
+    ///
+    /// Returns the address paid this round.
+    pub fn close_round(env: Env, circle_id: u64) -> Address {
+        let mut circle = Self::load_active_circle(&env, circle_id);
+        let round = circle.current_round;
+        let now = env.ledger().timestamp();
+
+        let mut missing: Vec<Address> = Vec::new(&env);
+        for member in circle.members.iter() {
+            if !Self::has_contributed(env.clone(), circle_id, round, member.clone()) {
+                missing.push_back(member);
+            }
+        }
+        if !missing.is_empty() && now < circle.round_started_at + circle.config.round_interval {
+            panic_with_error!(&env, RoscaError::RoundNotOver);
+        }
+
+        // Record defaults
+        let contribution = circle.config.contribution_amount;
+        let penalty =
+            contribution * circle.config.default_penalty_bps as i128 / BPS_DENOMINATOR as i128;
+        for member in missing.iter() {
+            let defaults = Self::get_member_defaults(env.clone(), circle_id, member.clone());
+            env.storage().persistent().set(
+                &DataKey::MemberDefaults(circle_id, member.clone()),
+                &(defaults + 1),
+            );
+            Self::add_debt(&env, circle_id, &member, contribution + penalty);
+            RoscaEvents::member_defaulted(&env, circle_id, round, &member, penalty);
+        }
+
+        // Pick the recipient
+        let unpaid = Self::unpaid_members(&env, &circle);
+        let bid: Option<RoundBid> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RoundBid(circle_id, round));
+        let (recipient, bid_discount) = match circle.config.payout_order {
+            PayoutOrder::Fixed => (unpaid.get(0).unwrap(), 0),
+            PayoutOrder::Random => {
+                let index = env.prng().gen_range::<u64>(0..unpaid.len() as u64);
+                (unpaid.get(index as u32).unwrap(), 0)
+            }
+            PayoutOrder::Bid => match bid {
+                Some(bid) => (bid.bidder, bid.discount),
+                None => (unpaid.get(0).unwrap(), 0),
+            },
+        };
+
+        // Settle the pot: discount to the other members, recipient debt to reserve
+        let gross = circle.pot + circle.reserve;
+        let discount = bid_discount.min(gross);
+        let withheld =
+            Self::get_member_debt(env.clone(), circle_id, recipient.clone()).min(gross - discount);
+        if withheld > 0 {
+            Self::add_debt(&env, circle_id, &recipient, -withheld);
+        }
+        let payout = gross - discount - withheld;
+
+        let token_client = token::Client::new(&env, &circle.token);
+        if payout > 0 {
+            token_client.transfer(&env.current_contract_address(), &recipient, &payout);
+        }
+        let mut others = circle.members.clone();
+        if let Some(index) = others.first_index_of(&recipient) {
+            others.remove(index);
+        }
+        let dust = Self::split_equally(&env, &circle, discount, &others);
+
+        circle.pot = 0;
+        circle.reserve = withheld + dust;
+        circle.recipients.push_back(recipient.clone());
+        RoscaEvents::round_paid(&env, circle_id, round, &recipient, payout, discount);
+
+        if circle.recipients.len() == circle.members.len() {
+            circle.status = CircleStatus::Completed;
+            let debt_free = Self::debt_free_members(&env, &circle, None);
+            if !debt_free.is_empty() {
+                let reserve = circle.reserve;
+                circle.reserve = Self::split_equally(&env, &circle, reserve, &debt_free);
+            }
+            RoscaEvents::circle_completed(&env, circle_id, circle.recipients.len());
+        } else {
+            circle.current_round += 1;
+            circle.round_started_at = now;
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Circle(circle_id), &circle);
+
+        recipient
+    }
+
+    /// Pays off a member's outstanding missed contributions and penalties.
+    ///
+    /// While the circle is active the payment joins the next pot; once it
+    /// has completed, the payment is shared among debt-free members.
+    ///
+    /// Returns the amount settled.
+    pub fn settle_debt(env: Env, member: Address, circle_id: u64) -> i128 {
+        member.require_auth();
+
+        let mut circle = Self::load_circle(&env, circle_id);
+        let debt = Self::get_member_debt(env.clone(), circle_id, member.clone());
+        if debt <= 0 {
+            panic_with_error!(&env, RoscaError::NoDebt);
+        }
+
+        let token_client = token::Client::new(&env, &circle.token);
+        token_client.transfer(&member, &env.current_contract_address(), &debt);
+        env.storage()
+            .persistent()
+            .set(&DataKey::MemberDebt(circle_id, member.clone()), &0i128);
+
+        let debt_free = Self::debt_free_members(&env, &circle, Some(&member));
+        if circle.status == CircleStatus::Completed && !debt_free.is_empty() {
+            circle.reserve += Self::split_equally(&env, &circle, debt, &debt_free);
+        } else {
+            circle.reserve += debt;
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Circle(circle_id), &circle);
+
+        RoscaEvents::debt_settled(&env, circle_id, &member, debt);
+
+        debt
+    }
+
+    /// Get circle details.
+    pub fn get_circle(env: Env, circle_id: u64) -> Circle {
+        Self::load_circle(&env, circle_id)
+    }
+
+    /// Get member status for a circle.
+    pub fn is_circle_member(env: Env, circle_id: u64, member: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CircleMember(circle_id, member))
+            .unwrap_or(false)
+    }
+
+    /// Returns whether a member has contributed in a given round.
+    pub fn has_contributed(env: Env, circle_id: u64, round: u32, member: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::RoundContribution(circle_id, round, member))
+    }
+
+    /// Returns the highest bid in the current round, if any.
+    pub fn get_current_bid(env: Env, circle_id: u64) -> Option<RoundBid> {
+        let circle = Self::load_circle(&env, circle_id);
+        env.storage()
+            .persistent()
+            .get(&DataKey::RoundBid(circle_id, circle.current_round))
+    }
+
+    /// Returns the number of rounds a member has defaulted on.
+    pub fn get_member_defaults(env: Env, circle_id: u64, member: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MemberDefaults(circle_id, member))
+            .unwrap_or(0)
+    }
+
+    /// Returns the missed contributions plus penalties a member still owes.
+    pub fn get_member_debt(env: Env, circle_id: u64, member: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MemberDebt(circle_id, member))
+            .unwrap_or(0)
+    }
+
+    /// Returns the admin address.
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized")
+    }
+
+    /// Updates the admin address.
+    pub fn set_admin(env: Env, current_admin: Address, new_admin: Address) {
+        current_admin.require_auth();
+        Self::require_admin(&env, &current_admin);
+
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+    }
+
+    /// Returns the total number of circles created.
+    pub fn get_total_circles_created(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalCirclesCreated)
+            .unwrap_or(0)
+    }
+
+    // Internal helper to load a circle
+    fn load_circle(env: &Env, circle_id: u64) -> Circle {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Circle(circle_id))
+            .unwrap_or_else(|| panic_with_error!(env, RoscaError::CircleNotFound))
+    }
+
+    // Internal helper to load a circle that must still be running rounds
+    fn load_active_circle(env: &Env, circle_id: u64) -> Circle {
+        let circle = Self::load_circle(env, circle_id);
+        if circle.status != CircleStatus::Active {
+            panic_with_error!(env, RoscaError::CircleNotActive);
+        }
+        circle
+    }
+
+    // Internal helper to verify circle membership
+    fn require_member(env: &Env, circle_id: u64, member: &Address) {
+        if !Self::is_circle_member(env.clone(), circle_id, member.clone()) {
+            panic_with_error!(env, RoscaError::MemberNotFound);
+        }
+    }
+
+    // Internal helper to adjust a member's outstanding debt
+    fn add_debt(env: &Env, circle_id: u64, member: &Address, delta: i128) {
+        let debt = Self::get_member_debt(env.clone(), circle_id, member.clone());
+        env.storage().persistent().set(
+            &DataKey::MemberDebt(circle_id, member.clone()),
+            &(debt + delta),
+        );
+    }
+
+    // Internal helper returning members who have not been paid yet, in listing order
+    fn unpaid_members(env: &Env, circle: &Circle) -> Vec<Address> {
+        let mut unpaid = Vec::new(env);
+        for member in circle.members.iter() {
+            if !circle.recipients.contains(&member) {
+                unpaid.push_back(member);
+            }
+        }
+        unpaid
+    }
+
+    // Internal helper returning members with no outstanding debt
+    fn debt_free_members(env: &Env, circle: &Circle, exclude: Option<&Address>) -> Vec<Address> {
+        let mut members = Vec::new(env);
+        for member in circle.members.iter() {
+            if Some(&member) == exclude {
+                continue;
+            }
+            if Self::get_member_debt(env.clone(), circle.id, member.clone()) <= 0 {
+                members.push_back(member);
+            }
+        }
+        members
+    }
+
+    // Internal helper that pays `amount` equally to `recipients` and returns
+    // the undistributed remainder
+    fn split_equally(env: &Env, circle: &Circle, amount: i128, recipients: &Vec<Address>) -> i128 {
+        if amount <= 0 || recipients.is_empty() {
+            return amount.max(0);
+        }
+        let share = amount / recipients.len() as i128;
+        if share == 0 {
+            return amount;
+        }
+        let token_client = token::Client::new(env, &circle.token);
+        for recipient in recipients.iter() {
+            token_client.transfer(&env.current_contract_address(), &recipient, &share);
+        }
+        amount - share * recipients.len() as i128
+    }
+
+    // Internal helper to verify admin
+    fn require_admin(env: &Env, caller: &Address) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+
+        if *caller != admin {
+            panic_with_error!(env, RoscaError::Unauthorized);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
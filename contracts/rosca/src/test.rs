@@ -0,0 +1,330 @@
+// Integration tests for the rotating savings circle contract.
+
+#![cfg(test)]
+
+use crate::{CircleConfig, CircleStatus, PayoutOrder, RoscaContract, RoscaContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env, Symbol, Vec,
+};
+
+const CONTRIBUTION: i128 = 10_000_000;
+const ROUND_INTERVAL: u64 = 604_800;
+
+/// Creates a test environment with the contract deployed and initialized.
+fn setup_test_env() -> (
+    Env,
+    Address,
+    Address,
+    token::Client<'static>,
+    RoscaContractClient<'static>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 12345;
+        li.timestamp = 1_700_000_000;
+    });
+
+    let issuer = Address::generate(&env);
+    let stellar_asset = env.register_stellar_asset_contract_v2(issuer);
+    let token_id: Address = stellar_asset.address();
+    let token_client = token::Client::new(&env, &token_id);
+
+    let contract_id = env.register(RoscaContract, ());
+    let client = RoscaContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    (env, admin, token_id, token_client, client)
+}
+
+/// Generates `count` members, each funded with enough tokens for every round.
+fn funded_members(env: &Env, token: &Address, count: u32) -> Vec<Address> {
+    let asset = token::StellarAssetClient::new(env, token);
+    let mut members = Vec::new(env);
+    for _ in 0..count {
+        let member = Address::generate(env);
+        asset.mint(&member, &(CONTRIBUTION * 10));
+        members.push_back(member);
+    }
+    members
+}
+
+fn config(payout_order: PayoutOrder) -> CircleConfig {
+    CircleConfig {
+        contribution_amount: CONTRIBUTION,
+        round_interval: ROUND_INTERVAL,
+        payout_order,
+        default_penalty_bps: 1_000,
+    }
+}
+
+fn advance_time(env: &Env, seconds: u64) {
+    env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+#[test]
+fn test_initialize_contract() {
+    let (_env, admin, _token, _token_client, client) = setup_test_env();
+
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_total_circles_created(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Contract already initialized")]
+fn test_cannot_initialize_twice() {
+    let (env, _admin, _token, _token_client, client) = setup_test_env();
+
+    client.initialize(&Address::generate(&env));
+}
+
+#[test]
+fn test_create_circle() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let members = funded_members(&env, &token, 3);
+    let creator = members.get(0).unwrap();
+
+    let circle_id = client.create_circle(
+        &creator,
+        &Symbol::new(&env, "chama"),
+        &members,
+        &token,
+        &config(PayoutOrder::Fixed),
+    );
+
+    let circle = client.get_circle(&circle_id);
+    assert_eq!(circle.current_round, 0);
+    assert_eq!(circle.status, CircleStatus::Active);
+    assert_eq!(client.get_total_circles_created(), 1);
+    for member in members.iter() {
+        assert!(client.is_circle_member(&circle_id, &member));
+    }
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_create_circle_duplicate_members_fails() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let member = Address::generate(&env);
+    let members = Vec::from_array(&env, [member.clone(), member.clone()]);
+
+    client.create_circle(
+        &member,
+        &Symbol::new(&env, "dup"),
+        &members,
+        &token,
+        &config(PayoutOrder::Fixed),
+    );
+}
+
+#[test]
+fn test_fixed_order_full_rotation() {
+    let (env, _admin, token, token_client, client) = setup_test_env();
+    let members = funded_members(&env, &token, 3);
+    let circle_id = client.create_circle(
+        &members.get(0).unwrap(),
+        &Symbol::new(&env, "susu"),
+        &members,
+        &token,
+        &config(PayoutOrder::Fixed),
+    );
+
+    for round in 0..3u32 {
+        for member in members.iter() {
+            client.contribute(&member, &circle_id);
+        }
+        // Everyone paid, so the round closes early
+        let recipient = client.close_round(&circle_id);
+        assert_eq!(recipient, members.get(round).unwrap());
+    }
+
+    let circle = client.get_circle(&circle_id);
+    assert_eq!(circle.status, CircleStatus::Completed);
+    assert_eq!(circle.recipients, members);
+    for member in members.iter() {
+        assert_eq!(token_client.balance(&member), CONTRIBUTION * 10);
+    }
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_close_round_before_interval_fails() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let members = funded_members(&env, &token, 3);
+    let circle_id = client.create_circle(
+        &members.get(0).unwrap(),
+        &Symbol::new(&env, "early"),
+        &members,
+        &token,
+        &config(PayoutOrder::Fixed),
+    );
+
+    client.contribute(&members.get(0).unwrap(), &circle_id);
+    client.close_round(&circle_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_contribute_twice_fails() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let members = funded_members(&env, &token, 2);
+    let circle_id = client.create_circle(
+        &members.get(0).unwrap(),
+        &Symbol::new(&env, "twice"),
+        &members,
+        &token,
+        &config(PayoutOrder::Fixed),
+    );
+
+    client.contribute(&members.get(0).unwrap(), &circle_id);
+    client.contribute(&members.get(0).unwrap(), &circle_id);
+}
+
+#[test]
+fn test_default_records_penalty_and_debt_is_withheld() {
+    let (env, _admin, token, token_client, client) = setup_test_env();
+    let members = funded_members(&env, &token, 3);
+    let first = members.get(0).unwrap();
+    let second = members.get(1).unwrap();
+    let third = members.get(2).unwrap();
+    let circle_id = client.create_circle(
+        &first,
+        &Symbol::new(&env, "default"),
+        &members,
+        &token,
+        &config(PayoutOrder::Fixed),
+    );
+
+    // Round 0: the second member skips
+    client.contribute(&first, &circle_id);
+    client.contribute(&third, &circle_id);
+    advance_time(&env, ROUND_INTERVAL);
+    client.close_round(&circle_id);
+
+    assert_eq!(client.get_member_defaults(&circle_id, &second), 1);
+    assert_eq!(
+        client.get_member_debt(&circle_id, &second),
+        CONTRIBUTION + CONTRIBUTION / 10
+    );
+    assert_eq!(token_client.balance(&first), CONTRIBUTION * 11);
+
+    // Round 1: the second member's debt is withheld from their payout
+    for member in members.iter() {
+        client.contribute(&member, &circle_id);
+    }
+    client.close_round(&circle_id);
+    assert_eq!(client.get_member_debt(&circle_id, &second), 0);
+    assert_eq!(
+        token_client.balance(&second),
+        CONTRIBUTION * 10 - CONTRIBUTION + CONTRIBUTION * 3 - CONTRIBUTION * 11 / 10
+    );
+    assert_eq!(
+        client.get_circle(&circle_id).reserve,
+        CONTRIBUTION * 11 / 10
+    );
+}
+
+#[test]
+fn test_settle_debt() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let members = funded_members(&env, &token, 2);
+    let first = members.get(0).unwrap();
+    let second = members.get(1).unwrap();
+    let circle_id = client.create_circle(
+        &first,
+        &Symbol::new(&env, "settle"),
+        &members,
+        &token,
+        &config(PayoutOrder::Fixed),
+    );
+
+    client.contribute(&first, &circle_id);
+    advance_time(&env, ROUND_INTERVAL);
+    client.close_round(&circle_id);
+
+    assert_eq!(
+        client.settle_debt(&second, &circle_id),
+        CONTRIBUTION * 11 / 10
+    );
+    assert_eq!(client.get_member_debt(&circle_id, &second), 0);
+    assert_eq!(
+        client.get_circle(&circle_id).reserve,
+        CONTRIBUTION * 11 / 10
+    );
+}
+
+#[test]
+fn test_bid_order_highest_bid_wins() {
+    let (env, _admin, token, token_client, client) = setup_test_env();
+    let members = funded_members(&env, &token, 3);
+    let first = members.get(0).unwrap();
+    let second = members.get(1).unwrap();
+    let third = members.get(2).unwrap();
+    let circle_id = client.create_circle(
+        &first,
+        &Symbol::new(&env, "bid"),
+        &members,
+        &token,
+        &config(PayoutOrder::Bid),
+    );
+
+    client.place_bid(&second, &circle_id, &2_000_000);
+    client.place_bid(&third, &circle_id, &4_000_000);
+    assert_eq!(client.get_current_bid(&circle_id).unwrap().bidder, third);
+
+    for member in members.iter() {
+        client.contribute(&member, &circle_id);
+    }
+    assert_eq!(client.close_round(&circle_id), third);
+
+    // Winner forgoes the discount, which is split between the others
+    assert_eq!(token_client.balance(&third), CONTRIBUTION * 12 - 4_000_000);
+    assert_eq!(token_client.balance(&first), CONTRIBUTION * 9 + 2_000_000);
+    assert_eq!(token_client.balance(&second), CONTRIBUTION * 9 + 2_000_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #14)")]
+fn test_lower_bid_rejected() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let members = funded_members(&env, &token, 3);
+    let circle_id = client.create_circle(
+        &members.get(0).unwrap(),
+        &Symbol::new(&env, "lowbid"),
+        &members,
+        &token,
+        &config(PayoutOrder::Bid),
+    );
+
+    client.place_bid(&members.get(1).unwrap(), &circle_id, &4_000_000);
+    client.place_bid(&members.get(2).unwrap(), &circle_id, &3_000_000);
+}
+
+#[test]
+fn test_random_order_pays_each_member_once() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let members = funded_members(&env, &token, 4);
+    let circle_id = client.create_circle(
+        &members.get(0).unwrap(),
+        &Symbol::new(&env, "random"),
+        &members,
+        &token,
+        &config(PayoutOrder::Random),
+    );
+
+    for _ in 0..4 {
+        for member in members.iter() {
+            client.contribute(&member, &circle_id);
+        }
+        client.close_round(&circle_id);
+    }
+
+    let circle = client.get_circle(&circle_id);
+    assert_eq!(circle.status, CircleStatus::Completed);
+    for member in members.iter() {
+        assert!(circle.recipients.contains(&member));
+    }
+}
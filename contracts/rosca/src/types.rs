@@ -0,0 +1,196 @@
+// Types and events for rotating savings circles.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+/// Minimum number of members in a circle.
+pub const MIN_CIRCLE_MEMBERS: u32 = 2;
+
+/// Maximum number of members in a circle.
+pub const MAX_CIRCLE_MEMBERS: u32 = 20;
+
+/// Basis points denominator (100%).
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// How each round's recipient is chosen.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum PayoutOrder {
+    /// Members are paid in the order they were listed
+    Fixed,
+    /// A member who has not been paid yet is drawn from the ledger PRNG seed
+    Random,
+    /// The unpaid member offering the largest discount on the pot wins; the
+    /// discount is shared among the other members
+    Bid,
+}
+
+/// Lifecycle state of a circle.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum CircleStatus {
+    /// Rounds are in progress
+    Active,
+    /// Every member has received a payout
+    Completed,
+}
+
+/// Settings chosen when a circle is created.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct CircleConfig {
+    /// Amount each member contributes per round
+    pub contribution_amount: i128,
+    /// Length of a round in seconds
+    pub round_interval: u64,
+    /// How each round's recipient is chosen
+    pub payout_order: PayoutOrder,
+    /// Penalty added to a missed contribution (basis points)
+    pub default_penalty_bps: u32,
+}
+
+/// Represents a rotating savings circle.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct Circle {
+    /// Unique identifier for the circle
+    pub id: u64,
+    /// Name of the circle
+    pub name: Symbol,
+    /// Creator of the circle
+    pub creator: Address,
+    /// Token contributed and paid out
+    pub token: Address,
+    /// Members of the circle, in listing order
+    pub members: Vec<Address>,
+    /// Contribution, timing and payout settings
+    pub config: CircleConfig,
+    /// Current round (0-based)
+    pub current_round: u32,
+    /// Timestamp when the current round started
+    pub round_started_at: u64,
+    /// Contributions collected in the current round
+    pub pot: i128,
+    /// Recovered debts carried into the next payout
+    pub reserve: i128,
+    /// Recipients of completed rounds, in round order
+    pub recipients: Vec<Address>,
+    /// Lifecycle state
+    pub status: CircleStatus,
+    /// Timestamp when the circle was created
+    pub created_at: u64,
+}
+
+/// Highest bid placed in a bid-based round.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct RoundBid {
+    /// Bidding member
+    pub bidder: Address,
+    /// Amount of the pot the bidder gives up
+    pub discount: i128,
+}
+
+/// Storage keys for contract state.
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    /// Admin address
+    Admin,
+    /// Circle details by ID
+    Circle(u64),
+    /// Whether an address is a member of a circle
+    CircleMember(u64, Address),
+    /// Whether a member has contributed in a round (circle_id, round, member)
+    RoundContribution(u64, u32, Address),
+    /// Highest bid in a round (circle_id, round)
+    RoundBid(u64, u32),
+    /// Number of rounds a member has defaulted on
+    MemberDefaults(u64, Address),
+    /// Outstanding missed contributions plus penalties owed by a member
+    MemberDebt(u64, Address),
+    /// Total number of circles created
+    TotalCirclesCreated,
+}
+
+/// Events emitted by the rotating savings circle contract.
+pub struct RoscaEvents;
+
+impl RoscaEvents {
+    /// Event emitted when a circle is created.
+    pub fn circle_created(
+        env: &Env,
+        circle_id: u64,
+        creator: &Address,
+        members: &Vec<Address>,
+        token: &Address,
+    ) {
+        let topics = (symbol_short!("circle"), symbol_short!("created"));
+        env.events().publish(
+            topics,
+            (circle_id, creator.clone(), members.clone(), token.clone()),
+        );
+    }
+
+    /// Event emitted when a member contributes to a round.
+    pub fn contribution_added(
+        env: &Env,
+        circle_id: u64,
+        round: u32,
+        contributor: &Address,
+        amount: i128,
+    ) {
+        let topics = (symbol_short!("circle"), symbol_short!("contrib"), circle_id);
+        env.events()
+            .publish(topics, (round, contributor.clone(), amount));
+    }
+
+    /// Event emitted when a member places a bid for the current pot.
+    pub fn bid_placed(env: &Env, circle_id: u64, round: u32, bidder: &Address, discount: i128) {
+        let topics = (symbol_short!("circle"), symbol_short!("bid"), circle_id);
+        env.events()
+            .publish(topics, (round, bidder.clone(), discount));
+    }
+
+    /// Event emitted when a member misses a round's contribution.
+    pub fn member_defaulted(
+        env: &Env,
+        circle_id: u64,
+        round: u32,
+        member: &Address,
+        penalty: i128,
+    ) {
+        let topics = (symbol_short!("circle"), symbol_short!("default"), circle_id);
+        env.events()
+            .publish(topics, (round, member.clone(), penalty));
+    }
+
+    /// Event emitted when a member settles outstanding debt.
+    pub fn debt_settled(env: &Env, circle_id: u64, member: &Address, amount: i128) {
+        let topics = (symbol_short!("circle"), symbol_short!("settled"), circle_id);
+        env.events().publish(topics, (member.clone(), amount));
+    }
+
+    /// Event emitted when a round's pot is paid out.
+    pub fn round_paid(
+        env: &Env,
+        circle_id: u64,
+        round: u32,
+        recipient: &Address,
+        amount: i128,
+        discount: i128,
+    ) {
+        let topics = (symbol_short!("circle"), symbol_short!("payout"), circle_id);
+        env.events()
+            .publish(topics, (round, recipient.clone(), amount, discount));
+    }
+
+    /// Event emitted when every member has received a payout.
+    pub fn circle_completed(env: &Env, circle_id: u64, rounds: u32) {
+        let topics = (
+            symbol_short!("circle"),
+            symbol_short!("complete"),
+            circle_id,
+        );
+        env.events().publish(topics, rounds);
+    }
+}
@@ -0,0 +1,37 @@
+// Validation helpers for rotating savings circle operations.
+
+use soroban_sdk::{Address, Vec};
+
+use crate::types::{BPS_DENOMINATOR, MAX_CIRCLE_MEMBERS, MIN_CIRCLE_MEMBERS};
+
+/// Validates an amount.
+pub fn validate_amount(amount: i128) -> Result<(), &'static str> {
+    if amount <= 0 {
+        return Err("invalid_amount");
+    }
+    Ok(())
+}
+
+/// Validates a penalty in basis points (0-10000).
+pub fn validate_penalty_bps(penalty_bps: u32) -> Result<(), &'static str> {
+    if penalty_bps > BPS_DENOMINATOR {
+        return Err("invalid_penalty");
+    }
+    Ok(())
+}
+
+/// Validates a circle's member list: within size limits and without duplicates.
+pub fn validate_members(members: &Vec<Address>) -> Result<(), &'static str> {
+    if members.len() < MIN_CIRCLE_MEMBERS {
+        return Err("too_few_members");
+    }
+    if members.len() > MAX_CIRCLE_MEMBERS {
+        return Err("too_many_members");
+    }
+    for (i, member) in members.iter().enumerate() {
+        if members.first_index_of(&member) != Some(i as u32) {
+            return Err("duplicate_member");
+        }
+    }
+    Ok(())
+}
@@ -12,6 +12,9 @@
 //! - **Error Handling**: Gracefully handles invalid inputs with detailed error codes
 //! - **Optimized Storage**: Minimizes storage writes by batching operations
 //! - **Partial Failure Support**: Batch operations continue even if some individual operations fail
+//! - **Token Custody**: Contributions and withdrawals move real tokens held by the contract
+//! - **Time-Locked Vaults**: Locked goals charge a penalty on withdrawals before the deadline
+//! - **Joint Goals**: Several members contribute to one goal with tracked shares and a withdrawal policy
 //!
 //! ## Optimization Strategies
 //!
@@ -25,14 +28,18 @@
 mod types;
 mod validation;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, Address, Env, Vec};
+use soroban_sdk::{contract, contractimpl, panic_with_error, token, Address, Env, Vec};
 
 pub use crate::types::{
     BatchGoalMetrics, BatchGoalResult, BatchMilestoneMetrics, BatchMilestoneResult, DataKey,
-    ErrorCode, GoalEvents, GoalResult, MilestoneAchievement, MilestoneAchievementRequest,
-    MilestoneResult, SavingsGoal, SavingsGoalRequest, MAX_BATCH_SIZE,
+    ErrorCode, GoalEvents, GoalLock, GoalResult, JointGoalConfig, MemberShare,
+    MilestoneAchievement, MilestoneAchievementRequest, MilestoneResult, PenaltyDestination,
+    PenaltyTier, SavingsGoal, SavingsGoalRequest, WithdrawalPolicy, WithdrawalProposal,
+    BONUS_INDEX_SCALE, BPS_DENOMINATOR, DEFAULT_PENALTY_BPS, MAX_BATCH_SIZE, MAX_GOAL_MEMBERS,
+};
+use crate::validation::{
+    is_valid_penalty_schedule, validate_goal_request, validate_milestone_request,
 };
-use crate::validation::{validate_goal_request, validate_milestone_request};
 
 /// Error codes for the savings goals contract.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -48,6 +55,50 @@ pub enum SavingsGoalError {
     EmptyBatch = 4,
     /// Batch exceeds maximum size
     BatchTooLarge = 5,
+    /// Savings token has not been configured
+    TokenNotSet = 6,
+    /// Goal does not exist
+    GoalNotFound = 7,
+    /// Goal is not active
+    GoalNotActive = 8,
+    /// Amount must be positive
+    InvalidAmount = 9,
+    /// Goal does not hold enough funds
+    InsufficientBalance = 10,
+    /// Goal is already locked
+    AlreadyLocked = 11,
+    /// Goal is not locked
+    NotLocked = 12,
+    /// Goal deadline has already passed
+    DeadlinePassed = 13,
+    /// Lock cannot be released before the deadline
+    LockNotExpired = 14,
+    /// Penalty schedule is empty, unordered or out of range
+    InvalidPenaltySchedule = 15,
+    /// Penalties are routed to a treasury that has not been set
+    TreasuryNotSet = 16,
+    /// Goal has not reached its target
+    GoalNotReached = 17,
+    /// No bonus is available to claim
+    NoBonusAvailable = 18,
+    /// Address is not a member of the goal
+    NotMember = 19,
+    /// Address is already a member of the goal
+    AlreadyMember = 20,
+    /// Goal is already a joint goal
+    AlreadyJoint = 21,
+    /// Goal is not a joint goal
+    NotJointGoal = 22,
+    /// Joint goal member limit exceeded
+    TooManyMembers = 23,
+    /// Goal's withdrawal policy does not permit this withdrawal
+    WithdrawalNotPermitted = 24,
+    /// Withdrawal proposal does not exist
+    ProposalNotFound = 25,
+    /// Withdrawal proposal has already been executed
+    ProposalAlreadyExecuted = 26,
+    /// Member has already approved the proposal
+    AlreadyApproved = 27,
 }
 
 impl From<SavingsGoalError> for soroban_sdk::Error {
@@ -247,6 +298,10 @@ impl SavingsGoalsContract {
     /// This is the main entry point for batch goal creation. It validates all requests,
     /// creates goals, emits events, and updates storage efficiently.
     ///
+    /// Once a savings token is set, requests with a non-zero
+    /// `initial_contribution` fail, since funds must be deposited with
+    /// `contribute`.
+    ///
     /// # Arguments
     /// * `env` - The contract environment
     /// * `caller` - The address calling this function (must be admin)
@@ -310,10 +365,18 @@ impl SavingsGoalsContract {
             .get(&DataKey::LastGoalId)
             .unwrap_or(0);
 
+        // Once a savings token is set, goal funds must come through custody,
+        // so an initial contribution cannot be credited without a deposit
+        let token_backed = env.storage().instance().has(&DataKey::Token);
+
         // Process each request
         for request in requests.iter() {
             // Validate the request
-            match validate_goal_request(&env, &request) {
+            let mut validation = validate_goal_request(&env, &request);
+            if validation.is_ok() && token_backed && request.initial_contribution != 0 {
+                validation = Err(ErrorCode::UNBACKED_INITIAL_CONTRIBUTION);
+            }
+            match validation {
                 Ok(()) => {
                     // Validation succeeded - create the goal
                     goal_id_counter += 1;
@@ -475,7 +538,607 @@ impl SavingsGoalsContract {
             .persistent()
             .set(&DataKey::GoalMilestonesPercent(goal_id), &triggered);
     }
-    // ...existing code...
+
+    /// Sets the token held in custody for all savings goals.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The address calling this function (must be admin)
+    /// * `token` - The SAC token address used for contributions
+    pub fn set_token(env: Env, caller: Address, token: Address) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        env.storage().instance().set(&DataKey::Token, &token);
+    }
+
+    /// Returns the savings token address, if configured.
+    pub fn get_token(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Token)
+    }
+
+    /// Deposits tokens from the goal owner into the goal.
+    ///
+    /// Milestone events are emitted automatically when the new balance
+    /// crosses a progress threshold.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `goal_id` - The goal to contribute to
+    /// * `amount` - The amount to deposit (in stroops)
+    ///
+    /// # Returns
+    /// * `i128` - The goal's new current amount
+    pub fn contribute(env: Env, goal_id: u64, amount: i128) -> i128 {
+        let goal = Self::load_active_goal(&env, goal_id);
+        goal.user.require_auth();
+
+        let owner = goal.user.clone();
+        Self::deposit(&env, &owner, goal, amount)
+    }
+
+    /// Deposits tokens from a member of a joint goal.
+    ///
+    /// The deposit is credited to the member's share, and milestone events
+    /// reached by it are attributed to the member.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `contributor` - The contributing member (must be the owner or a joint member)
+    /// * `goal_id` - The goal to contribute to
+    /// * `amount` - The amount to deposit (in stroops)
+    ///
+    /// # Returns
+    /// * `i128` - The goal's new current amount
+    pub fn contribute_from(env: Env, contributor: Address, goal_id: u64, amount: i128) -> i128 {
+        contributor.require_auth();
+        let goal = Self::load_active_goal(&env, goal_id);
+        if !Self::goal_members(&env, &goal).contains(&contributor) {
+            panic_with_error!(&env, SavingsGoalError::NotMember);
+        }
+
+        Self::deposit(&env, &contributor, goal, amount)
+    }
+
+    /// Withdraws tokens held for a goal back to the goal owner.
+    ///
+    /// Only funds actually deposited through `contribute` can be withdrawn.
+    /// Withdrawals from a locked goal before its deadline are charged the
+    /// current early-withdrawal penalty, which is deducted from the payout.
+    /// Joint goals only allow this under the `OwnerOnly` policy.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `goal_id` - The goal to withdraw from
+    /// * `amount` - The amount to withdraw (in stroops)
+    ///
+    /// # Returns
+    /// * `i128` - The goal's new current amount
+    pub fn withdraw(env: Env, goal_id: u64, amount: i128) -> i128 {
+        let mut goal = Self::load_active_goal(&env, goal_id);
+        goal.user.require_auth();
+
+        if Self::withdrawal_policy(&env, goal_id) != WithdrawalPolicy::OwnerOnly {
+            panic_with_error!(&env, SavingsGoalError::WithdrawalNotPermitted);
+        }
+
+        let owner = goal.user.clone();
+        Self::withdraw_to(&env, &mut goal, amount, &owner)
+    }
+
+    /// Closes a goal, returning all remaining funds and marking the goal
+    /// inactive.
+    ///
+    /// Funds go to the owner, except for joint goals under the
+    /// `MajorityApproval` or `ProRataOnClose` policies, which pay each
+    /// member pro-rata to their share.
+    ///
+    /// Closing a locked goal early is charged the early-withdrawal penalty.
+    /// A locked goal whose custody balance reached its target also receives
+    /// its unclaimed bonus; otherwise the accrued bonus is forfeited back to
+    /// the pool.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `goal_id` - The goal to close
+    ///
+    /// # Returns
+    /// * `i128` - The total amount paid out
+    pub fn close_goal(env: Env, goal_id: u64) -> i128 {
+        let mut goal = Self::load_active_goal(&env, goal_id);
+        goal.user.require_auth();
+
+        let balance = Self::get_goal_balance(env.clone(), goal_id);
+        let reached = balance >= goal.target_amount;
+        let penalty = Self::charge_lock_penalty(&env, &goal, balance, balance);
+        let bonus = Self::finish_lock(&env, &goal, reached);
+        let refund = balance - penalty + bonus;
+
+        if Self::withdrawal_policy(&env, goal_id) == WithdrawalPolicy::OwnerOnly {
+            if refund > 0 {
+                let token_client = Self::token_client(&env);
+                token_client.transfer(&env.current_contract_address(), &goal.user, &refund);
+            }
+            Self::reduce_shares(&env, &goal, balance, balance);
+        } else {
+            Self::pay_out_pro_rata(&env, &goal, refund);
+        }
+
+        goal.current_amount -= balance;
+        goal.is_active = false;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Goal(goal_id), &goal);
+        env.storage()
+            .persistent()
+            .set(&DataKey::GoalBalance(goal_id), &0i128);
+
+        GoalEvents::goal_closed(&env, goal_id, &goal.user, refund);
+
+        refund
+    }
+
+    /// Returns the token balance held in custody for a goal.
+    pub fn get_goal_balance(env: Env, goal_id: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::GoalBalance(goal_id))
+            .unwrap_or(0)
+    }
+
+    /// Turns a goal into a joint goal that several members contribute to.
+    ///
+    /// The owner is always a member. The withdrawal policy cannot be
+    /// changed afterwards.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `goal_id` - The goal to share
+    /// * `members` - Additional members (each must authorize)
+    /// * `policy` - Withdrawal policy for the goal
+    pub fn set_joint_goal(env: Env, goal_id: u64, members: Vec<Address>, policy: WithdrawalPolicy) {
+        let goal = Self::load_active_goal(&env, goal_id);
+        goal.user.require_auth();
+
+        if env.storage().persistent().has(&DataKey::JointGoal(goal_id)) {
+            panic_with_error!(&env, SavingsGoalError::AlreadyJoint);
+        }
+
+        let mut config = JointGoalConfig {
+            members: Vec::from_array(&env, [goal.user.clone()]),
+            policy,
+        };
+        for member in members.iter() {
+            if config.members.contains(&member) {
+                continue;
+            }
+            member.require_auth();
+            Self::push_member(&env, goal_id, &mut config, member);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::JointGoal(goal_id), &config);
+
+        GoalEvents::joint_goal_created(&env, goal_id, &config);
+    }
+
+    /// Adds a member to a joint goal.
+    ///
+    /// The new member and every existing member must authorize, so nobody
+    /// joins without agreeing and the owner cannot pack the membership.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `goal_id` - The joint goal
+    /// * `member` - The new member
+    pub fn add_goal_member(env: Env, goal_id: u64, member: Address) {
+        Self::load_active_goal(&env, goal_id);
+
+        let mut config: JointGoalConfig = env
+            .storage()
+            .persistent()
+            .get(&DataKey::JointGoal(goal_id))
+            .unwrap_or_else(|| panic_with_error!(&env, SavingsGoalError::NotJointGoal));
+        for existing in config.members.iter() {
+            existing.require_auth();
+        }
+        if config.members.contains(&member) {
+            panic_with_error!(&env, SavingsGoalError::AlreadyMember);
+        }
+        member.require_auth();
+        Self::push_member(&env, goal_id, &mut config, member);
+        env.storage()
+            .persistent()
+            .set(&DataKey::JointGoal(goal_id), &config);
+    }
+
+    /// Proposes a withdrawal from a joint goal under the `MajorityApproval`
+    /// policy. The proposer's approval is counted immediately.
+    ///
+    /// The current members form the proposal's electorate; members added
+    /// later cannot vote on it and do not change its threshold.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `proposer` - The proposing member
+    /// * `goal_id` - The joint goal
+    /// * `amount` - The amount to withdraw (in stroops)
+    /// * `recipient` - The address receiving the funds
+    ///
+    /// # Returns
+    /// * `u64` - The proposal ID
+    pub fn propose_withdrawal(
+        env: Env,
+        proposer: Address,
+        goal_id: u64,
+        amount: i128,
+        recipient: Address,
+    ) -> u64 {
+        proposer.require_auth();
+        let mut goal = Self::load_active_goal(&env, goal_id);
+
+        if Self::withdrawal_policy(&env, goal_id) != WithdrawalPolicy::MajorityApproval {
+            panic_with_error!(&env, SavingsGoalError::WithdrawalNotPermitted);
+        }
+        let members = Self::goal_members(&env, &goal);
+        if !members.contains(&proposer) {
+            panic_with_error!(&env, SavingsGoalError::NotMember);
+        }
+        if amount <= 0 {
+            panic_with_error!(&env, SavingsGoalError::InvalidAmount);
+        }
+        if amount > Self::get_goal_balance(env.clone(), goal_id) {
+            panic_with_error!(&env, SavingsGoalError::InsufficientBalance);
+        }
+
+        let proposal_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LastProposalId(goal_id))
+            .unwrap_or(0u64)
+            + 1;
+        env.storage()
+            .persistent()
+            .set(&DataKey::LastProposalId(goal_id), &proposal_id);
+
+        let mut proposal = WithdrawalProposal {
+            proposal_id,
+            goal_id,
+            proposer: proposer.clone(),
+            recipient,
+            amount,
+            threshold: members.len() / 2 + 1,
+            electorate: members,
+            approvals: Vec::from_array(&env, [proposer]),
+            executed: false,
+            created_at: env.ledger().sequence() as u64,
+        };
+        GoalEvents::withdrawal_proposed(&env, &proposal);
+
+        Self::execute_if_approved(&env, &mut goal, &mut proposal);
+        env.storage().persistent().set(
+            &DataKey::WithdrawalProposal(goal_id, proposal_id),
+            &proposal,
+        );
+
+        proposal_id
+    }
+
+    /// Approves a pending withdrawal proposal. The withdrawal executes once
+    /// more than half of the proposal's electorate has approved it.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `member` - The approving member
+    /// * `goal_id` - The joint goal
+    /// * `proposal_id` - The proposal to approve
+    ///
+    /// # Returns
+    /// * `bool` - Whether the withdrawal was executed
+    pub fn approve_withdrawal(env: Env, member: Address, goal_id: u64, proposal_id: u64) -> bool {
+        member.require_auth();
+        let mut goal = Self::load_active_goal(&env, goal_id);
+
+        let key = DataKey::WithdrawalProposal(goal_id, proposal_id);
+        let mut proposal: WithdrawalProposal = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic_with_error!(&env, SavingsGoalError::ProposalNotFound));
+        if !proposal.electorate.contains(&member) {
+            panic_with_error!(&env, SavingsGoalError::NotMember);
+        }
+        if proposal.executed {
+            panic_with_error!(&env, SavingsGoalError::ProposalAlreadyExecuted);
+        }
+        if proposal.approvals.contains(&member) {
+            panic_with_error!(&env, SavingsGoalError::AlreadyApproved);
+        }
+
+        proposal.approvals.push_back(member.clone());
+        GoalEvents::withdrawal_approved(&env, goal_id, proposal_id, &member);
+
+        Self::execute_if_approved(&env, &mut goal, &mut proposal);
+        env.storage().persistent().set(&key, &proposal);
+
+        proposal.executed
+    }
+
+    /// Returns the membership and policy of a joint goal.
+    pub fn get_joint_goal(env: Env, goal_id: u64) -> Option<JointGoalConfig> {
+        env.storage().persistent().get(&DataKey::JointGoal(goal_id))
+    }
+
+    /// Returns each member's share of a goal's custody balance.
+    pub fn get_member_shares(env: Env, goal_id: u64) -> Vec<MemberShare> {
+        let mut shares = Vec::new(&env);
+        let goal = match Self::get_goal(env.clone(), goal_id) {
+            Some(goal) => goal,
+            None => return shares,
+        };
+        for member in Self::goal_members(&env, &goal).iter() {
+            let contributed = Self::get_member_contribution(env.clone(), goal_id, member.clone());
+            shares.push_back(MemberShare {
+                member,
+                contributed,
+            });
+        }
+        shares
+    }
+
+    /// Returns a member's share of a goal's custody balance.
+    pub fn get_member_contribution(env: Env, goal_id: u64, member: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MemberContribution(goal_id, member))
+            .unwrap_or(0)
+    }
+
+    /// Returns the joint goals an address belongs to as a non-owner member.
+    pub fn get_member_goals(env: Env, member: Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MemberGoals(member))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Returns a withdrawal proposal.
+    pub fn get_withdrawal_proposal(
+        env: Env,
+        goal_id: u64,
+        proposal_id: u64,
+    ) -> Option<WithdrawalProposal> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::WithdrawalProposal(goal_id, proposal_id))
+    }
+
+    /// Puts a goal into lock mode until its deadline.
+    ///
+    /// While locked, withdrawals before the deadline pay the early-withdrawal
+    /// penalty, and the goal's balance earns a share of the bonus pool.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `goal_id` - The goal to lock
+    pub fn lock_goal(env: Env, goal_id: u64) {
+        let goal = Self::load_active_goal(&env, goal_id);
+        goal.user.require_auth();
+
+        if Self::active_lock(&env, goal_id).is_some() {
+            panic_with_error!(&env, SavingsGoalError::AlreadyLocked);
+        }
+        let now = env.ledger().sequence() as u64;
+        if now >= goal.deadline {
+            panic_with_error!(&env, SavingsGoalError::DeadlinePassed);
+        }
+
+        let lock = GoalLock {
+            locked_at: now,
+            active: true,
+            bonus_index: Self::bonus_index(&env),
+            accrued_bonus: 0,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::GoalLock(goal_id), &lock);
+        Self::adjust_locked_balance(&env, Self::get_goal_balance(env.clone(), goal_id));
+
+        GoalEvents::goal_locked(&env, goal_id, &goal.user, goal.deadline);
+    }
+
+    /// Releases a goal's lock once its deadline has been reached.
+    ///
+    /// Withdrawing or closing after the deadline releases the lock
+    /// automatically; this is only needed to stop the lock explicitly.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `goal_id` - The goal to unlock
+    pub fn unlock_goal(env: Env, goal_id: u64) {
+        let goal = Self::load_active_goal(&env, goal_id);
+        goal.user.require_auth();
+
+        let lock = Self::active_lock(&env, goal_id)
+            .unwrap_or_else(|| panic_with_error!(&env, SavingsGoalError::NotLocked));
+        if (env.ledger().sequence() as u64) < goal.deadline {
+            panic_with_error!(&env, SavingsGoalError::LockNotExpired);
+        }
+
+        Self::release_lock(&env, &goal, lock);
+    }
+
+    /// Pays out the bonus pool share accrued by a locked goal whose custody
+    /// balance has reached its target.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `goal_id` - The goal claiming its bonus
+    ///
+    /// # Returns
+    /// * `i128` - The bonus paid to the goal owner
+    pub fn claim_bonus(env: Env, goal_id: u64) -> i128 {
+        let goal = Self::load_active_goal(&env, goal_id);
+        goal.user.require_auth();
+
+        if Self::get_goal_balance(env.clone(), goal_id) < goal.target_amount {
+            panic_with_error!(&env, SavingsGoalError::GoalNotReached);
+        }
+        let mut lock: GoalLock = env
+            .storage()
+            .persistent()
+            .get(&DataKey::GoalLock(goal_id))
+            .unwrap_or_else(|| panic_with_error!(&env, SavingsGoalError::NotLocked));
+        if lock.active {
+            Self::settle_bonus(
+                &env,
+                &mut lock,
+                Self::get_goal_balance(env.clone(), goal_id),
+            );
+        }
+
+        let bonus = lock.accrued_bonus;
+        if bonus <= 0 {
+            panic_with_error!(&env, SavingsGoalError::NoBonusAvailable);
+        }
+        lock.accrued_bonus = 0;
+        env.storage()
+            .persistent()
+            .set(&DataKey::GoalLock(goal_id), &lock);
+
+        let token_client = Self::token_client(&env);
+        token_client.transfer(&env.current_contract_address(), &goal.user, &bonus);
+        GoalEvents::bonus_claimed(&env, goal_id, &goal.user, bonus);
+
+        bonus
+    }
+
+    /// Sets the early-withdrawal penalty schedule.
+    ///
+    /// Tiers must be ordered by strictly decreasing `min_ledgers_remaining`
+    /// with non-increasing penalties, so the penalty steps down as the
+    /// deadline nears.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The address calling this function (must be admin)
+    /// * `schedule` - The penalty tiers
+    pub fn set_penalty_schedule(env: Env, caller: Address, schedule: Vec<PenaltyTier>) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        if !is_valid_penalty_schedule(&schedule) {
+            panic_with_error!(&env, SavingsGoalError::InvalidPenaltySchedule);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::PenaltySchedule, &schedule);
+    }
+
+    /// Sets where early-withdrawal penalties are sent.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - The address calling this function (must be admin)
+    /// * `destination` - Treasury or bonus pool
+    /// * `treasury` - Treasury address (required unless one is already set)
+    pub fn set_penalty_destination(
+        env: Env,
+        caller: Address,
+        destination: PenaltyDestination,
+        treasury: Option<Address>,
+    ) {
+        caller.require_auth();
+        Self::require_admin(&env, &caller);
+
+        if let Some(treasury) = treasury {
+            env.storage().instance().set(&DataKey::Treasury, &treasury);
+        }
+        if destination == PenaltyDestination::Treasury
+            && !env.storage().instance().has(&DataKey::Treasury)
+        {
+            panic_with_error!(&env, SavingsGoalError::TreasuryNotSet);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::PenaltyDestination, &destination);
+    }
+
+    /// Returns the penalty that would be charged for withdrawing `amount`
+    /// from a goal right now.
+    pub fn get_projected_penalty(env: Env, goal_id: u64, amount: i128) -> i128 {
+        let goal = match Self::get_goal(env.clone(), goal_id) {
+            Some(goal) => goal,
+            None => return 0,
+        };
+        if Self::active_lock(&env, goal_id).is_none() {
+            return 0;
+        }
+        match Self::penalty_bps(&env, &goal) {
+            Some(bps) => amount * bps as i128 / BPS_DENOMINATOR as i128,
+            None => 0,
+        }
+    }
+
+    /// Returns the early-withdrawal penalty schedule.
+    pub fn get_penalty_schedule(env: Env) -> Vec<PenaltyTier> {
+        env.storage()
+            .instance()
+            .get(&DataKey::PenaltySchedule)
+            .unwrap_or_else(|| {
+                Vec::from_array(
+                    &env,
+                    [PenaltyTier {
+                        min_ledgers_remaining: 0,
+                        penalty_bps: DEFAULT_PENALTY_BPS,
+                    }],
+                )
+            })
+    }
+
+    /// Returns where early-withdrawal penalties are sent.
+    pub fn get_penalty_destination(env: Env) -> PenaltyDestination {
+        env.storage()
+            .instance()
+            .get(&DataKey::PenaltyDestination)
+            .unwrap_or(PenaltyDestination::BonusPool)
+    }
+
+    /// Returns the treasury address, if configured.
+    pub fn get_treasury(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Treasury)
+    }
+
+    /// Returns the lock state of a goal, if it was ever locked.
+    pub fn get_goal_lock(env: Env, goal_id: u64) -> Option<GoalLock> {
+        env.storage().persistent().get(&DataKey::GoalLock(goal_id))
+    }
+
+    /// Returns the bonus currently accrued by a locked goal.
+    pub fn get_claimable_bonus(env: Env, goal_id: u64) -> i128 {
+        let mut lock: GoalLock = match env.storage().persistent().get(&DataKey::GoalLock(goal_id)) {
+            Some(lock) => lock,
+            None => return 0,
+        };
+        if lock.active {
+            Self::settle_bonus(
+                &env,
+                &mut lock,
+                Self::get_goal_balance(env.clone(), goal_id),
+            );
+        }
+        lock.accrued_bonus
+    }
+
+    /// Returns the sum of balances held by actively locked goals.
+    pub fn get_total_locked_balance(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalLockedBalance)
+            .unwrap_or(0)
+    }
 
     /// Retrieves a savings goal by ID.
     ///
@@ -597,6 +1260,418 @@ impl SavingsGoalsContract {
             .unwrap_or(0)
     }
 
+    // Internal helper to load a goal that must exist and be active
+    fn load_active_goal(env: &Env, goal_id: u64) -> SavingsGoal {
+        let goal: SavingsGoal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Goal(goal_id))
+            .unwrap_or_else(|| panic_with_error!(env, SavingsGoalError::GoalNotFound));
+        if !goal.is_active {
+            panic_with_error!(env, SavingsGoalError::GoalNotActive);
+        }
+        goal
+    }
+
+    // Internal helper that moves tokens from a contributor into a goal and
+    // credits the contributor's share
+    fn deposit(env: &Env, contributor: &Address, mut goal: SavingsGoal, amount: i128) -> i128 {
+        if amount <= 0 {
+            panic_with_error!(env, SavingsGoalError::InvalidAmount);
+        }
+        let goal_id = goal.goal_id;
+
+        let token_client = Self::token_client(env);
+        token_client.transfer(contributor, &env.current_contract_address(), &amount);
+
+        let balance = Self::get_goal_balance(env.clone(), goal_id);
+        if let Some(mut lock) = Self::active_lock(env, goal_id) {
+            Self::settle_bonus(env, &mut lock, balance);
+            env.storage()
+                .persistent()
+                .set(&DataKey::GoalLock(goal_id), &lock);
+            Self::adjust_locked_balance(env, amount);
+        }
+
+        goal.current_amount = goal
+            .current_amount
+            .checked_add(amount)
+            .unwrap_or_else(|| panic_with_error!(env, SavingsGoalError::InvalidAmount));
+        env.storage()
+            .persistent()
+            .set(&DataKey::Goal(goal_id), &goal);
+        env.storage()
+            .persistent()
+            .set(&DataKey::GoalBalance(goal_id), &(balance + amount));
+        let share_key = DataKey::MemberContribution(goal_id, contributor.clone());
+        let share: i128 = env.storage().persistent().get(&share_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&share_key, &(share + amount));
+
+        GoalEvents::contribution_made(env, goal_id, contributor, amount, goal.current_amount);
+
+        let triggered_before = Self::triggered_milestones(env, goal_id).len();
+        Self::check_and_emit_milestones(env, goal_id);
+        let triggered = Self::triggered_milestones(env, goal_id);
+        if triggered.len() > triggered_before {
+            let shares = Self::get_member_shares(env.clone(), goal_id);
+            for i in triggered_before..triggered.len() {
+                GoalEvents::milestone_attributed(
+                    env,
+                    goal_id,
+                    triggered.get(i).unwrap(),
+                    contributor,
+                    shares.clone(),
+                );
+            }
+        }
+
+        goal.current_amount
+    }
+
+    // Internal helper that withdraws from a goal to `recipient`, charging any
+    // lock penalty and reducing member shares pro-rata
+    fn withdraw_to(env: &Env, goal: &mut SavingsGoal, amount: i128, recipient: &Address) -> i128 {
+        if amount <= 0 {
+            panic_with_error!(env, SavingsGoalError::InvalidAmount);
+        }
+        let goal_id = goal.goal_id;
+
+        let balance = Self::get_goal_balance(env.clone(), goal_id);
+        if amount > balance {
+            panic_with_error!(env, SavingsGoalError::InsufficientBalance);
+        }
+
+        let penalty = Self::charge_lock_penalty(env, goal, balance, amount);
+        let token_client = Self::token_client(env);
+        token_client.transfer(
+            &env.current_contract_address(),
+            recipient,
+            &(amount - penalty),
+        );
+        Self::reduce_shares(env, goal, balance, amount);
+
+        goal.current_amount -= amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Goal(goal_id), goal);
+        env.storage()
+            .persistent()
+            .set(&DataKey::GoalBalance(goal_id), &(balance - amount));
+
+        GoalEvents::withdrawal_made(env, goal_id, recipient, amount, goal.current_amount);
+
+        goal.current_amount
+    }
+
+    // Internal helper that executes a withdrawal proposal once it has
+    // reached its threshold
+    fn execute_if_approved(env: &Env, goal: &mut SavingsGoal, proposal: &mut WithdrawalProposal) {
+        if proposal.approvals.len() < proposal.threshold {
+            return;
+        }
+        proposal.executed = true;
+        let recipient = proposal.recipient.clone();
+        Self::withdraw_to(env, goal, proposal.amount, &recipient);
+    }
+
+    // Internal helper that reduces member shares pro-rata when `amount`
+    // leaves a goal holding `balance`. The last member with a share absorbs
+    // rounding so shares keep summing to the balance.
+    fn reduce_shares(env: &Env, goal: &SavingsGoal, balance: i128, amount: i128) {
+        if balance <= 0 || amount <= 0 {
+            return;
+        }
+        let members = Self::goal_members(env, goal);
+        let mut remaining = amount;
+        let mut last_holder: Option<Address> = None;
+        for member in members.iter() {
+            let key = DataKey::MemberContribution(goal.goal_id, member.clone());
+            let share: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            if share <= 0 {
+                continue;
+            }
+            let cut = share * amount / balance;
+            env.storage().persistent().set(&key, &(share - cut));
+            remaining -= cut;
+            last_holder = Some(member);
+        }
+        if let Some(member) = last_holder {
+            let key = DataKey::MemberContribution(goal.goal_id, member);
+            let share: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            env.storage().persistent().set(&key, &(share - remaining));
+        }
+    }
+
+    // Internal helper that splits `payout` among members pro-rata to their
+    // recorded contributions and clears them. The payout can exceed the
+    // shares (e.g. by a bonus), so it is divided by the sum of the shares;
+    // with no shares left the owner receives it.
+    fn pay_out_pro_rata(env: &Env, goal: &SavingsGoal, payout: i128) {
+        let token_client = Self::token_client(env);
+        let shares = Self::get_member_shares(env.clone(), goal.goal_id);
+        let mut total_contributed: i128 = 0;
+        let mut last_holder: Option<Address> = None;
+        for share in shares.iter() {
+            if share.contributed <= 0 {
+                continue;
+            }
+            total_contributed += share.contributed;
+            last_holder = Some(share.member.clone());
+        }
+        if total_contributed <= 0 {
+            if payout > 0 {
+                token_client.transfer(&env.current_contract_address(), &goal.user, &payout);
+            }
+            GoalEvents::member_payout(env, goal.goal_id, &goal.user, payout);
+            return;
+        }
+
+        let mut remaining = payout;
+        for share in shares.iter() {
+            if share.contributed <= 0 {
+                continue;
+            }
+            let amount = if Some(share.member.clone()) == last_holder {
+                remaining
+            } else {
+                payout * share.contributed / total_contributed
+            };
+            remaining -= amount;
+            env.storage().persistent().set(
+                &DataKey::MemberContribution(goal.goal_id, share.member.clone()),
+                &0i128,
+            );
+            if amount > 0 {
+                token_client.transfer(&env.current_contract_address(), &share.member, &amount);
+            }
+            GoalEvents::member_payout(env, goal.goal_id, &share.member, amount);
+        }
+    }
+
+    // Internal helper returning the members of a goal; a regular goal has
+    // only its owner
+    fn goal_members(env: &Env, goal: &SavingsGoal) -> Vec<Address> {
+        match Self::get_joint_goal(env.clone(), goal.goal_id) {
+            Some(config) => config.members,
+            None => Vec::from_array(env, [goal.user.clone()]),
+        }
+    }
+
+    // Internal helper returning a goal's withdrawal policy; regular goals
+    // behave as owner-only
+    fn withdrawal_policy(env: &Env, goal_id: u64) -> WithdrawalPolicy {
+        Self::get_joint_goal(env.clone(), goal_id)
+            .map(|config| config.policy)
+            .unwrap_or(WithdrawalPolicy::OwnerOnly)
+    }
+
+    // Internal helper that appends a member to a joint goal and indexes the goal
+    // under the member
+    fn push_member(env: &Env, goal_id: u64, config: &mut JointGoalConfig, member: Address) {
+        if config.members.len() >= MAX_GOAL_MEMBERS {
+            panic_with_error!(env, SavingsGoalError::TooManyMembers);
+        }
+        config.members.push_back(member.clone());
+
+        let mut member_goals = Self::get_member_goals(env.clone(), member.clone());
+        member_goals.push_back(goal_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::MemberGoals(member.clone()), &member_goals);
+
+        GoalEvents::member_added(env, goal_id, &member);
+    }
+
+    // Internal helper returning the milestone percentages already triggered
+    fn triggered_milestones(env: &Env, goal_id: u64) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::GoalMilestonesPercent(goal_id))
+            .unwrap_or(Vec::new(env))
+    }
+
+    // Internal helper to load a goal's lock if it is still in force
+    fn active_lock(env: &Env, goal_id: u64) -> Option<GoalLock> {
+        env.storage()
+            .persistent()
+            .get::<_, GoalLock>(&DataKey::GoalLock(goal_id))
+            .filter(|lock| lock.active)
+    }
+
+    // Internal helper returning the penalty in basis points for a goal that
+    // is still before its deadline, or None once the deadline has passed
+    fn penalty_bps(env: &Env, goal: &SavingsGoal) -> Option<u32> {
+        let now = env.ledger().sequence() as u64;
+        if now >= goal.deadline {
+            return None;
+        }
+        let remaining = goal.deadline - now;
+        let schedule = Self::get_penalty_schedule(env.clone());
+        let mut bps = schedule.last().map(|tier| tier.penalty_bps).unwrap_or(0);
+        for tier in schedule.iter() {
+            if remaining >= tier.min_ledgers_remaining {
+                bps = tier.penalty_bps;
+                break;
+            }
+        }
+        Some(bps)
+    }
+
+    // Internal helper that charges the early-withdrawal penalty on `amount`
+    // leaving a locked goal, or releases the lock if the deadline has passed.
+    // Must be called before the goal balance is updated.
+    fn charge_lock_penalty(env: &Env, goal: &SavingsGoal, balance: i128, amount: i128) -> i128 {
+        let mut lock = match Self::active_lock(env, goal.goal_id) {
+            Some(lock) => lock,
+            None => return 0,
+        };
+        let bps = match Self::penalty_bps(env, goal) {
+            Some(bps) => bps,
+            None => {
+                Self::release_lock(env, goal, lock);
+                return 0;
+            }
+        };
+
+        Self::settle_bonus(env, &mut lock, balance);
+        env.storage()
+            .persistent()
+            .set(&DataKey::GoalLock(goal.goal_id), &lock);
+        Self::adjust_locked_balance(env, -amount);
+
+        let penalty = amount * bps as i128 / BPS_DENOMINATOR as i128;
+        if penalty == 0 {
+            return 0;
+        }
+        let destination = Self::get_penalty_destination(env.clone());
+        match destination {
+            PenaltyDestination::Treasury => {
+                let treasury = Self::get_treasury(env.clone())
+                    .unwrap_or_else(|| panic_with_error!(env, SavingsGoalError::TreasuryNotSet));
+                Self::token_client(env).transfer(
+                    &env.current_contract_address(),
+                    &treasury,
+                    &penalty,
+                );
+            }
+            PenaltyDestination::BonusPool => Self::distribute_bonus(env, penalty),
+        }
+        GoalEvents::penalty_charged(env, goal.goal_id, penalty, bps, destination);
+
+        penalty
+    }
+
+    // Internal helper that ends a goal's lock when the goal is closed. Returns
+    // the accrued bonus if the goal reached its target; otherwise the bonus is
+    // returned to the pool.
+    fn finish_lock(env: &Env, goal: &SavingsGoal, reached: bool) -> i128 {
+        let mut lock: GoalLock = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::GoalLock(goal.goal_id))
+        {
+            Some(lock) => lock,
+            None => return 0,
+        };
+        // charge_lock_penalty already settled and removed the balance from the
+        // locked total, so only the flag needs clearing here
+        lock.active = false;
+        let bonus = lock.accrued_bonus;
+        lock.accrued_bonus = 0;
+        env.storage()
+            .persistent()
+            .set(&DataKey::GoalLock(goal.goal_id), &lock);
+
+        if bonus <= 0 {
+            return 0;
+        }
+        if reached {
+            GoalEvents::bonus_claimed(env, goal.goal_id, &goal.user, bonus);
+            bonus
+        } else {
+            Self::distribute_bonus(env, bonus);
+            0
+        }
+    }
+
+    // Internal helper that releases a lock at its deadline
+    fn release_lock(env: &Env, goal: &SavingsGoal, mut lock: GoalLock) {
+        let balance = Self::get_goal_balance(env.clone(), goal.goal_id);
+        Self::settle_bonus(env, &mut lock, balance);
+        lock.active = false;
+        env.storage()
+            .persistent()
+            .set(&DataKey::GoalLock(goal.goal_id), &lock);
+        Self::adjust_locked_balance(env, -balance);
+
+        GoalEvents::goal_unlocked(
+            env,
+            goal.goal_id,
+            &goal.user,
+            env.ledger().sequence() as u64,
+        );
+    }
+
+    // Internal helper that accrues a lock's share of the bonus pool since its
+    // last settlement
+    fn settle_bonus(env: &Env, lock: &mut GoalLock, balance: i128) {
+        let index = Self::bonus_index(env);
+        lock.accrued_bonus += balance * (index - lock.bonus_index) / BONUS_INDEX_SCALE;
+        lock.bonus_index = index;
+    }
+
+    // Internal helper that shares `amount` among actively locked balances,
+    // holding it back while nobody is locked
+    fn distribute_bonus(env: &Env, amount: i128) {
+        let undistributed: i128 = env
+            .storage()
+            .instance()
+            .get(&DataKey::BonusUndistributed)
+            .unwrap_or(0);
+        let total_locked = Self::get_total_locked_balance(env.clone());
+        if total_locked <= 0 {
+            env.storage()
+                .instance()
+                .set(&DataKey::BonusUndistributed, &(undistributed + amount));
+            return;
+        }
+
+        let index =
+            Self::bonus_index(env) + (undistributed + amount) * BONUS_INDEX_SCALE / total_locked;
+        env.storage().instance().set(&DataKey::BonusIndex, &index);
+        env.storage()
+            .instance()
+            .set(&DataKey::BonusUndistributed, &0i128);
+    }
+
+    // Internal helper returning the cumulative bonus index
+    fn bonus_index(env: &Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::BonusIndex)
+            .unwrap_or(0)
+    }
+
+    // Internal helper to adjust the sum of actively locked balances
+    fn adjust_locked_balance(env: &Env, delta: i128) {
+        let total = Self::get_total_locked_balance(env.clone()) + delta;
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalLockedBalance, &total);
+    }
+
+    // Internal helper to build a client for the configured savings token
+    fn token_client(env: &Env) -> token::Client<'_> {
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .unwrap_or_else(|| panic_with_error!(env, SavingsGoalError::TokenNotSet));
+        token::Client::new(env, &token)
+    }
+
     // Internal helper to verify admin
     fn require_admin(env: &Env, caller: &Address) {
         let admin: Address = env
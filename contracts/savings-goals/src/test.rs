@@ -3,7 +3,11 @@
 #![cfg(test)]
 
 use crate::{SavingsGoalsContract, SavingsGoalsContractClient};
-use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, Symbol, Vec};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events as _},
+    Address, Env, Symbol, Vec,
+};
 
 use crate::types::{
     ErrorCode, GoalResult, MilestoneAchievementRequest, MilestoneResult, SavingsGoalRequest,
@@ -845,3 +849,582 @@ fn test_milestone_batch_too_large() {
 
     client.batch_mark_milestones(&user, &milestone_requests);
 }
+
+// ============================================================================
+// Token Custody Tests
+// ============================================================================
+
+/// Helper that configures a Stellar asset as the savings token and funds a user.
+fn setup_token(
+    env: &Env,
+    admin: &Address,
+    client: &SavingsGoalsContractClient<'static>,
+    user: &Address,
+    amount: i128,
+) -> soroban_sdk::token::Client<'static> {
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    soroban_sdk::token::StellarAssetClient::new(env, &sac.address()).mint(user, &amount);
+    client.set_token(admin, &sac.address());
+    soroban_sdk::token::Client::new(env, &sac.address())
+}
+
+/// Helper that creates a single goal with no initial contribution.
+fn create_empty_goal(
+    env: &Env,
+    admin: &Address,
+    client: &SavingsGoalsContractClient<'static>,
+    user: &Address,
+    target: i128,
+) -> u64 {
+    let mut request = create_valid_request(env, user, "custody", target);
+    request.initial_contribution = 0;
+    let mut requests = Vec::new(env);
+    requests.push_back(request);
+    let result = client.batch_set_savings_goals(admin, &requests);
+    match result.results.get(0).unwrap() {
+        GoalResult::Success(goal) => goal.goal_id,
+        _ => panic!("expected goal creation to succeed"),
+    }
+}
+
+#[test]
+fn test_contribute_transfers_tokens() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let token = setup_token(&env, &admin, &client, &user, 100_000_000);
+    let goal_id = create_empty_goal(&env, &admin, &client, &user, 80_000_000);
+
+    assert_eq!(client.contribute(&goal_id, &30_000_000), 30_000_000);
+    assert_eq!(token.balance(&user), 70_000_000);
+    assert_eq!(token.balance(&client.address), 30_000_000);
+    assert_eq!(client.get_goal_balance(&goal_id), 30_000_000);
+    assert_eq!(
+        client.get_goal(&goal_id).unwrap().current_amount,
+        30_000_000
+    );
+}
+
+#[test]
+fn test_contribute_emits_milestones() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    setup_token(&env, &admin, &client, &user, 100_000_000);
+    let goal_id = create_empty_goal(&env, &admin, &client, &user, 10_000_000);
+
+    client.contribute(&goal_id, &5_000_000);
+    let triggered: Vec<u32> = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get(&crate::DataKey::GoalMilestonesPercent(goal_id))
+            .unwrap()
+    });
+    assert_eq!(triggered.len(), 2);
+    assert!(triggered.contains(25));
+    assert!(triggered.contains(50));
+}
+
+#[test]
+fn test_withdraw_returns_tokens() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let token = setup_token(&env, &admin, &client, &user, 100_000_000);
+    let goal_id = create_empty_goal(&env, &admin, &client, &user, 80_000_000);
+
+    client.contribute(&goal_id, &30_000_000);
+    assert_eq!(client.withdraw(&goal_id, &10_000_000), 20_000_000);
+    assert_eq!(token.balance(&user), 80_000_000);
+    assert_eq!(client.get_goal_balance(&goal_id), 20_000_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_withdraw_more_than_balance_fails() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    setup_token(&env, &admin, &client, &user, 100_000_000);
+    let goal_id = create_empty_goal(&env, &admin, &client, &user, 80_000_000);
+
+    client.contribute(&goal_id, &10_000_000);
+    client.withdraw(&goal_id, &10_010_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")]
+fn test_contribute_zero_fails() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    setup_token(&env, &admin, &client, &user, 100_000_000);
+    let goal_id = create_empty_goal(&env, &admin, &client, &user, 80_000_000);
+
+    client.contribute(&goal_id, &0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_contribute_without_token_fails() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let goal_id = create_empty_goal(&env, &admin, &client, &user, 80_000_000);
+
+    client.contribute(&goal_id, &1_000_000);
+}
+
+#[test]
+fn test_close_goal_refunds_balance() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let token = setup_token(&env, &admin, &client, &user, 100_000_000);
+    let goal_id = create_empty_goal(&env, &admin, &client, &user, 80_000_000);
+
+    client.contribute(&goal_id, &40_000_000);
+    assert_eq!(client.close_goal(&goal_id), 40_000_000);
+    assert_eq!(token.balance(&user), 100_000_000);
+    assert_eq!(client.get_goal_balance(&goal_id), 0);
+    assert!(!client.get_goal(&goal_id).unwrap().is_active);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #8)")]
+fn test_contribute_to_closed_goal_fails() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    setup_token(&env, &admin, &client, &user, 100_000_000);
+    let goal_id = create_empty_goal(&env, &admin, &client, &user, 80_000_000);
+
+    client.close_goal(&goal_id);
+    client.contribute(&goal_id, &1_000_000);
+}
+
+// ============================================================================
+// Time-Locked Vault Tests
+// ============================================================================
+
+/// Helper that advances the ledger sequence.
+fn advance_ledger(env: &Env, ledgers: u32) {
+    use soroban_sdk::testutils::Ledger;
+    env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+#[test]
+fn test_lock_goal_and_early_withdraw_pays_treasury() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let token = setup_token(&env, &admin, &client, &user, 100_000_000);
+    let goal_id = create_empty_goal(&env, &admin, &client, &user, 80_000_000);
+
+    client.set_penalty_destination(
+        &admin,
+        &crate::PenaltyDestination::Treasury,
+        &Some(treasury.clone()),
+    );
+    client.contribute(&goal_id, &50_000_000);
+    client.lock_goal(&goal_id);
+    assert!(client.get_goal_lock(&goal_id).unwrap().active);
+    assert_eq!(client.get_total_locked_balance(), 50_000_000);
+
+    // Default schedule charges 10%
+    assert_eq!(
+        client.get_projected_penalty(&goal_id, &20_000_000),
+        2_000_000
+    );
+    client.withdraw(&goal_id, &20_000_000);
+
+    assert_eq!(token.balance(&user), 68_000_000);
+    assert_eq!(token.balance(&treasury), 2_000_000);
+    assert_eq!(client.get_goal_balance(&goal_id), 30_000_000);
+    assert_eq!(client.get_total_locked_balance(), 30_000_000);
+}
+
+#[test]
+fn test_penalty_schedule_steps_down() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    setup_token(&env, &admin, &client, &user, 100_000_000);
+    let goal_id = create_empty_goal(&env, &admin, &client, &user, 80_000_000);
+
+    let schedule = Vec::from_array(
+        &env,
+        [
+            crate::PenaltyTier {
+                min_ledgers_remaining: 500,
+                penalty_bps: 2_000,
+            },
+            crate::PenaltyTier {
+                min_ledgers_remaining: 100,
+                penalty_bps: 500,
+            },
+            crate::PenaltyTier {
+                min_ledgers_remaining: 0,
+                penalty_bps: 100,
+            },
+        ],
+    );
+    client.set_penalty_schedule(&admin, &schedule);
+    client.contribute(&goal_id, &10_000_000);
+    client.lock_goal(&goal_id);
+
+    // Deadline is 1000 ledgers away
+    assert_eq!(
+        client.get_projected_penalty(&goal_id, &10_000_000),
+        2_000_000
+    );
+    advance_ledger(&env, 600);
+    assert_eq!(client.get_projected_penalty(&goal_id, &10_000_000), 500_000);
+    advance_ledger(&env, 350);
+    assert_eq!(client.get_projected_penalty(&goal_id, &10_000_000), 100_000);
+    advance_ledger(&env, 50);
+    assert_eq!(client.get_projected_penalty(&goal_id, &10_000_000), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_penalty_schedule_rising_fails() {
+    let (env, admin, client) = setup_test_contract();
+    let schedule = Vec::from_array(
+        &env,
+        [
+            crate::PenaltyTier {
+                min_ledgers_remaining: 500,
+                penalty_bps: 100,
+            },
+            crate::PenaltyTier {
+                min_ledgers_remaining: 0,
+                penalty_bps: 500,
+            },
+        ],
+    );
+    client.set_penalty_schedule(&admin, &schedule);
+}
+
+#[test]
+fn test_withdraw_after_deadline_unlocks_without_penalty() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let token = setup_token(&env, &admin, &client, &user, 100_000_000);
+    let goal_id = create_empty_goal(&env, &admin, &client, &user, 80_000_000);
+
+    client.contribute(&goal_id, &40_000_000);
+    client.lock_goal(&goal_id);
+    advance_ledger(&env, 1_000);
+
+    client.withdraw(&goal_id, &40_000_000);
+    assert_eq!(token.balance(&user), 100_000_000);
+    assert!(!client.get_goal_lock(&goal_id).unwrap().active);
+    assert_eq!(client.get_total_locked_balance(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #14)")]
+fn test_unlock_before_deadline_fails() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    setup_token(&env, &admin, &client, &user, 100_000_000);
+    let goal_id = create_empty_goal(&env, &admin, &client, &user, 80_000_000);
+
+    client.lock_goal(&goal_id);
+    client.unlock_goal(&goal_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_lock_twice_fails() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    setup_token(&env, &admin, &client, &user, 100_000_000);
+    let goal_id = create_empty_goal(&env, &admin, &client, &user, 80_000_000);
+
+    client.lock_goal(&goal_id);
+    client.lock_goal(&goal_id);
+}
+
+#[test]
+fn test_bonus_pool_shared_with_savers_who_reach_goal() {
+    let (env, admin, client) = setup_test_contract();
+    let saver = Address::generate(&env);
+    let quitter = Address::generate(&env);
+    let token = setup_token(&env, &admin, &client, &saver, 100_000_000);
+    soroban_sdk::token::StellarAssetClient::new(&env, &token.address).mint(&quitter, &100_000_000);
+
+    let saver_goal = create_empty_goal(&env, &admin, &client, &saver, 50_000_000);
+    let quitter_goal = create_empty_goal(&env, &admin, &client, &quitter, 50_000_000);
+    client.contribute(&saver_goal, &50_000_000);
+    client.contribute(&quitter_goal, &40_000_000);
+    client.lock_goal(&saver_goal);
+    client.lock_goal(&quitter_goal);
+
+    // Quitter closes early: 10% penalty flows to the bonus pool
+    assert_eq!(client.close_goal(&quitter_goal), 36_000_000);
+    assert_eq!(client.get_claimable_bonus(&saver_goal), 4_000_000);
+
+    assert_eq!(client.claim_bonus(&saver_goal), 4_000_000);
+    assert_eq!(token.balance(&saver), 54_000_000);
+    assert_eq!(client.get_claimable_bonus(&saver_goal), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_claim_bonus_before_reaching_goal_fails() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    setup_token(&env, &admin, &client, &user, 100_000_000);
+    let goal_id = create_empty_goal(&env, &admin, &client, &user, 80_000_000);
+
+    client.contribute(&goal_id, &10_000_000);
+    client.lock_goal(&goal_id);
+    client.claim_bonus(&goal_id);
+}
+
+#[test]
+fn test_initial_contribution_rejected_for_token_backed_goals() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    setup_token(&env, &admin, &client, &user, 100_000_000);
+
+    let mut requests = Vec::new(&env);
+    requests.push_back(create_valid_request(&env, &user, "unbacked", 80_000_000));
+    let result = client.batch_set_savings_goals(&admin, &requests);
+
+    assert_eq!(result.metrics.failed_goals, 1);
+    match result.results.get(0).unwrap() {
+        GoalResult::Failure(_, code) => {
+            assert_eq!(code, ErrorCode::UNBACKED_INITIAL_CONTRIBUTION)
+        }
+        _ => panic!("expected goal creation to fail"),
+    }
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_claim_bonus_uses_custody_balance() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    setup_token(&env, &admin, &client, &user, 100_000_000);
+    let goal_id = create_empty_goal(&env, &admin, &client, &user, 80_000_000);
+
+    client.contribute(&goal_id, &10_000_000);
+    client.lock_goal(&goal_id);
+    // Progress without a matching deposit does not count towards the target
+    client.test_set_goal_current_amount(&goal_id, &80_000_000);
+    client.claim_bonus(&goal_id);
+}
+
+// ============================================================================
+// Joint Goal Tests
+// ============================================================================
+
+/// Helper that creates a joint goal owned by `owner` with one extra member,
+/// both funded with tokens.
+fn setup_joint_goal(
+    policy: crate::WithdrawalPolicy,
+) -> (
+    Env,
+    SavingsGoalsContractClient<'static>,
+    soroban_sdk::token::Client<'static>,
+    Address,
+    Address,
+    u64,
+) {
+    let (env, admin, client) = setup_test_contract();
+    let owner = Address::generate(&env);
+    let member = Address::generate(&env);
+    let token = setup_token(&env, &admin, &client, &owner, 100_000_000);
+    soroban_sdk::token::StellarAssetClient::new(&env, &token.address).mint(&member, &100_000_000);
+
+    let goal_id = create_empty_goal(&env, &admin, &client, &owner, 100_000_000);
+    client.set_joint_goal(&goal_id, &Vec::from_array(&env, [member.clone()]), &policy);
+
+    (env, client, token, owner, member, goal_id)
+}
+
+#[test]
+fn test_joint_goal_tracks_member_shares() {
+    let (env, client, _, owner, member, goal_id) =
+        setup_joint_goal(crate::WithdrawalPolicy::OwnerOnly);
+
+    client.contribute(&goal_id, &30_000_000);
+    client.contribute_from(&member, &goal_id, &10_000_000);
+
+    assert_eq!(client.get_member_contribution(&goal_id, &owner), 30_000_000);
+    assert_eq!(
+        client.get_member_contribution(&goal_id, &member),
+        10_000_000
+    );
+    assert_eq!(
+        client.get_goal(&goal_id).unwrap().current_amount,
+        40_000_000
+    );
+    assert_eq!(
+        client.get_member_goals(&member),
+        Vec::from_array(&env, [goal_id])
+    );
+
+    // Owner-only withdrawals reduce shares pro-rata
+    client.withdraw(&goal_id, &20_000_000);
+    assert_eq!(client.get_member_contribution(&goal_id, &owner), 15_000_000);
+    assert_eq!(client.get_member_contribution(&goal_id, &member), 5_000_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_contribute_from_non_member_fails() {
+    let (env, client, token, _, _, goal_id) = setup_joint_goal(crate::WithdrawalPolicy::OwnerOnly);
+    let outsider = Address::generate(&env);
+    soroban_sdk::token::StellarAssetClient::new(&env, &token.address).mint(&outsider, &10_000_000);
+
+    client.contribute_from(&outsider, &goal_id, &10_000_000);
+}
+
+#[test]
+fn test_joint_goal_pro_rata_payout_on_close() {
+    let (_, client, token, owner, member, goal_id) =
+        setup_joint_goal(crate::WithdrawalPolicy::ProRataOnClose);
+
+    client.contribute(&goal_id, &30_000_000);
+    client.contribute_from(&member, &goal_id, &10_000_000);
+
+    assert_eq!(client.close_goal(&goal_id), 40_000_000);
+    assert_eq!(token.balance(&owner), 100_000_000);
+    assert_eq!(token.balance(&member), 100_000_000);
+    assert_eq!(client.get_member_contribution(&goal_id, &member), 0);
+}
+
+#[test]
+fn test_joint_goal_pro_rata_payout_includes_bonus() {
+    let (env, client, token, owner, member, goal_id) =
+        setup_joint_goal(crate::WithdrawalPolicy::ProRataOnClose);
+    let admin = client.get_admin();
+
+    client.contribute(&goal_id, &60_000_000);
+    client.contribute_from(&member, &goal_id, &40_000_000);
+    client.lock_goal(&goal_id);
+
+    // Another saver quits early, funding the bonus pool with a 10% penalty
+    let quitter = Address::generate(&env);
+    soroban_sdk::token::StellarAssetClient::new(&env, &token.address).mint(&quitter, &10_000_000);
+    let quitter_goal = create_empty_goal(&env, &admin, &client, &quitter, 50_000_000);
+    client.contribute(&quitter_goal, &10_000_000);
+    client.lock_goal(&quitter_goal);
+    client.close_goal(&quitter_goal);
+
+    advance_ledger(&env, 1_000);
+    assert_eq!(client.close_goal(&goal_id), 101_000_000);
+
+    // The bonus is split 60/40 along with the balance, leaving nothing behind
+    assert_eq!(token.balance(&owner), 100_600_000);
+    assert_eq!(token.balance(&member), 100_400_000);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #24)")]
+fn test_pro_rata_goal_blocks_withdraw() {
+    let (_, client, _, _, _, goal_id) = setup_joint_goal(crate::WithdrawalPolicy::ProRataOnClose);
+
+    client.contribute(&goal_id, &30_000_000);
+    client.withdraw(&goal_id, &10_000_000);
+}
+
+#[test]
+fn test_majority_approval_withdrawal() {
+    let (env, client, token, owner, member, goal_id) =
+        setup_joint_goal(crate::WithdrawalPolicy::MajorityApproval);
+    let third = Address::generate(&env);
+    client.add_goal_member(&goal_id, &third);
+
+    client.contribute(&goal_id, &30_000_000);
+    client.contribute_from(&member, &goal_id, &30_000_000);
+
+    // One of three approvals is not a majority
+    let proposal_id = client.propose_withdrawal(&owner, &goal_id, &12_000_000, &owner);
+    assert!(
+        !client
+            .get_withdrawal_proposal(&goal_id, &proposal_id)
+            .unwrap()
+            .executed
+    );
+    assert_eq!(client.get_goal_balance(&goal_id), 60_000_000);
+
+    assert!(client.approve_withdrawal(&member, &goal_id, &proposal_id));
+    assert_eq!(token.balance(&owner), 82_000_000);
+    assert_eq!(client.get_goal_balance(&goal_id), 48_000_000);
+    assert_eq!(client.get_member_contribution(&goal_id, &owner), 24_000_000);
+    assert_eq!(
+        client.get_member_contribution(&goal_id, &member),
+        24_000_000
+    );
+}
+
+#[test]
+fn test_add_goal_member_needs_new_and_existing_members() {
+    let (env, client, _, owner, member, goal_id) =
+        setup_joint_goal(crate::WithdrawalPolicy::MajorityApproval);
+    let third = Address::generate(&env);
+    client.add_goal_member(&goal_id, &third);
+
+    let authorized = |address: &Address| env.auths().iter().any(|(signer, _)| signer == address);
+    assert!(authorized(&owner));
+    assert!(authorized(&member));
+    assert!(authorized(&third));
+}
+
+#[test]
+fn test_proposal_electorate_fixed_at_creation() {
+    let (env, client, _, owner, member, goal_id) =
+        setup_joint_goal(crate::WithdrawalPolicy::MajorityApproval);
+    let third = Address::generate(&env);
+    client.add_goal_member(&goal_id, &third);
+    client.contribute(&goal_id, &30_000_000);
+
+    // Three voters, so two approvals are a majority
+    let proposal_id = client.propose_withdrawal(&owner, &goal_id, &10_000_000, &owner);
+    let proposal = client
+        .get_withdrawal_proposal(&goal_id, &proposal_id)
+        .unwrap();
+    assert_eq!(proposal.electorate.len(), 3);
+    assert_eq!(proposal.threshold, 2);
+
+    // Members added afterwards neither vote nor raise the threshold
+    let late = Address::generate(&env);
+    let later = Address::generate(&env);
+    client.add_goal_member(&goal_id, &late);
+    client.add_goal_member(&goal_id, &later);
+    assert_eq!(
+        client.try_approve_withdrawal(&late, &goal_id, &proposal_id),
+        Err(Ok(crate::SavingsGoalError::NotMember.into()))
+    );
+    assert!(client.approve_withdrawal(&member, &goal_id, &proposal_id));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #26)")]
+fn test_approve_executed_proposal_fails() {
+    let (env, client, _, owner, member, goal_id) =
+        setup_joint_goal(crate::WithdrawalPolicy::MajorityApproval);
+    let third = Address::generate(&env);
+    client.add_goal_member(&goal_id, &third);
+    client.contribute(&goal_id, &30_000_000);
+
+    let proposal_id = client.propose_withdrawal(&owner, &goal_id, &10_000_000, &owner);
+    client.approve_withdrawal(&member, &goal_id, &proposal_id);
+    client.approve_withdrawal(&third, &goal_id, &proposal_id);
+}
+
+#[test]
+fn test_milestone_attributed_to_contributor() {
+    let (env, client, _, _, member, goal_id) = setup_joint_goal(crate::WithdrawalPolicy::OwnerOnly);
+
+    client.contribute_from(&member, &goal_id, &50_000_000);
+
+    let attributed = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            let name: Result<Symbol, _> =
+                soroban_sdk::TryFromVal::try_from_val(&env, &topics.get(1).unwrap());
+            name == Ok(symbol_short!("ms_attr"))
+        })
+        .count();
+    assert_eq!(attributed, 2);
+}
@@ -11,6 +11,21 @@ pub const MIN_GOAL_AMOUNT: i128 = 10_000_000;
 /// Maximum goal amount (1 billion XLM in stroops)
 pub const MAX_GOAL_AMOUNT: i128 = 1_000_000_000_000_000_000;
 
+/// Basis points denominator (100%)
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Early-withdrawal penalty applied when no schedule has been configured (10%)
+pub const DEFAULT_PENALTY_BPS: u32 = 1_000;
+
+/// Maximum number of tiers in a penalty schedule
+pub const MAX_PENALTY_TIERS: u32 = 10;
+
+/// Fixed-point scale for the bonus pool reward index
+pub const BONUS_INDEX_SCALE: i128 = 1_000_000_000_000;
+
+/// Maximum number of members in a joint savings goal (including the owner)
+pub const MAX_GOAL_MEMBERS: u32 = 20;
+
 /// Represents a savings goal request for a user.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -49,6 +64,102 @@ pub struct SavingsGoal {
     pub is_active: bool,
 }
 
+/// One step of an early-withdrawal penalty schedule.
+///
+/// A tier applies while at least `min_ledgers_remaining` ledgers are left
+/// before the goal deadline. Tiers are ordered from furthest to nearest.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct PenaltyTier {
+    /// Minimum ledgers remaining before the deadline for this tier to apply
+    pub min_ledgers_remaining: u64,
+    /// Penalty charged on early withdrawals (basis points)
+    pub penalty_bps: u32,
+}
+
+/// Where early-withdrawal penalties are sent.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum PenaltyDestination {
+    /// Transferred to the configured treasury address
+    Treasury,
+    /// Kept in the contract and shared among locked savers who reach their goals
+    BonusPool,
+}
+
+/// Lock state of a time-locked goal.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct GoalLock {
+    /// Ledger at which the goal was locked
+    pub locked_at: u64,
+    /// Whether the lock is still in force
+    pub active: bool,
+    /// Bonus pool index at the last settlement
+    pub bonus_index: i128,
+    /// Bonus accrued and not yet claimed
+    pub accrued_bonus: i128,
+}
+
+/// Who may take funds out of a joint savings goal.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum WithdrawalPolicy {
+    /// Only the goal owner may withdraw; closing refunds the owner
+    OwnerOnly,
+    /// Withdrawals need approval from a majority of members; closing pays
+    /// members pro-rata to their shares
+    MajorityApproval,
+    /// No withdrawals; closing pays members pro-rata to their shares
+    ProRataOnClose,
+}
+
+/// Membership and withdrawal policy of a joint savings goal.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct JointGoalConfig {
+    /// Members allowed to contribute (the owner is always first)
+    pub members: Vec<Address>,
+    /// Withdrawal policy
+    pub policy: WithdrawalPolicy,
+}
+
+/// A member's share of a goal's custody balance.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct MemberShare {
+    /// Member address
+    pub member: Address,
+    /// Net amount contributed and not yet withdrawn (in stroops)
+    pub contributed: i128,
+}
+
+/// A pending withdrawal from a majority-approval joint goal.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct WithdrawalProposal {
+    /// Proposal ID (unique per goal)
+    pub proposal_id: u64,
+    /// Goal to withdraw from
+    pub goal_id: u64,
+    /// Member who proposed the withdrawal
+    pub proposer: Address,
+    /// Address receiving the funds
+    pub recipient: Address,
+    /// Amount to withdraw (in stroops)
+    pub amount: i128,
+    /// Members allowed to vote, fixed when the proposal is created
+    pub electorate: Vec<Address>,
+    /// Approvals needed to execute (a strict majority of the electorate)
+    pub threshold: u32,
+    /// Members who approved the withdrawal
+    pub approvals: Vec<Address>,
+    /// Whether the withdrawal has been executed
+    pub executed: bool,
+    /// Ledger sequence when the proposal was created
+    pub created_at: u64,
+}
+
 /// Result of processing a single goal creation.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -199,6 +310,34 @@ pub enum DataKey {
     GoalMilestonesPercent(u64),
     /// Total milestones achieved lifetime
     TotalMilestonesAchieved,
+    /// SAC token held in custody for goals
+    Token,
+    /// Token balance held for a goal (goal_id -> i128)
+    GoalBalance(u64),
+    /// Lock state of a time-locked goal (goal_id -> GoalLock)
+    GoalLock(u64),
+    /// Early-withdrawal penalty schedule (Vec<PenaltyTier>)
+    PenaltySchedule,
+    /// Destination of early-withdrawal penalties
+    PenaltyDestination,
+    /// Treasury address receiving penalties
+    Treasury,
+    /// Cumulative bonus pool reward index (scaled by BONUS_INDEX_SCALE)
+    BonusIndex,
+    /// Bonus pool funds with no locked savers to share them yet
+    BonusUndistributed,
+    /// Sum of balances held by active locked goals
+    TotalLockedBalance,
+    /// Joint goal membership and policy (goal_id -> JointGoalConfig)
+    JointGoal(u64),
+    /// Member's share of a goal's balance ((goal_id, member) -> i128)
+    MemberContribution(u64, Address),
+    /// Joint goals a non-owner member belongs to (member -> Vec<goal_id>)
+    MemberGoals(Address),
+    /// Withdrawal proposal ((goal_id, proposal_id) -> WithdrawalProposal)
+    WithdrawalProposal(u64, u64),
+    /// Last withdrawal proposal ID for a goal
+    LastProposalId(u64),
 }
 
 /// Error codes for goal validation and creation.
@@ -225,6 +364,8 @@ pub mod ErrorCode {
     pub const UNAUTHORIZED_USER: u32 = 8;
     /// Goal has already achieved this milestone
     pub const MILESTONE_ALREADY_ACHIEVED: u32 = 9;
+    /// Initial contribution given while goals are token-backed
+    pub const UNBACKED_INITIAL_CONTRIBUTION: u32 = 11;
 }
 
 /// Events emitted by the savings goals contract.
@@ -299,6 +440,126 @@ impl GoalEvents {
         env.events().publish(topics, (goal_id, milestone_percent));
     }
 
+    /// Event emitted when tokens are contributed to a goal.
+    pub fn contribution_made(
+        env: &Env,
+        goal_id: u64,
+        user: &Address,
+        amount: i128,
+        new_amount: i128,
+    ) {
+        let topics = (symbol_short!("goal"), symbol_short!("contrib"), goal_id);
+        env.events()
+            .publish(topics, (user.clone(), amount, new_amount));
+    }
+
+    /// Event emitted when tokens are withdrawn from a goal.
+    pub fn withdrawal_made(
+        env: &Env,
+        goal_id: u64,
+        user: &Address,
+        amount: i128,
+        new_amount: i128,
+    ) {
+        let topics = (symbol_short!("goal"), symbol_short!("withdraw"), goal_id);
+        env.events()
+            .publish(topics, (user.clone(), amount, new_amount));
+    }
+
+    /// Event emitted when a goal is closed and its funds returned.
+    pub fn goal_closed(env: &Env, goal_id: u64, user: &Address, refunded: i128) {
+        let topics = (symbol_short!("goal"), symbol_short!("closed"), goal_id);
+        env.events().publish(topics, (user.clone(), refunded));
+    }
+
+    /// Event emitted when a goal enters lock mode.
+    pub fn goal_locked(env: &Env, goal_id: u64, user: &Address, deadline: u64) {
+        let topics = (symbol_short!("goal"), symbol_short!("locked"), goal_id);
+        env.events().publish(topics, (user.clone(), deadline));
+    }
+
+    /// Event emitted when a goal's lock is released at its deadline.
+    pub fn goal_unlocked(env: &Env, goal_id: u64, user: &Address, unlocked_at: u64) {
+        let topics = (symbol_short!("goal"), symbol_short!("unlocked"), goal_id);
+        env.events().publish(topics, (user.clone(), unlocked_at));
+    }
+
+    /// Event emitted when an early-withdrawal penalty is charged.
+    pub fn penalty_charged(
+        env: &Env,
+        goal_id: u64,
+        penalty: i128,
+        penalty_bps: u32,
+        destination: PenaltyDestination,
+    ) {
+        let topics = (symbol_short!("goal"), symbol_short!("penalty"), goal_id);
+        env.events()
+            .publish(topics, (penalty, penalty_bps, destination));
+    }
+
+    /// Event emitted when a saver claims their share of the bonus pool.
+    pub fn bonus_claimed(env: &Env, goal_id: u64, user: &Address, amount: i128) {
+        let topics = (symbol_short!("goal"), symbol_short!("bonus"), goal_id);
+        env.events().publish(topics, (user.clone(), amount));
+    }
+
+    /// Event emitted when a goal becomes a joint goal.
+    pub fn joint_goal_created(env: &Env, goal_id: u64, config: &JointGoalConfig) {
+        let topics = (symbol_short!("goal"), symbol_short!("joint"), goal_id);
+        env.events()
+            .publish(topics, (config.members.len(), config.policy));
+    }
+
+    /// Event emitted when a member joins a joint goal.
+    pub fn member_added(env: &Env, goal_id: u64, member: &Address) {
+        let topics = (symbol_short!("goal"), symbol_short!("member"), goal_id);
+        env.events().publish(topics, member.clone());
+    }
+
+    /// Event emitted when a milestone is reached, attributing the goal's
+    /// balance to each member.
+    pub fn milestone_attributed(
+        env: &Env,
+        goal_id: u64,
+        milestone_percentage: u32,
+        triggered_by: &Address,
+        shares: Vec<MemberShare>,
+    ) {
+        let topics = (symbol_short!("goal"), symbol_short!("ms_attr"), goal_id);
+        env.events()
+            .publish(topics, (milestone_percentage, triggered_by.clone(), shares));
+    }
+
+    /// Event emitted when a withdrawal is proposed on a joint goal.
+    pub fn withdrawal_proposed(env: &Env, proposal: &WithdrawalProposal) {
+        let topics = (
+            symbol_short!("goal"),
+            symbol_short!("wd_prop"),
+            proposal.goal_id,
+        );
+        env.events().publish(
+            topics,
+            (
+                proposal.proposal_id,
+                proposal.proposer.clone(),
+                proposal.recipient.clone(),
+                proposal.amount,
+            ),
+        );
+    }
+
+    /// Event emitted when a member approves a withdrawal proposal.
+    pub fn withdrawal_approved(env: &Env, goal_id: u64, proposal_id: u64, member: &Address) {
+        let topics = (symbol_short!("goal"), symbol_short!("wd_appr"), goal_id);
+        env.events().publish(topics, (proposal_id, member.clone()));
+    }
+
+    /// Event emitted for each member paid out when a joint goal is closed.
+    pub fn member_payout(env: &Env, goal_id: u64, member: &Address, amount: i128) {
+        let topics = (symbol_short!("goal"), symbol_short!("payout"), goal_id);
+        env.events().publish(topics, (member.clone(), amount));
+    }
+
     /// Event emitted when milestone achievement fails.
     pub fn milestone_achievement_failed(env: &Env, batch_id: u64, goal_id: u64, error_code: u32) {
         let topics = (
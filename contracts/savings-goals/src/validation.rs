@@ -3,8 +3,8 @@
 use soroban_sdk::{Address, Env};
 
 use crate::types::{
-    DataKey, ErrorCode, MilestoneAchievementRequest, SavingsGoal, SavingsGoalRequest,
-    MAX_GOAL_AMOUNT, MIN_GOAL_AMOUNT,
+    DataKey, ErrorCode, MilestoneAchievementRequest, PenaltyTier, SavingsGoal, SavingsGoalRequest,
+    BPS_DENOMINATOR, MAX_GOAL_AMOUNT, MAX_PENALTY_TIERS, MIN_GOAL_AMOUNT,
 };
 
 /// Validates a savings goal request.
@@ -202,6 +202,37 @@ pub fn is_valid_milestone_percentage(percentage: u32) -> bool {
     percentage >= 1 && percentage <= 100
 }
 
+/// Validates an early-withdrawal penalty schedule.
+///
+/// # Arguments
+/// * `schedule` - The penalty tiers, ordered from furthest to nearest deadline
+///
+/// # Returns
+/// * `true` if the schedule is non-empty, within the tier limit, ordered by
+///   strictly decreasing `min_ledgers_remaining` and steps the penalty down
+pub fn is_valid_penalty_schedule(schedule: &soroban_sdk::Vec<PenaltyTier>) -> bool {
+    if schedule.is_empty() || schedule.len() > MAX_PENALTY_TIERS {
+        return false;
+    }
+
+    let mut previous: Option<PenaltyTier> = None;
+    for tier in schedule.iter() {
+        if tier.penalty_bps > BPS_DENOMINATOR {
+            return false;
+        }
+        if let Some(prev) = previous {
+            if tier.min_ledgers_remaining >= prev.min_ledgers_remaining
+                || tier.penalty_bps > prev.penalty_bps
+            {
+                return false;
+            }
+        }
+        previous = Some(tier);
+    }
+
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -308,4 +339,36 @@ mod tests {
         assert!(!is_valid_initial_contribution(-1, 100_000_000));
         assert!(!is_valid_initial_contribution(100_000_001, 100_000_000));
     }
+
+    fn tier(min_ledgers_remaining: u64, penalty_bps: u32) -> PenaltyTier {
+        PenaltyTier {
+            min_ledgers_remaining,
+            penalty_bps,
+        }
+    }
+
+    #[test]
+    fn test_valid_penalty_schedule() {
+        let env = Env::default();
+        let schedule =
+            soroban_sdk::Vec::from_array(&env, [tier(10_000, 2_000), tier(1_000, 500), tier(0, 0)]);
+        assert!(is_valid_penalty_schedule(&schedule));
+    }
+
+    #[test]
+    fn test_invalid_penalty_schedule() {
+        let env = Env::default();
+        assert!(!is_valid_penalty_schedule(&soroban_sdk::Vec::new(&env)));
+
+        // Penalty must not increase as the deadline nears
+        let rising = soroban_sdk::Vec::from_array(&env, [tier(1_000, 500), tier(0, 1_000)]);
+        assert!(!is_valid_penalty_schedule(&rising));
+
+        // Thresholds must be strictly decreasing
+        let unordered = soroban_sdk::Vec::from_array(&env, [tier(0, 500), tier(1_000, 500)]);
+        assert!(!is_valid_penalty_schedule(&unordered));
+
+        let too_high = soroban_sdk::Vec::from_array(&env, [tier(0, 10_001)]);
+        assert!(!is_valid_penalty_schedule(&too_high));
+    }
 }
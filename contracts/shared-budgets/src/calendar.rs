@@ -0,0 +1,44 @@
+// Calendar arithmetic for allowance periods. Dates are UTC and use the
+// proleptic Gregorian calendar, so monthly windows follow real month
+// boundaries.
+
+/// Seconds in a UTC day.
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Days between 0000-03-01 and 1970-01-01 in the proleptic Gregorian calendar.
+const EPOCH_SHIFT: i64 = 719_468;
+
+/// Days in a 400-year Gregorian era.
+const DAYS_PER_ERA: i64 = 146_097;
+
+/// Converts days since 1970-01-01 into a `(year, month, day)` civil date.
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + EPOCH_SHIFT;
+    let era = z.div_euclid(DAYS_PER_ERA);
+    let doe = z.rem_euclid(DAYS_PER_ERA);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Converts a civil date into days since 1970-01-01.
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * DAYS_PER_ERA + doe - EPOCH_SHIFT
+}
+
+/// Returns the timestamp of UTC midnight on the first day of the month
+/// containing `timestamp`.
+pub fn month_start(timestamp: u64) -> u64 {
+    let (year, month, _) = civil_from_days((timestamp / SECONDS_PER_DAY) as i64);
+    days_from_civil(year, month, 1) as u64 * SECONDS_PER_DAY
+}
@@ -1,5 +1,6 @@
 #![no_std]
 
+mod calendar;
 mod types;
 mod validation;
 
@@ -8,8 +9,10 @@ use soroban_sdk::{
 };
 
 pub use crate::types::{
-    Budget, BudgetContribution, BudgetSpendingRule, DataKey, SharedBudgetEvents,
-    MAX_BUDGET_MEMBERS, MAX_SPENDING_RULES,
+    AllowancePeriod, ApprovalConfig, Budget, BudgetContribution, BudgetSpendingRule, DataKey,
+    Dissolution, DissolutionRefund, DissolutionReport, MemberAllowance, MemberRole,
+    SharedBudgetEvents, SpendRequest, SpendRequestStatus, DEFAULT_APPROVAL_QUORUM,
+    DEFAULT_APPROVAL_TIMEOUT, MAX_BUDGET_MEMBERS, MAX_SPENDING_RULES,
 };
 use crate::validation::{validate_amount, validate_percentage};
 
@@ -43,6 +46,28 @@ pub enum SharedBudgetError {
     TooManyMembers = 12,
     /// Too many spending rules
     TooManyRules = 13,
+    /// Spend request does not exist
+    RequestNotFound = 14,
+    /// Spend request is no longer pending
+    RequestNotPending = 15,
+    /// Member has already voted on the spend request
+    AlreadyVoted = 16,
+    /// Spender cannot vote on their own request
+    CannotVoteOwnRequest = 17,
+    /// Quorum or timeout is invalid
+    InvalidApprovalConfig = 18,
+    /// Spend request has not expired yet
+    RequestNotExpired = 19,
+    /// Member's role does not permit this action
+    InsufficientRole = 20,
+    /// The budget owner cannot be removed or change role
+    CannotRemoveOwner = 21,
+    /// Spend exceeds the member's allowance for the current period
+    AllowanceExceeded = 22,
+    /// No dissolution has been proposed for the budget
+    DissolutionNotFound = 23,
+    /// A dissolution is already pending for the budget
+    DissolutionPending = 24,
 }
 
 impl From<SharedBudgetError> for soroban_sdk::Error {
@@ -200,18 +225,41 @@ impl SharedBudgetContract {
             .instance()
             .set(&DataKey::TotalContributionsProcessed, &contribution_id);
 
+        // Track per-contributor totals for dissolution refunds
+        let contributed_key = DataKey::ContributedBy(budget_id, contributor.clone());
+        let contributed: i128 = env
+            .storage()
+            .persistent()
+            .get(&contributed_key)
+            .unwrap_or(0);
+        if contributed == 0 {
+            let mut contributors = Self::get_budget_contributors(env.clone(), budget_id);
+            contributors.push_back(contributor.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::Contributors(budget_id), &contributors);
+        }
+        env.storage()
+            .persistent()
+            .set(&contributed_key, &(contributed + amount));
+
         // Emit event
         SharedBudgetEvents::contribution_added(&env, budget_id, &contributor, amount);
     }
 
     /// Spend from a shared budget with spending rule enforcement.
+    ///
+    /// A spend that exceeds a rule marked `requires_approval` is not
+    /// transferred; instead a pending spend request is created for other
+    /// members to approve. Returns the request ID in that case, or `None`
+    /// when the funds were transferred immediately.
     pub fn spend_from_budget(
         env: Env,
         spender: Address,
         budget_id: u64,
         recipient: Address,
         amount: i128,
-    ) {
+    ) -> Option<u64> {
         spender.require_auth();
 
         // Validate amount
@@ -241,28 +289,210 @@ impl SharedBudgetContract {
             panic_with_error!(&env, SharedBudgetError::MemberNotFound);
         }
 
+        // Viewers cannot spend
+        if Self::get_member_role(env.clone(), budget_id, spender.clone())
+            == Some(MemberRole::Viewer)
+        {
+            panic_with_error!(&env, SharedBudgetError::InsufficientRole);
+        }
+
         // Check if budget has sufficient balance
         if budget.balance < amount {
             panic_with_error!(&env, SharedBudgetError::InsufficientBalance);
         }
 
+        // Enforce the member's periodic allowance
+        Self::require_allowance(&env, budget_id, &spender, amount);
+
         // Enforce spending rules
-        Self::enforce_spending_rules(&env, &budget, &spender, amount);
+        if Self::enforce_spending_rules(&env, &budget, &spender, amount) {
+            let request_id =
+                Self::create_spend_request(&env, budget_id, spender, recipient, amount);
+            return Some(request_id);
+        }
 
-        // Transfer tokens from contract to recipient
-        let token_client = token::Client::new(&env, &budget.token);
-        token_client.transfer(&env.current_contract_address(), &recipient, &amount);
+        Self::execute_spend(&env, &mut budget, &spender, &recipient, amount);
+        None
+    }
 
-        // Update budget balance
-        budget.balance -= amount;
+    /// Sets the approval quorum and request timeout for a budget.
+    ///
+    /// The quorum cannot exceed the number of members able to vote on a
+    /// member's request.
+    pub fn set_approval_config(
+        env: Env,
+        caller: Address,
+        budget_id: u64,
+        quorum: u32,
+        timeout: u64,
+    ) {
+        caller.require_auth();
 
-        // Store updated budget
+        let budget: Budget = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Budget(budget_id))
+            .unwrap_or_else(|| panic_with_error!(&env, SharedBudgetError::BudgetNotFound));
+
+        // Only creators or admins can configure approvals
+        if caller != budget.creator {
+            Self::require_admin(&env, &caller);
+        }
+
+        // Spenders are members and cannot vote on their own requests, so at
+        // most all other members can approve
+        let max_quorum = budget.members.len().saturating_sub(1);
+        if quorum == 0 || quorum > max_quorum || timeout == 0 {
+            panic_with_error!(&env, SharedBudgetError::InvalidApprovalConfig);
+        }
+
+        env.storage().persistent().set(
+            &DataKey::ApprovalConfig(budget_id),
+            &ApprovalConfig { quorum, timeout },
+        );
+    }
+
+    /// Approves a pending spend request.
+    ///
+    /// Funds are transferred once the budget's quorum of approvals is
+    /// reached. A request past its expiry is marked expired instead.
+    pub fn approve_spend_request(
+        env: Env,
+        approver: Address,
+        request_id: u64,
+    ) -> SpendRequestStatus {
+        approver.require_auth();
+
+        let mut request = Self::load_votable_request(&env, &approver, request_id);
+        if Self::expire_if_due(&env, &mut request) {
+            return request.status;
+        }
+
+        request.approvals.push_back(approver.clone());
+        SharedBudgetEvents::spend_approved(&env, &request, &approver);
+
+        let config = Self::get_approval_config(env.clone(), request.budget_id);
+        if request.approvals.len() >= config.quorum {
+            let mut budget: Budget = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Budget(request.budget_id))
+                .unwrap_or_else(|| panic_with_error!(&env, SharedBudgetError::BudgetNotFound));
+            if !budget.is_active {
+                panic_with_error!(&env, SharedBudgetError::BudgetNotActive);
+            }
+            if budget.balance < request.amount {
+                panic_with_error!(&env, SharedBudgetError::InsufficientBalance);
+            }
+            // Other spends or a new period may have changed the allowance
+            // since the request was created
+            Self::require_allowance(&env, budget.id, &request.spender, request.amount);
+
+            Self::execute_spend(
+                &env,
+                &mut budget,
+                &request.spender,
+                &request.recipient,
+                request.amount,
+            );
+            Self::finalize_request(&env, &mut request, SpendRequestStatus::Approved);
+        } else {
+            Self::store_request(&env, &request);
+        }
+
+        request.status
+    }
+
+    /// Rejects a pending spend request.
+    ///
+    /// The request is rejected once too few members remain to reach the
+    /// quorum. A request past its expiry is marked expired instead.
+    pub fn reject_spend_request(
+        env: Env,
+        rejecter: Address,
+        request_id: u64,
+    ) -> SpendRequestStatus {
+        rejecter.require_auth();
+
+        let mut request = Self::load_votable_request(&env, &rejecter, request_id);
+        if Self::expire_if_due(&env, &mut request) {
+            return request.status;
+        }
+
+        request.rejections.push_back(rejecter.clone());
+        SharedBudgetEvents::spend_rejected(&env, &request, &rejecter);
+
+        let budget = Self::get_budget(env.clone(), request.budget_id);
+        let config = Self::get_approval_config(env.clone(), request.budget_id);
+        let eligible = Self::eligible_approvers(&budget, &request.spender);
+        if eligible.saturating_sub(request.rejections.len()) < config.quorum {
+            Self::finalize_request(&env, &mut request, SpendRequestStatus::Rejected);
+        } else {
+            Self::store_request(&env, &request);
+        }
+
+        request.status
+    }
+
+    /// Marks a pending spend request as expired once its timeout has passed.
+    /// Anyone may call this.
+    pub fn expire_spend_request(env: Env, request_id: u64) {
+        let mut request = Self::get_spend_request(env.clone(), request_id);
+        if request.status != SpendRequestStatus::Pending {
+            panic_with_error!(&env, SharedBudgetError::RequestNotPending);
+        }
+        if !Self::expire_if_due(&env, &mut request) {
+            panic_with_error!(&env, SharedBudgetError::RequestNotExpired);
+        }
+    }
+
+    /// Get spend request details.
+    pub fn get_spend_request(env: Env, request_id: u64) -> SpendRequest {
         env.storage()
             .persistent()
-            .set(&DataKey::Budget(budget_id), &budget);
+            .get(&DataKey::SpendRequest(request_id))
+            .unwrap_or_else(|| panic_with_error!(&env, SharedBudgetError::RequestNotFound))
+    }
 
-        // Emit event
-        SharedBudgetEvents::expense_incurred(&env, budget_id, &spender, &recipient, amount);
+    /// Returns the pending, unexpired spend requests of a budget.
+    pub fn get_pending_requests(env: Env, budget_id: u64) -> Vec<SpendRequest> {
+        let now = env.ledger().timestamp();
+        let mut pending = Vec::new(&env);
+        for request_id in Self::pending_request_ids(&env, budget_id).iter() {
+            let request = Self::get_spend_request(env.clone(), request_id);
+            if now < request.expires_at {
+                pending.push_back(request);
+            }
+        }
+        pending
+    }
+
+    /// Returns the pending requests of a budget that a member can still vote on.
+    pub fn get_approval_inbox(env: Env, budget_id: u64, member: Address) -> Vec<SpendRequest> {
+        let mut inbox = Vec::new(&env);
+        if !Self::is_budget_member(env.clone(), budget_id, member.clone()) {
+            return inbox;
+        }
+        for request in Self::get_pending_requests(env.clone(), budget_id).iter() {
+            if request.spender != member
+                && !request.approvals.contains(&member)
+                && !request.rejections.contains(&member)
+            {
+                inbox.push_back(request);
+            }
+        }
+        inbox
+    }
+
+    /// Returns the approval quorum and timeout of a budget.
+    pub fn get_approval_config(env: Env, budget_id: u64) -> ApprovalConfig {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ApprovalConfig(budget_id))
+            .unwrap_or(ApprovalConfig {
+                quorum: DEFAULT_APPROVAL_QUORUM,
+                timeout: DEFAULT_APPROVAL_TIMEOUT,
+            })
     }
 
     /// Add a member to an existing budget.
@@ -276,10 +506,8 @@ impl SharedBudgetContract {
             .get(&DataKey::Budget(budget_id))
             .unwrap_or_else(|| panic_with_error!(&env, SharedBudgetError::BudgetNotFound));
 
-        // Only creators or admins can add members
-        if caller != budget.creator {
-            Self::require_admin(&env, &caller);
-        }
+        // Only owners, managers or admins can add members
+        Self::require_manager(&env, &budget, &caller);
 
         // Check if member already exists
         let member_exists = env
@@ -293,12 +521,7 @@ impl SharedBudgetContract {
         }
 
         // Check member limit
-        let mut member_count = 0u32;
-        for member in budget.members.iter() {
-            member_count += 1;
-        }
-
-        if member_count >= MAX_BUDGET_MEMBERS {
+        if budget.members.len() >= MAX_BUDGET_MEMBERS {
             panic_with_error!(&env, SharedBudgetError::TooManyMembers);
         }
 
@@ -307,35 +530,336 @@ impl SharedBudgetContract {
 
         // Store updated budget
         env.storage()
-            .instance()
-            .set(&DataKey::TotalBatches, &(total_batches + 1));
-        env.storage().instance().set(
-            &DataKey::TotalAllocationsProcessed,
-            &(total_processed + request_count as u64),
-        );
-        env.storage().instance().set(
-            &DataKey::TotalAllocatedVolume,
-            &total_allocated
-                .checked_add(total_volume)
-                .unwrap_or(total_volume),
-        );
+            .persistent()
+            .set(&DataKey::Budget(budget_id), &budget);
+        env.storage()
+            .persistent()
+            .set(&DataKey::BudgetMember(budget_id, new_member.clone()), &true);
+
+        // Emit event
+        SharedBudgetEvents::member_added(&env, budget_id, &new_member);
+    }
+
+    /// Remove a member from a budget.
+    ///
+    /// Owners and admins can remove anyone except the owner; managers can
+    /// only remove spenders and viewers.
+    pub fn remove_member(env: Env, caller: Address, budget_id: u64, member: Address) {
+        caller.require_auth();
+
+        let mut budget = Self::get_budget(env.clone(), budget_id);
+        let caller_role = Self::require_manager(&env, &budget, &caller);
+
+        let role = Self::get_member_role(env.clone(), budget_id, member.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, SharedBudgetError::MemberNotFound));
+        if role == MemberRole::Owner {
+            panic_with_error!(&env, SharedBudgetError::CannotRemoveOwner);
+        }
+        if caller_role == Some(MemberRole::Manager) && role == MemberRole::Manager {
+            panic_with_error!(&env, SharedBudgetError::InsufficientRole);
+        }
+
+        Self::drop_member(&env, &mut budget, &member);
+
+        SharedBudgetEvents::member_removed(&env, budget_id, &member, &caller);
+    }
+
+    /// Leave a budget.
+    pub fn leave_budget(env: Env, member: Address, budget_id: u64) {
+        member.require_auth();
+
+        let mut budget = Self::get_budget(env.clone(), budget_id);
+        match Self::get_member_role(env.clone(), budget_id, member.clone()) {
+            None => panic_with_error!(&env, SharedBudgetError::MemberNotFound),
+            Some(MemberRole::Owner) => {
+                panic_with_error!(&env, SharedBudgetError::CannotRemoveOwner)
+            }
+            Some(_) => {}
+        }
+
+        Self::drop_member(&env, &mut budget, &member);
+
+        SharedBudgetEvents::member_removed(&env, budget_id, &member, &member);
+    }
+
+    /// Set a member's role.
+    ///
+    /// Owners and admins can assign the manager, spender and viewer roles;
+    /// managers can only move members between spender and viewer.
+    pub fn set_member_role(
+        env: Env,
+        caller: Address,
+        budget_id: u64,
+        member: Address,
+        role: MemberRole,
+    ) {
+        caller.require_auth();
+
+        let budget = Self::get_budget(env.clone(), budget_id);
+        let caller_role = Self::require_manager(&env, &budget, &caller);
+
+        let current = Self::get_member_role(env.clone(), budget_id, member.clone())
+            .unwrap_or_else(|| panic_with_error!(&env, SharedBudgetError::MemberNotFound));
+        if current == MemberRole::Owner || role == MemberRole::Owner {
+            panic_with_error!(&env, SharedBudgetError::CannotRemoveOwner);
+        }
+        if caller_role == Some(MemberRole::Manager)
+            && (current == MemberRole::Manager || role == MemberRole::Manager)
+        {
+            panic_with_error!(&env, SharedBudgetError::InsufficientRole);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::MemberRole(budget_id, member.clone()), &role);
+
+        SharedBudgetEvents::role_set(&env, budget_id, &member, role);
+    }
+
+    /// Set a member's spending allowance for a recurring period.
+    ///
+    /// Daily (86,400s) and weekly (604,800s) periods are fixed windows
+    /// aligned to the Unix epoch; monthly periods follow UTC calendar months.
+    /// Managers can only set allowances for spenders and viewers.
+    pub fn set_member_allowance(
+        env: Env,
+        caller: Address,
+        budget_id: u64,
+        member: Address,
+        period: AllowancePeriod,
+        limit: i128,
+    ) {
+        caller.require_auth();
+
+        let budget = Self::get_budget(env.clone(), budget_id);
+        let caller_role = Self::require_manager(&env, &budget, &caller);
+        Self::require_allowance_authority(&env, caller_role, budget_id, &member);
+        validate_amount(limit).unwrap_or_else(|_| {
+            panic_with_error!(&env, SharedBudgetError::InvalidAmount);
+        });
 
-        // Emit batch completed event
-        SharedBudgetEvents::batch_completed(
-            &env,
-            batch_id,
-            successful_count,
-            failed_count,
-            total_allocated,
+        // Keep usage in the current window when only the limit changes
+        let now = env.ledger().timestamp();
+        let spent = match Self::get_member_allowance(env.clone(), budget_id, member.clone()) {
+            Some(existing) if existing.period == period => existing.spent,
+            _ => 0,
+        };
+        let allowance = MemberAllowance {
+            period,
+            limit,
+            spent,
+            period_start: period.window_start(now),
+        };
+        env.storage().persistent().set(
+            &DataKey::MemberAllowance(budget_id, member.clone()),
+            &allowance,
         );
 
-        AllocationBatchResult {
-            total_requests: request_count,
-            successful: successful_count,
-            failed: failed_count,
-            total_allocated,
-            results,
+        SharedBudgetEvents::allowance_set(&env, budget_id, &member, period, limit);
+    }
+
+    /// Remove a member's spending allowance.
+    ///
+    /// Managers can only remove allowances of spenders and viewers.
+    pub fn remove_member_allowance(env: Env, caller: Address, budget_id: u64, member: Address) {
+        caller.require_auth();
+
+        let budget = Self::get_budget(env.clone(), budget_id);
+        let caller_role = Self::require_manager(&env, &budget, &caller);
+        Self::require_allowance_authority(&env, caller_role, budget_id, &member);
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::MemberAllowance(budget_id, member));
+    }
+
+    /// Get a member's role, or `None` if the address is not part of the budget.
+    ///
+    /// The creator is always the owner; other members default to spender.
+    pub fn get_member_role(env: Env, budget_id: u64, member: Address) -> Option<MemberRole> {
+        let budget: Budget = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Budget(budget_id))?;
+        if member == budget.creator {
+            return Some(MemberRole::Owner);
+        }
+        if !Self::is_budget_member(env.clone(), budget_id, member.clone()) {
+            return None;
+        }
+        Some(
+            env.storage()
+                .persistent()
+                .get(&DataKey::MemberRole(budget_id, member))
+                .unwrap_or(MemberRole::Spender),
+        )
+    }
+
+    /// Get a member's allowance, with usage reset if a new period has started.
+    pub fn get_member_allowance(
+        env: Env,
+        budget_id: u64,
+        member: Address,
+    ) -> Option<MemberAllowance> {
+        let mut allowance: MemberAllowance = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MemberAllowance(budget_id, member))?;
+        let window_start = allowance.period.window_start(env.ledger().timestamp());
+        if window_start > allowance.period_start {
+            allowance.period_start = window_start;
+            allowance.spent = 0;
+        }
+        Some(allowance)
+    }
+
+    /// Propose dissolving a budget.
+    ///
+    /// Only the creator can start a dissolution. It settles once the
+    /// budget's approval quorum of other members has ratified it, capped at
+    /// the number of such members; a budget with no other members settles
+    /// immediately.
+    pub fn dissolve_budget(env: Env, creator: Address, budget_id: u64) {
+        creator.require_auth();
+
+        let budget = Self::get_budget(env.clone(), budget_id);
+        if creator != budget.creator {
+            panic_with_error!(&env, SharedBudgetError::Unauthorized);
         }
+        if !budget.is_active {
+            panic_with_error!(&env, SharedBudgetError::BudgetNotActive);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Dissolution(budget_id))
+        {
+            panic_with_error!(&env, SharedBudgetError::DissolutionPending);
+        }
+
+        let config = Self::get_approval_config(env.clone(), budget_id);
+        let dissolution = Dissolution {
+            budget_id,
+            proposed_at: env.ledger().timestamp(),
+            ratifications: Vec::new(&env),
+            required: config
+                .quorum
+                .min(Self::eligible_approvers(&budget, &creator)),
+        };
+
+        SharedBudgetEvents::dissolution_proposed(&env, budget_id, dissolution.required);
+
+        if dissolution.required == 0 {
+            Self::settle_dissolution(&env, budget);
+        } else {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Dissolution(budget_id), &dissolution);
+        }
+    }
+
+    /// Ratify a pending dissolution. Settles the budget once quorum is reached.
+    pub fn ratify_dissolution(env: Env, member: Address, budget_id: u64) {
+        member.require_auth();
+
+        let budget = Self::get_budget(env.clone(), budget_id);
+        let mut dissolution = Self::get_dissolution(env.clone(), budget_id)
+            .unwrap_or_else(|| panic_with_error!(&env, SharedBudgetError::DissolutionNotFound));
+        if member == budget.creator
+            || !Self::is_budget_member(env.clone(), budget_id, member.clone())
+        {
+            panic_with_error!(&env, SharedBudgetError::MemberNotFound);
+        }
+        if dissolution.ratifications.contains(&member) {
+            panic_with_error!(&env, SharedBudgetError::AlreadyVoted);
+        }
+
+        dissolution.ratifications.push_back(member.clone());
+        SharedBudgetEvents::dissolution_ratified(&env, budget_id, &member);
+
+        if dissolution.ratifications.len() >= dissolution.required {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Dissolution(budget_id));
+            Self::settle_dissolution(&env, budget);
+        } else {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Dissolution(budget_id), &dissolution);
+        }
+    }
+
+    /// Withdraw a pending dissolution.
+    pub fn cancel_dissolution(env: Env, creator: Address, budget_id: u64) {
+        creator.require_auth();
+
+        let budget = Self::get_budget(env.clone(), budget_id);
+        if creator != budget.creator {
+            panic_with_error!(&env, SharedBudgetError::Unauthorized);
+        }
+        if Self::get_dissolution(env.clone(), budget_id).is_none() {
+            panic_with_error!(&env, SharedBudgetError::DissolutionNotFound);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Dissolution(budget_id));
+    }
+
+    /// Get the pending dissolution of a budget, if any.
+    pub fn get_dissolution(env: Env, budget_id: u64) -> Option<Dissolution> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Dissolution(budget_id))
+    }
+
+    /// Get the settlement report of a dissolved budget, if any.
+    pub fn get_dissolution_report(env: Env, budget_id: u64) -> Option<DissolutionReport> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DissolutionReport(budget_id))
+    }
+
+    /// Get every address that has contributed to a budget.
+    pub fn get_budget_contributors(env: Env, budget_id: u64) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Contributors(budget_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the total an address has contributed to a budget.
+    pub fn get_contributed_amount(env: Env, budget_id: u64, contributor: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ContributedBy(budget_id, contributor))
+            .unwrap_or(0)
+    }
+
+    /// Add a spending rule to an existing budget.
+    pub fn add_spending_rule(env: Env, caller: Address, budget_id: u64, rule: BudgetSpendingRule) {
+        caller.require_auth();
+
+        // Load budget
+        let mut budget: Budget = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Budget(budget_id))
+            .unwrap_or_else(|| panic_with_error!(&env, SharedBudgetError::BudgetNotFound));
+
+        // Only creators or admins can add rules
+        if caller != budget.creator {
+            Self::require_admin(&env, &caller);
+        }
+
+        // Check rule limit
+        if budget.spending_rules.len() >= MAX_SPENDING_RULES {
+            panic_with_error!(&env, SharedBudgetError::TooManyRules);
+        }
+
+        validate_percentage(rule.percentage_threshold).unwrap_or_else(|_| {
+            panic_with_error!(&env, SharedBudgetError::InvalidPercentage);
+        });
 
         // Emit event first before modifying the budget
         SharedBudgetEvents::spending_rule_added(&env, budget_id, &rule);
@@ -406,25 +930,320 @@ impl SharedBudgetContract {
             .unwrap_or(0)
     }
 
-    // Internal helper to enforce spending rules
-    fn enforce_spending_rules(env: &Env, budget: &Budget, spender: &Address, amount: i128) {
+    // Internal helper to enforce spending rules. Returns true if the spend
+    // exceeds a rule that requires approval.
+    fn enforce_spending_rules(env: &Env, budget: &Budget, spender: &Address, amount: i128) -> bool {
+        let mut needs_approval = false;
+
         // Check each spending rule to see if it applies
         for rule in budget.spending_rules.iter() {
             // If this rule applies to the spender and the amount exceeds threshold
             if rule.applicable_to == *spender {
                 // Check if rule applies to this specific spender
                 let threshold_amount = if budget.total_contributed > 0 {
-                    (budget.total_contributed as f64 * (rule.percentage_threshold as f64 / 100.0))
-                        as i128
+                    budget.total_contributed * rule.percentage_threshold as i128 / 100
                 } else {
                     0 // If no contributions yet, threshold is 0
                 };
 
-                if amount > threshold_amount && !rule.requires_approval {
-                    panic_with_error!(env, SharedBudgetError::Unauthorized);
+                if amount > threshold_amount {
+                    if !rule.requires_approval {
+                        panic_with_error!(env, SharedBudgetError::Unauthorized);
+                    }
+                    needs_approval = true;
                 }
             }
         }
+
+        needs_approval
+    }
+
+    // Internal helper to transfer funds out of a budget
+    fn execute_spend(
+        env: &Env,
+        budget: &mut Budget,
+        spender: &Address,
+        recipient: &Address,
+        amount: i128,
+    ) {
+        // Transfer tokens from contract to recipient
+        let token_client = token::Client::new(env, &budget.token);
+        token_client.transfer(&env.current_contract_address(), recipient, &amount);
+
+        // Update budget balance
+        budget.balance -= amount;
+
+        // Store updated budget
+        env.storage()
+            .persistent()
+            .set(&DataKey::Budget(budget.id), budget);
+
+        // Record allowance usage
+        if let Some(mut allowance) =
+            Self::get_member_allowance(env.clone(), budget.id, spender.clone())
+        {
+            allowance.spent += amount;
+            env.storage().persistent().set(
+                &DataKey::MemberAllowance(budget.id, spender.clone()),
+                &allowance,
+            );
+        }
+
+        // Emit event
+        SharedBudgetEvents::expense_incurred(env, budget.id, spender, recipient, amount);
+    }
+
+    // Internal helper to open a pending spend request
+    fn create_spend_request(
+        env: &Env,
+        budget_id: u64,
+        spender: Address,
+        recipient: Address,
+        amount: i128,
+    ) -> u64 {
+        let request_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TotalSpendRequests)
+            .unwrap_or(0)
+            + 1;
+        let config = Self::get_approval_config(env.clone(), budget_id);
+        let now = env.ledger().timestamp();
+
+        let request = SpendRequest {
+            id: request_id,
+            budget_id,
+            spender,
+            recipient,
+            amount,
+            approvals: Vec::new(env),
+            rejections: Vec::new(env),
+            status: SpendRequestStatus::Pending,
+            created_at: now,
+            expires_at: now + config.timeout,
+        };
+        Self::store_request(env, &request);
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalSpendRequests, &request_id);
+
+        let mut pending = Self::pending_request_ids(env, budget_id);
+        pending.push_back(request_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::PendingRequests(budget_id), &pending);
+
+        SharedBudgetEvents::spend_requested(env, &request);
+
+        request_id
+    }
+
+    // Internal helper to load a pending request a member is allowed to vote on
+    fn load_votable_request(env: &Env, voter: &Address, request_id: u64) -> SpendRequest {
+        let request = Self::get_spend_request(env.clone(), request_id);
+        if request.status != SpendRequestStatus::Pending {
+            panic_with_error!(env, SharedBudgetError::RequestNotPending);
+        }
+        if !Self::is_budget_member(env.clone(), request.budget_id, voter.clone()) {
+            panic_with_error!(env, SharedBudgetError::MemberNotFound);
+        }
+        if request.spender == *voter {
+            panic_with_error!(env, SharedBudgetError::CannotVoteOwnRequest);
+        }
+        if request.approvals.contains(voter) || request.rejections.contains(voter) {
+            panic_with_error!(env, SharedBudgetError::AlreadyVoted);
+        }
+        request
+    }
+
+    // Internal helper that marks a request expired if its timeout has passed
+    fn expire_if_due(env: &Env, request: &mut SpendRequest) -> bool {
+        if env.ledger().timestamp() < request.expires_at {
+            return false;
+        }
+        Self::finalize_request(env, request, SpendRequestStatus::Expired);
+        true
+    }
+
+    // Internal helper that closes a request and removes it from the pending index
+    fn finalize_request(env: &Env, request: &mut SpendRequest, status: SpendRequestStatus) {
+        request.status = status;
+        Self::store_request(env, request);
+
+        let mut pending = Self::pending_request_ids(env, request.budget_id);
+        if let Some(index) = pending.first_index_of(request.id) {
+            pending.remove(index);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::PendingRequests(request.budget_id), &pending);
+
+        SharedBudgetEvents::spend_request_closed(env, request);
+    }
+
+    // Internal helper to persist a spend request
+    fn store_request(env: &Env, request: &SpendRequest) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::SpendRequest(request.id), request);
+    }
+
+    // Internal helper returning the IDs of a budget's pending requests
+    fn pending_request_ids(env: &Env, budget_id: u64) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingRequests(budget_id))
+            .unwrap_or(Vec::new(env))
+    }
+
+    // Internal helper counting the members other than the spender who may vote
+    fn eligible_approvers(budget: &Budget, spender: &Address) -> u32 {
+        if budget.members.contains(spender) {
+            budget.members.len() - 1
+        } else {
+            budget.members.len()
+        }
+    }
+
+    // Internal helper that refunds the remaining balance pro rata to
+    // contributors, closes pending requests and deactivates the budget.
+    // Rounding dust goes to the last contributor so the balance reaches zero.
+    fn settle_dissolution(env: &Env, mut budget: Budget) {
+        let token_client = token::Client::new(env, &budget.token);
+        let contributors = Self::get_budget_contributors(env.clone(), budget.id);
+        let remaining = budget.balance;
+
+        let mut refunds = Vec::new(env);
+        let mut refunded: i128 = 0;
+        for (index, contributor) in contributors.iter().enumerate() {
+            let contributed =
+                Self::get_contributed_amount(env.clone(), budget.id, contributor.clone());
+            let amount = if index as u32 == contributors.len() - 1 {
+                remaining - refunded
+            } else {
+                remaining * contributed / budget.total_contributed
+            };
+            if amount > 0 {
+                token_client.transfer(&env.current_contract_address(), &contributor, &amount);
+            }
+            refunded += amount;
+            refunds.push_back(DissolutionRefund {
+                contributor,
+                contributed,
+                refunded: amount,
+            });
+        }
+
+        for request_id in Self::pending_request_ids(env, budget.id).iter() {
+            let mut request = Self::get_spend_request(env.clone(), request_id);
+            Self::finalize_request(env, &mut request, SpendRequestStatus::Rejected);
+        }
+
+        budget.balance -= refunded;
+        budget.is_active = false;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Budget(budget.id), &budget);
+
+        let report = DissolutionReport {
+            budget_id: budget.id,
+            total_contributed: budget.total_contributed,
+            total_spent: budget.total_contributed - remaining,
+            total_refunded: refunded,
+            refunds,
+            dissolved_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::DissolutionReport(budget.id), &report);
+
+        SharedBudgetEvents::budget_dissolved(env, &report);
+    }
+
+    // Internal helper requiring the caller to be the owner, a manager or the
+    // admin. Returns the caller's role in the budget, if any.
+    fn require_manager(env: &Env, budget: &Budget, caller: &Address) -> Option<MemberRole> {
+        let role = Self::get_member_role(env.clone(), budget.id, caller.clone());
+        match role {
+            Some(MemberRole::Owner) | Some(MemberRole::Manager) => role,
+            _ => {
+                Self::require_admin(env, caller);
+                role
+            }
+        }
+    }
+
+    // Internal helper that panics unless the member exists and the caller may
+    // manage their allowance. Managers may only act on spenders and viewers,
+    // which also keeps them from changing their own allowance.
+    fn require_allowance_authority(
+        env: &Env,
+        caller_role: Option<MemberRole>,
+        budget_id: u64,
+        member: &Address,
+    ) {
+        let role = Self::get_member_role(env.clone(), budget_id, member.clone())
+            .unwrap_or_else(|| panic_with_error!(env, SharedBudgetError::MemberNotFound));
+        if caller_role == Some(MemberRole::Manager)
+            && (role == MemberRole::Manager || role == MemberRole::Owner)
+        {
+            panic_with_error!(env, SharedBudgetError::InsufficientRole);
+        }
+    }
+
+    // Internal helper that panics if a spend would exceed the member's
+    // allowance for the current period
+    fn require_allowance(env: &Env, budget_id: u64, spender: &Address, amount: i128) {
+        if let Some(allowance) = Self::get_member_allowance(env.clone(), budget_id, spender.clone())
+        {
+            if allowance.spent + amount > allowance.limit {
+                panic_with_error!(env, SharedBudgetError::AllowanceExceeded);
+            }
+        }
+    }
+
+    // Internal helper that removes a member and their role and allowance,
+    // rejects the member's pending spend requests and withdraws their votes
+    // from the others. Requests that can no longer reach the quorum are
+    // rejected.
+    fn drop_member(env: &Env, budget: &mut Budget, member: &Address) {
+        if let Some(index) = budget.members.first_index_of(member) {
+            budget.members.remove(index);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Budget(budget.id), budget);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::BudgetMember(budget.id, member.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::MemberRole(budget.id, member.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::MemberAllowance(budget.id, member.clone()));
+
+        let config = Self::get_approval_config(env.clone(), budget.id);
+        for request_id in Self::pending_request_ids(env, budget.id).iter() {
+            let mut request = Self::get_spend_request(env.clone(), request_id);
+            if request.spender == *member {
+                Self::finalize_request(env, &mut request, SpendRequestStatus::Rejected);
+                continue;
+            }
+
+            if let Some(index) = request.approvals.first_index_of(member) {
+                request.approvals.remove(index);
+            }
+            if let Some(index) = request.rejections.first_index_of(member) {
+                request.rejections.remove(index);
+            }
+            let eligible = Self::eligible_approvers(budget, &request.spender);
+            if eligible.saturating_sub(request.rejections.len()) < config.quorum {
+                Self::finalize_request(env, &mut request, SpendRequestStatus::Rejected);
+            } else {
+                Self::store_request(env, &request);
+            }
+        }
     }
 
     // Internal helper to verify admin
@@ -3,8 +3,8 @@
 #![cfg(test)]
 
 use crate::{
-    Budget, BudgetContribution, BudgetSpendingRule, SharedBudgetContract,
-    SharedBudgetContractClient, SharedBudgetError,
+    AllowancePeriod, Budget, BudgetContribution, BudgetSpendingRule, MemberRole,
+    SharedBudgetContract, SharedBudgetContractClient, SharedBudgetError, SpendRequestStatus,
 };
 use soroban_sdk::{
     testutils::{Address as _, Events, Ledger},
@@ -41,6 +41,11 @@ fn setup_test_env() -> (
     (env, admin, token_id, token_client, client)
 }
 
+/// Mints `amount` of the budget token to `to` so it can contribute.
+fn fund(env: &Env, token: &Address, to: &Address, amount: i128) {
+    token::StellarAssetClient::new(env, token).mint(to, &amount);
+}
+
 // Initialization Tests
 
 #[test]
@@ -123,6 +128,7 @@ fn test_contribute_to_budget() {
 
     // Contribute to the budget
     let contribution_amount = 100_000_000; // 10 XLM
+    fund(&env, &token, &contributor, contribution_amount);
     client.contribute_to_budget(&contributor, &budget_id, &contribution_amount);
 
     // Check that budget balance increased
@@ -152,6 +158,7 @@ fn test_spend_from_budget() {
 
     // Contribute to budget first
     let contribution_amount = 100_000_000; // 10 XLM
+    fund(&env, &token, &member1, contribution_amount);
     client.contribute_to_budget(&member1, &budget_id, &contribution_amount);
 
     // Spend from budget
@@ -268,6 +275,7 @@ fn test_budget_stats_accumulate() {
 // Error Tests
 
 #[test]
+#[should_panic(expected = "Error(Contract, #8)")]
 fn test_spend_without_sufficient_funds() {
     let (env, admin, token, _token_client, client) = setup_test_env();
 
@@ -289,6 +297,7 @@ fn test_spend_without_sufficient_funds() {
 }
 
 #[test]
+#[should_panic(expected = "Error(Contract, #5)")]
 fn test_non_member_cannot_spend() {
     let (env, admin, token, _token_client, client) = setup_test_env();
 
@@ -307,6 +316,7 @@ fn test_non_member_cannot_spend() {
 
     // Contribute to budget first
     let contribution_amount = 100_000_000; // 10 XLM
+    fund(&env, &token, &member1, contribution_amount);
     client.contribute_to_budget(&member1, &budget_id, &contribution_amount);
 
     // Non-member tries to spend (should fail)
@@ -324,3 +334,614 @@ fn test_unauthorized_admin_function() {
 
     client.set_admin(&unauthorized_user, &new_admin);
 }
+
+// Approval Workflow Tests
+
+/// Creates a budget with three members whose first member must get approval
+/// for any spend above 10% of contributions, and funds it with 100 XLM.
+fn setup_approval_budget(
+    env: &Env,
+    token: &Address,
+    client: &SharedBudgetContractClient<'static>,
+) -> (u64, Address, Vec<Address>) {
+    let creator = Address::generate(env);
+    let members = Vec::from_array(
+        env,
+        [
+            Address::generate(env),
+            Address::generate(env),
+            Address::generate(env),
+        ],
+    );
+    let spending_rules = Vec::from_array(
+        env,
+        [BudgetSpendingRule {
+            applicable_to: members.get(0).unwrap(),
+            percentage_threshold: 10,
+            requires_approval: true,
+            description: Symbol::new(env, "large_purchases"),
+        }],
+    );
+    let budget_id = client.create_budget(
+        &creator,
+        &Symbol::new(env, "household"),
+        &members,
+        token,
+        &spending_rules,
+    );
+
+    token::StellarAssetClient::new(env, token).mint(&creator, &1_000_000_000);
+    client.contribute_to_budget(&creator, &budget_id, &1_000_000_000);
+
+    (budget_id, creator, members)
+}
+
+#[test]
+fn test_spend_within_rule_executes_immediately() {
+    let (env, _admin, token, token_client, client) = setup_test_env();
+    let (budget_id, _creator, members) = setup_approval_budget(&env, &token, &client);
+    let recipient = Address::generate(&env);
+
+    let result = client.spend_from_budget(
+        &members.get(0).unwrap(),
+        &budget_id,
+        &recipient,
+        &50_000_000,
+    );
+
+    assert_eq!(result, None);
+    assert_eq!(token_client.balance(&recipient), 50_000_000);
+}
+
+#[test]
+fn test_spend_over_rule_creates_pending_request() {
+    let (env, _admin, token, token_client, client) = setup_test_env();
+    let (budget_id, _creator, members) = setup_approval_budget(&env, &token, &client);
+    let spender = members.get(0).unwrap();
+    let recipient = Address::generate(&env);
+
+    let request_id = client
+        .spend_from_budget(&spender, &budget_id, &recipient, &200_000_000)
+        .unwrap();
+
+    let request = client.get_spend_request(&request_id);
+    assert_eq!(request.status, SpendRequestStatus::Pending);
+    assert_eq!(request.amount, 200_000_000);
+    assert_eq!(token_client.balance(&recipient), 0);
+    assert_eq!(client.get_budget(&budget_id).balance, 1_000_000_000);
+
+    assert_eq!(client.get_pending_requests(&budget_id).len(), 1);
+    assert_eq!(
+        client
+            .get_approval_inbox(&budget_id, &members.get(1).unwrap())
+            .len(),
+        1
+    );
+    assert_eq!(client.get_approval_inbox(&budget_id, &spender).len(), 0);
+}
+
+#[test]
+fn test_quorum_approval_releases_funds() {
+    let (env, _admin, token, token_client, client) = setup_test_env();
+    let (budget_id, creator, members) = setup_approval_budget(&env, &token, &client);
+    let recipient = Address::generate(&env);
+    client.set_approval_config(&creator, &budget_id, &2, &3_600);
+
+    let request_id = client
+        .spend_from_budget(
+            &members.get(0).unwrap(),
+            &budget_id,
+            &recipient,
+            &200_000_000,
+        )
+        .unwrap();
+
+    assert_eq!(
+        client.approve_spend_request(&members.get(1).unwrap(), &request_id),
+        SpendRequestStatus::Pending
+    );
+    assert_eq!(token_client.balance(&recipient), 0);
+
+    assert_eq!(
+        client.approve_spend_request(&members.get(2).unwrap(), &request_id),
+        SpendRequestStatus::Approved
+    );
+    assert_eq!(token_client.balance(&recipient), 200_000_000);
+    assert_eq!(client.get_budget(&budget_id).balance, 800_000_000);
+    assert_eq!(client.get_pending_requests(&budget_id).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_quorum_above_eligible_approvers_fails() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let (budget_id, creator, _members) = setup_approval_budget(&env, &token, &client);
+
+    // Three members leave only two approvers for any member's request
+    client.set_approval_config(&creator, &budget_id, &3, &3_600);
+}
+
+#[test]
+fn test_rejection_blocks_quorum() {
+    let (env, _admin, token, token_client, client) = setup_test_env();
+    let (budget_id, creator, members) = setup_approval_budget(&env, &token, &client);
+    let recipient = Address::generate(&env);
+    client.set_approval_config(&creator, &budget_id, &2, &3_600);
+
+    let request_id = client
+        .spend_from_budget(
+            &members.get(0).unwrap(),
+            &budget_id,
+            &recipient,
+            &200_000_000,
+        )
+        .unwrap();
+
+    // Two eligible approvers and a quorum of two: one rejection is final
+    assert_eq!(
+        client.reject_spend_request(&members.get(1).unwrap(), &request_id),
+        SpendRequestStatus::Rejected
+    );
+    assert_eq!(token_client.balance(&recipient), 0);
+    assert_eq!(client.get_pending_requests(&budget_id).len(), 0);
+}
+
+#[test]
+fn test_request_expires_after_timeout() {
+    let (env, _admin, token, token_client, client) = setup_test_env();
+    let (budget_id, _creator, members) = setup_approval_budget(&env, &token, &client);
+    let recipient = Address::generate(&env);
+
+    let request_id = client
+        .spend_from_budget(
+            &members.get(0).unwrap(),
+            &budget_id,
+            &recipient,
+            &200_000_000,
+        )
+        .unwrap();
+
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    assert_eq!(client.get_pending_requests(&budget_id).len(), 0);
+
+    // A late approval marks the request expired instead of paying out
+    assert_eq!(
+        client.approve_spend_request(&members.get(1).unwrap(), &request_id),
+        SpendRequestStatus::Expired
+    );
+    assert_eq!(token_client.balance(&recipient), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn test_spender_cannot_approve_own_request() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let (budget_id, _creator, members) = setup_approval_budget(&env, &token, &client);
+    let spender = members.get(0).unwrap();
+
+    let request_id = client
+        .spend_from_budget(&spender, &budget_id, &Address::generate(&env), &200_000_000)
+        .unwrap();
+    client.approve_spend_request(&spender, &request_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_expire_before_timeout_fails() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let (budget_id, _creator, members) = setup_approval_budget(&env, &token, &client);
+
+    let request_id = client
+        .spend_from_budget(
+            &members.get(0).unwrap(),
+            &budget_id,
+            &Address::generate(&env),
+            &200_000_000,
+        )
+        .unwrap();
+    client.expire_spend_request(&request_id);
+}
+
+// ========== Roles and Allowances Tests ==========
+
+#[test]
+fn test_member_roles_default_and_update() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let (budget_id, creator, members) = setup_approval_budget(&env, &token, &client);
+    let member = members.get(1).unwrap();
+
+    assert_eq!(
+        client.get_member_role(&budget_id, &creator),
+        Some(MemberRole::Owner)
+    );
+    assert_eq!(
+        client.get_member_role(&budget_id, &member),
+        Some(MemberRole::Spender)
+    );
+    assert_eq!(
+        client.get_member_role(&budget_id, &Address::generate(&env)),
+        None
+    );
+
+    client.set_member_role(&creator, &budget_id, &member, &MemberRole::Manager);
+    assert_eq!(
+        client.get_member_role(&budget_id, &member),
+        Some(MemberRole::Manager)
+    );
+
+    // Managers can add and demote regular members
+    let newcomer = Address::generate(&env);
+    client.add_member_to_budget(&member, &budget_id, &newcomer);
+    client.set_member_role(&member, &budget_id, &newcomer, &MemberRole::Viewer);
+    assert_eq!(
+        client.get_member_role(&budget_id, &newcomer),
+        Some(MemberRole::Viewer)
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #20)")]
+fn test_viewer_cannot_spend() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let (budget_id, creator, members) = setup_approval_budget(&env, &token, &client);
+    let viewer = members.get(1).unwrap();
+
+    client.set_member_role(&creator, &budget_id, &viewer, &MemberRole::Viewer);
+    client.spend_from_budget(&viewer, &budget_id, &Address::generate(&env), &1_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #20)")]
+fn test_manager_cannot_remove_manager() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let (budget_id, creator, members) = setup_approval_budget(&env, &token, &client);
+    let first = members.get(1).unwrap();
+    let second = members.get(2).unwrap();
+
+    client.set_member_role(&creator, &budget_id, &first, &MemberRole::Manager);
+    client.set_member_role(&creator, &budget_id, &second, &MemberRole::Manager);
+    client.remove_member(&first, &budget_id, &second);
+}
+
+#[test]
+fn test_manager_cannot_manage_manager_allowances() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let (budget_id, creator, members) = setup_approval_budget(&env, &token, &client);
+    let manager = members.get(1).unwrap();
+    let other_manager = members.get(2).unwrap();
+    let spender = members.get(0).unwrap();
+
+    client.set_member_role(&creator, &budget_id, &manager, &MemberRole::Manager);
+    client.set_member_role(&creator, &budget_id, &other_manager, &MemberRole::Manager);
+    client.set_member_allowance(
+        &creator,
+        &budget_id,
+        &manager,
+        &AllowancePeriod::Daily,
+        &1_000,
+    );
+
+    for target in [&manager, &other_manager, &creator] {
+        let result = client.try_set_member_allowance(
+            &manager,
+            &budget_id,
+            target,
+            &AllowancePeriod::Daily,
+            &1_000_000,
+        );
+        assert_eq!(result, Err(Ok(SharedBudgetError::InsufficientRole.into())));
+        let result = client.try_remove_member_allowance(&manager, &budget_id, target);
+        assert_eq!(result, Err(Ok(SharedBudgetError::InsufficientRole.into())));
+    }
+    assert_eq!(
+        client
+            .get_member_allowance(&budget_id, &manager)
+            .unwrap()
+            .limit,
+        1_000
+    );
+
+    // Spenders remain within a manager's reach
+    client.set_member_allowance(
+        &manager,
+        &budget_id,
+        &spender,
+        &AllowancePeriod::Daily,
+        &1_000,
+    );
+    client.remove_member_allowance(&manager, &budget_id, &spender);
+    assert_eq!(client.get_member_allowance(&budget_id, &spender), None);
+
+    let outsider = Address::generate(&env);
+    let result = client.try_remove_member_allowance(&creator, &budget_id, &outsider);
+    assert_eq!(result, Err(Ok(SharedBudgetError::MemberNotFound.into())));
+}
+
+#[test]
+fn test_remove_and_leave_budget() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let (budget_id, creator, members) = setup_approval_budget(&env, &token, &client);
+    let removed = members.get(1).unwrap();
+    let leaver = members.get(2).unwrap();
+
+    client.set_member_allowance(
+        &creator,
+        &budget_id,
+        &removed,
+        &AllowancePeriod::Daily,
+        &1_000,
+    );
+    client.remove_member(&creator, &budget_id, &removed);
+    client.leave_budget(&leaver, &budget_id);
+
+    assert!(!client.is_budget_member(&budget_id, &removed));
+    assert!(!client.is_budget_member(&budget_id, &leaver));
+    assert_eq!(client.get_member_role(&budget_id, &removed), None);
+    assert_eq!(client.get_member_allowance(&budget_id, &removed), None);
+    let budget = client.get_budget(&budget_id);
+    assert!(!budget.members.contains(&removed));
+    assert!(!budget.members.contains(&leaver));
+}
+
+#[test]
+fn test_removed_member_pending_requests_are_rejected() {
+    let (env, _admin, token, token_client, client) = setup_test_env();
+    let (budget_id, creator, members) = setup_approval_budget(&env, &token, &client);
+    let spender = members.get(0).unwrap();
+    let recipient = Address::generate(&env);
+
+    let request_id = client
+        .spend_from_budget(&spender, &budget_id, &recipient, &200_000_000)
+        .unwrap();
+    client.remove_member(&creator, &budget_id, &spender);
+
+    assert_eq!(
+        client.get_spend_request(&request_id).status,
+        SpendRequestStatus::Rejected
+    );
+    assert_eq!(client.get_pending_requests(&budget_id).len(), 0);
+
+    let result = client.try_approve_spend_request(&members.get(1).unwrap(), &request_id);
+    assert_eq!(result, Err(Ok(SharedBudgetError::RequestNotPending.into())));
+    assert_eq!(token_client.balance(&recipient), 0);
+}
+
+#[test]
+fn test_removed_member_votes_are_withdrawn() {
+    let (env, _admin, token, token_client, client) = setup_test_env();
+    let (budget_id, creator, members) = setup_approval_budget(&env, &token, &client);
+    let spender = members.get(0).unwrap();
+    let departed = members.get(1).unwrap();
+    let approver = members.get(2).unwrap();
+    let late_approver = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    client.add_member_to_budget(&creator, &budget_id, &late_approver);
+    client.set_approval_config(&creator, &budget_id, &2, &86_400);
+    let request_id = client
+        .spend_from_budget(&spender, &budget_id, &recipient, &200_000_000)
+        .unwrap();
+    client.approve_spend_request(&departed, &request_id);
+    client.remove_member(&creator, &budget_id, &departed);
+    assert_eq!(client.get_spend_request(&request_id).approvals.len(), 0);
+
+    // The departed member's approval no longer counts toward the quorum
+    let status = client.approve_spend_request(&approver, &request_id);
+    assert_eq!(status, SpendRequestStatus::Pending);
+    assert_eq!(token_client.balance(&recipient), 0);
+
+    let status = client.approve_spend_request(&late_approver, &request_id);
+    assert_eq!(status, SpendRequestStatus::Approved);
+    assert_eq!(token_client.balance(&recipient), 200_000_000);
+}
+
+#[test]
+fn test_request_rejected_when_quorum_unreachable_after_leave() {
+    let (env, _admin, token, token_client, client) = setup_test_env();
+    let (budget_id, creator, members) = setup_approval_budget(&env, &token, &client);
+    let spender = members.get(0).unwrap();
+    let leaver = members.get(1).unwrap();
+    let recipient = Address::generate(&env);
+
+    // Both other members must approve
+    client.set_approval_config(&creator, &budget_id, &2, &86_400);
+    let request_id = client
+        .spend_from_budget(&spender, &budget_id, &recipient, &200_000_000)
+        .unwrap();
+    client.approve_spend_request(&leaver, &request_id);
+    client.leave_budget(&leaver, &budget_id);
+
+    assert_eq!(
+        client.get_spend_request(&request_id).status,
+        SpendRequestStatus::Rejected
+    );
+    assert_eq!(client.get_pending_requests(&budget_id).len(), 0);
+    assert_eq!(token_client.balance(&recipient), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #21)")]
+fn test_owner_cannot_leave() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let (budget_id, creator, _members) = setup_approval_budget(&env, &token, &client);
+
+    client.leave_budget(&creator, &budget_id);
+}
+
+#[test]
+fn test_allowance_limits_spending_and_resets_each_period() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let (budget_id, creator, members) = setup_approval_budget(&env, &token, &client);
+    let spender = members.get(1).unwrap();
+    let recipient = Address::generate(&env);
+
+    client.set_member_allowance(
+        &creator,
+        &budget_id,
+        &spender,
+        &AllowancePeriod::Daily,
+        &50_000_000,
+    );
+    client.spend_from_budget(&spender, &budget_id, &recipient, &30_000_000);
+    assert_eq!(
+        client
+            .get_member_allowance(&budget_id, &spender)
+            .unwrap()
+            .spent,
+        30_000_000
+    );
+
+    let result = client.try_spend_from_budget(&spender, &budget_id, &recipient, &30_000_000);
+    assert_eq!(result, Err(Ok(SharedBudgetError::AllowanceExceeded.into())));
+
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    assert_eq!(
+        client
+            .get_member_allowance(&budget_id, &spender)
+            .unwrap()
+            .spent,
+        0
+    );
+    client.spend_from_budget(&spender, &budget_id, &recipient, &30_000_000);
+}
+
+#[test]
+fn test_monthly_allowance_resets_on_calendar_month() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let (budget_id, creator, members) = setup_approval_budget(&env, &token, &client);
+    let spender = members.get(1).unwrap();
+    let recipient = Address::generate(&env);
+
+    // 2024-01-31 12:00:00 UTC
+    env.ledger().with_mut(|li| li.timestamp = 1_706_702_400);
+    client.set_member_allowance(
+        &creator,
+        &budget_id,
+        &spender,
+        &AllowancePeriod::Monthly,
+        &50_000_000,
+    );
+    client.spend_from_budget(&spender, &budget_id, &recipient, &40_000_000);
+    let allowance = client.get_member_allowance(&budget_id, &spender).unwrap();
+    // 2024-01-01 00:00:00 UTC
+    assert_eq!(allowance.period_start, 1_704_067_200);
+
+    // 2024-02-01 00:00:00 UTC, one day later but a new month
+    env.ledger().with_mut(|li| li.timestamp = 1_706_745_600);
+    let allowance = client.get_member_allowance(&budget_id, &spender).unwrap();
+    assert_eq!(allowance.spent, 0);
+    assert_eq!(allowance.period_start, 1_706_745_600);
+    client.spend_from_budget(&spender, &budget_id, &recipient, &40_000_000);
+}
+
+#[test]
+fn test_allowance_rechecked_on_approval() {
+    let (env, _admin, token, token_client, client) = setup_test_env();
+    let (budget_id, creator, members) = setup_approval_budget(&env, &token, &client);
+    let spender = members.get(0).unwrap();
+    let approver = members.get(1).unwrap();
+    let recipient = Address::generate(&env);
+
+    client.set_member_allowance(
+        &creator,
+        &budget_id,
+        &spender,
+        &AllowancePeriod::Daily,
+        &300_000_000,
+    );
+
+    // Each request fits the allowance on its own, but not both together
+    let first = client
+        .spend_from_budget(&spender, &budget_id, &recipient, &200_000_000)
+        .unwrap();
+    let second = client
+        .spend_from_budget(&spender, &budget_id, &recipient, &200_000_000)
+        .unwrap();
+
+    client.approve_spend_request(&approver, &first);
+    let result = client.try_approve_spend_request(&approver, &second);
+    assert_eq!(result, Err(Ok(SharedBudgetError::AllowanceExceeded.into())));
+    assert_eq!(token_client.balance(&recipient), 200_000_000);
+    assert_eq!(
+        client
+            .get_member_allowance(&budget_id, &spender)
+            .unwrap()
+            .spent,
+        200_000_000
+    );
+}
+
+// ========== Dissolution Tests ==========
+
+#[test]
+fn test_dissolve_budget_refunds_pro_rata() {
+    let (env, _admin, token, token_client, client) = setup_test_env();
+    let (budget_id, creator, members) = setup_approval_budget(&env, &token, &client);
+    let member = members.get(1).unwrap();
+    token::StellarAssetClient::new(&env, &token).mint(&member, &500_000_000);
+    client.contribute_to_budget(&member, &budget_id, &500_000_000);
+
+    // Spend a third of the 1.5B pool, leaving 1B to refund 2:1
+    client.spend_from_budget(&member, &budget_id, &Address::generate(&env), &500_000_000);
+
+    client.dissolve_budget(&creator, &budget_id);
+    assert!(client.get_budget(&budget_id).is_active);
+    client.ratify_dissolution(&member, &budget_id);
+
+    let budget = client.get_budget(&budget_id);
+    assert!(!budget.is_active);
+    assert_eq!(budget.balance, 0);
+    assert!(client.get_dissolution(&budget_id).is_none());
+    assert_eq!(token_client.balance(&creator), 666_666_666);
+    assert_eq!(token_client.balance(&member), 333_333_334);
+
+    let report = client.get_dissolution_report(&budget_id).unwrap();
+    assert_eq!(report.total_contributed, 1_500_000_000);
+    assert_eq!(report.total_spent, 500_000_000);
+    assert_eq!(report.total_refunded, 1_000_000_000);
+    assert_eq!(report.refunds.len(), 2);
+    assert_eq!(report.refunds.get(1).unwrap().contributed, 500_000_000);
+}
+
+#[test]
+fn test_dissolution_rejects_pending_requests() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let (budget_id, creator, members) = setup_approval_budget(&env, &token, &client);
+
+    let request_id = client
+        .spend_from_budget(
+            &members.get(0).unwrap(),
+            &budget_id,
+            &Address::generate(&env),
+            &200_000_000,
+        )
+        .unwrap();
+    client.dissolve_budget(&creator, &budget_id);
+    client.ratify_dissolution(&members.get(1).unwrap(), &budget_id);
+
+    assert_eq!(
+        client.get_spend_request(&request_id).status,
+        SpendRequestStatus::Rejected
+    );
+    assert_eq!(client.get_pending_requests(&budget_id).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_only_creator_can_dissolve() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let (budget_id, _creator, members) = setup_approval_budget(&env, &token, &client);
+
+    client.dissolve_budget(&members.get(0).unwrap(), &budget_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #11)")]
+fn test_cannot_contribute_after_dissolution() {
+    let (env, _admin, token, _token_client, client) = setup_test_env();
+    let (budget_id, creator, members) = setup_approval_budget(&env, &token, &client);
+
+    client.dissolve_budget(&creator, &budget_id);
+    client.ratify_dissolution(&members.get(2).unwrap(), &budget_id);
+    client.contribute_to_budget(&creator, &budget_id, &1_000);
+}
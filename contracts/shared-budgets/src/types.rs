@@ -2,12 +2,20 @@
 
 use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
 
+use crate::calendar::{month_start, SECONDS_PER_DAY};
+
 /// Maximum number of members allowed in a budget.
 pub const MAX_BUDGET_MEMBERS: u32 = 20;
 
 /// Maximum number of spending rules allowed in a budget.
 pub const MAX_SPENDING_RULES: u32 = 10;
 
+/// Approvals needed for a spend request when no quorum is configured.
+pub const DEFAULT_APPROVAL_QUORUM: u32 = 1;
+
+/// Seconds a spend request stays open when no timeout is configured (1 day).
+pub const DEFAULT_APPROVAL_TIMEOUT: u64 = 86_400;
+
 /// Represents a shared budget with multiple members.
 #[derive(Clone, Debug)]
 #[contracttype]
@@ -62,6 +70,151 @@ pub struct BudgetSpendingRule {
     pub description: Symbol,
 }
 
+/// Approval settings for spend requests on a budget.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ApprovalConfig {
+    /// Number of approvals from other members needed to release funds
+    pub quorum: u32,
+    /// Seconds before a pending request expires
+    pub timeout: u64,
+}
+
+/// Lifecycle state of a spend request.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum SpendRequestStatus {
+    /// Waiting for votes
+    Pending,
+    /// Quorum reached and funds transferred
+    Approved,
+    /// Too many rejections to reach quorum
+    Rejected,
+    /// Timed out before reaching quorum
+    Expired,
+}
+
+/// A spend that exceeded a rule requiring approval.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct SpendRequest {
+    /// Unique identifier for the request
+    pub id: u64,
+    /// ID of the budget to spend from
+    pub budget_id: u64,
+    /// Member who requested the spend
+    pub spender: Address,
+    /// Address receiving the funds
+    pub recipient: Address,
+    /// Amount requested
+    pub amount: i128,
+    /// Members who approved
+    pub approvals: Vec<Address>,
+    /// Members who rejected
+    pub rejections: Vec<Address>,
+    /// Current status
+    pub status: SpendRequestStatus,
+    /// Timestamp when the request was created
+    pub created_at: u64,
+    /// Timestamp after which the request expires
+    pub expires_at: u64,
+}
+
+/// Role of a budget member.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum MemberRole {
+    /// Budget creator; manages everything and cannot be removed
+    Owner,
+    /// Manages members, roles and allowances, and can spend
+    Manager,
+    /// Can spend within rules and allowances
+    Spender,
+    /// Can contribute and view but not spend
+    Viewer,
+}
+
+/// Reset period of a member allowance.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum AllowancePeriod {
+    /// Resets every 86,400 seconds
+    Daily,
+    /// Resets every 604,800 seconds
+    Weekly,
+    /// Resets at UTC midnight on the first day of each calendar month
+    Monthly,
+}
+
+impl AllowancePeriod {
+    /// Start of the period window containing `timestamp`.
+    pub fn window_start(&self, timestamp: u64) -> u64 {
+        match self {
+            AllowancePeriod::Daily => timestamp - timestamp % SECONDS_PER_DAY,
+            AllowancePeriod::Weekly => timestamp - timestamp % (7 * SECONDS_PER_DAY),
+            AllowancePeriod::Monthly => month_start(timestamp),
+        }
+    }
+}
+
+/// A member's spending allowance and usage in the current period.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct MemberAllowance {
+    /// Reset period
+    pub period: AllowancePeriod,
+    /// Maximum spend per period
+    pub limit: i128,
+    /// Amount spent in the current period
+    pub spent: i128,
+    /// Timestamp when the current period started
+    pub period_start: u64,
+}
+
+/// A proposal to dissolve a budget, awaiting ratification by members.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct Dissolution {
+    /// Budget being dissolved
+    pub budget_id: u64,
+    /// Timestamp when the creator proposed the dissolution
+    pub proposed_at: u64,
+    /// Members who have ratified
+    pub ratifications: Vec<Address>,
+    /// Ratifications needed to settle
+    pub required: u32,
+}
+
+/// A contributor's line in a dissolution settlement report.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct DissolutionRefund {
+    /// Address that contributed
+    pub contributor: Address,
+    /// Total the address contributed over the budget's life
+    pub contributed: i128,
+    /// Amount refunded on dissolution
+    pub refunded: i128,
+}
+
+/// Settlement report recorded when a budget is dissolved.
+#[derive(Clone, Debug)]
+#[contracttype]
+pub struct DissolutionReport {
+    /// Dissolved budget
+    pub budget_id: u64,
+    /// Total contributed over the budget's life
+    pub total_contributed: i128,
+    /// Total spent before dissolution
+    pub total_spent: i128,
+    /// Total refunded to contributors
+    pub total_refunded: i128,
+    /// Per-contributor refunds
+    pub refunds: Vec<DissolutionRefund>,
+    /// Timestamp of the settlement
+    pub dissolved_at: u64,
+}
+
 /// Storage keys for contract state.
 #[derive(Clone)]
 #[contracttype]
@@ -78,6 +231,26 @@ pub enum DataKey {
     TotalBudgetsCreated,
     /// Total number of contributions processed
     TotalContributionsProcessed,
+    /// Spend request details by ID
+    SpendRequest(u64),
+    /// Total number of spend requests created
+    TotalSpendRequests,
+    /// IDs of a budget's pending spend requests
+    PendingRequests(u64),
+    /// Approval quorum and timeout for a budget
+    ApprovalConfig(u64),
+    /// Role of a member in a budget (defaults to spender)
+    MemberRole(u64, Address),
+    /// Periodic allowance of a member in a budget
+    MemberAllowance(u64, Address),
+    /// Addresses that have contributed to a budget
+    Contributors(u64),
+    /// Total contributed to a budget by an address
+    ContributedBy(u64, Address),
+    /// Pending dissolution of a budget
+    Dissolution(u64),
+    /// Settlement report of a dissolved budget
+    DissolutionReport(u64),
 }
 
 /// Events emitted by the shared budget contract.
@@ -105,6 +278,126 @@ impl SharedBudgetEvents {
         env.events().publish(topics, (contributor.clone(), amount));
     }
 
+    /// Event emitted when a member spends from a budget.
+    pub fn expense_incurred(
+        env: &Env,
+        budget_id: u64,
+        spender: &Address,
+        recipient: &Address,
+        amount: i128,
+    ) {
+        let topics = (symbol_short!("budget"), symbol_short!("expense"), budget_id);
+        env.events()
+            .publish(topics, (spender.clone(), recipient.clone(), amount));
+    }
+
+    /// Event emitted when a member is added to a budget.
+    pub fn member_added(env: &Env, budget_id: u64, member: &Address) {
+        let topics = (symbol_short!("budget"), symbol_short!("member"), budget_id);
+        env.events().publish(topics, member.clone());
+    }
+
+    /// Event emitted when a spend is held for approval.
+    pub fn spend_requested(env: &Env, request: &SpendRequest) {
+        let topics = (
+            symbol_short!("budget"),
+            symbol_short!("req"),
+            request.budget_id,
+        );
+        env.events().publish(
+            topics,
+            (
+                request.id,
+                request.spender.clone(),
+                request.recipient.clone(),
+                request.amount,
+                request.expires_at,
+            ),
+        );
+    }
+
+    /// Event emitted when a member approves a spend request.
+    pub fn spend_approved(env: &Env, request: &SpendRequest, approver: &Address) {
+        let topics = (
+            symbol_short!("budget"),
+            symbol_short!("approve"),
+            request.budget_id,
+        );
+        env.events().publish(topics, (request.id, approver.clone()));
+    }
+
+    /// Event emitted when a member rejects a spend request.
+    pub fn spend_rejected(env: &Env, request: &SpendRequest, rejecter: &Address) {
+        let topics = (
+            symbol_short!("budget"),
+            symbol_short!("reject"),
+            request.budget_id,
+        );
+        env.events().publish(topics, (request.id, rejecter.clone()));
+    }
+
+    /// Event emitted when a spend request leaves the pending state.
+    pub fn spend_request_closed(env: &Env, request: &SpendRequest) {
+        let topics = (
+            symbol_short!("budget"),
+            symbol_short!("req_done"),
+            request.budget_id,
+        );
+        env.events().publish(topics, (request.id, request.status));
+    }
+
+    /// Event emitted when the creator proposes dissolving a budget.
+    pub fn dissolution_proposed(env: &Env, budget_id: u64, required: u32) {
+        let topics = (
+            symbol_short!("budget"),
+            symbol_short!("dissolve"),
+            budget_id,
+        );
+        env.events().publish(topics, required);
+    }
+
+    /// Event emitted when a member ratifies a dissolution.
+    pub fn dissolution_ratified(env: &Env, budget_id: u64, member: &Address) {
+        let topics = (symbol_short!("budget"), symbol_short!("ratify"), budget_id);
+        env.events().publish(topics, member.clone());
+    }
+
+    /// Event emitted with the settlement report when a budget is dissolved.
+    pub fn budget_dissolved(env: &Env, report: &DissolutionReport) {
+        let topics = (
+            symbol_short!("budget"),
+            symbol_short!("dissolved"),
+            report.budget_id,
+        );
+        env.events().publish(topics, report.clone());
+    }
+
+    /// Event emitted when a member is removed from or leaves a budget.
+    pub fn member_removed(env: &Env, budget_id: u64, member: &Address, removed_by: &Address) {
+        let topics = (symbol_short!("budget"), symbol_short!("removed"), budget_id);
+        env.events()
+            .publish(topics, (member.clone(), removed_by.clone()));
+    }
+
+    /// Event emitted when a member's role changes.
+    pub fn role_set(env: &Env, budget_id: u64, member: &Address, role: MemberRole) {
+        let topics = (symbol_short!("budget"), symbol_short!("role"), budget_id);
+        env.events().publish(topics, (member.clone(), role));
+    }
+
+    /// Event emitted when a member's allowance is set.
+    pub fn allowance_set(
+        env: &Env,
+        budget_id: u64,
+        member: &Address,
+        period: AllowancePeriod,
+        limit: i128,
+    ) {
+        let topics = (symbol_short!("budget"), symbol_short!("allow"), budget_id);
+        env.events()
+            .publish(topics, (member.clone(), period, limit));
+    }
+
     /// Event emitted when an allocation fails for a recipient.
     pub fn allocation_failure(
         env: &Env,
@@ -0,0 +1,196 @@
+//! Calendar arithmetic for spending limit periods.
+//!
+//! All dates are UTC and derived from the ledger timestamp. Civil date
+//! conversion uses the proleptic Gregorian calendar, so month lengths and
+//! leap years match real calendar boundaries.
+
+use crate::types::LimitPeriod;
+
+/// Seconds in a UTC day.
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Days in a week.
+pub const DAYS_PER_WEEK: u64 = 7;
+
+/// Days between 0000-03-01 and 1970-01-01 in the proleptic Gregorian calendar.
+const EPOCH_SHIFT: i64 = 719_468;
+
+/// Days in a 400-year Gregorian era.
+const DAYS_PER_ERA: i64 = 146_097;
+
+/// Converts days since 1970-01-01 into a `(year, month, day)` civil date.
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + EPOCH_SHIFT;
+    let era = z.div_euclid(DAYS_PER_ERA);
+    let doe = z.rem_euclid(DAYS_PER_ERA);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Converts a civil date into days since 1970-01-01.
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * DAYS_PER_ERA + doe - EPOCH_SHIFT
+}
+
+/// Returns `true` if `year` is a Gregorian leap year.
+pub fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Returns the number of days in the given month.
+pub fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the weekday of a day since 1970-01-01, with Monday as 0.
+pub fn weekday(days: i64) -> u32 {
+    // 1970-01-01 was a Thursday
+    (days + 3).rem_euclid(DAYS_PER_WEEK as i64) as u32
+}
+
+/// Returns the `[start, end)` timestamps of the period containing `now`.
+///
+/// Daily, weekly and monthly periods start at UTC midnight. A monthly
+/// start day past the end of a short month falls on that month's last day.
+/// Custom periods are aligned to multiples of their length since the epoch.
+pub fn period_bounds(period: &LimitPeriod, now: u64) -> (u64, u64) {
+    let today = (now / SECONDS_PER_DAY) as i64;
+    let (start, end) = match period {
+        LimitPeriod::Daily => (today, today + 1),
+        LimitPeriod::Weekly(start_weekday) => {
+            let offset = (weekday(today) + 7 - start_weekday) % 7;
+            let start = today - offset as i64;
+            (start, start + DAYS_PER_WEEK as i64)
+        }
+        LimitPeriod::CalendarMonth(start_day) => {
+            let (year, month, _) = civil_from_days(today);
+            let this_month = month_anchor(year, month, *start_day);
+            if today >= this_month {
+                let (next_year, next_month) = add_months(year, month, 1);
+                (this_month, month_anchor(next_year, next_month, *start_day))
+            } else {
+                let (prev_year, prev_month) = add_months(year, month, -1);
+                (month_anchor(prev_year, prev_month, *start_day), this_month)
+            }
+        }
+        LimitPeriod::Custom(seconds) => {
+            let start = now - now % seconds;
+            return (start, start + seconds);
+        }
+    };
+    (
+        start.max(0) as u64 * SECONDS_PER_DAY,
+        end.max(0) as u64 * SECONDS_PER_DAY,
+    )
+}
+
+/// Day since the epoch on which a monthly period starts in the given month.
+fn month_anchor(year: i64, month: u32, start_day: u32) -> i64 {
+    days_from_civil(year, month, start_day.min(days_in_month(year, month)))
+}
+
+/// Shifts a `(year, month)` pair by `delta` months.
+fn add_months(year: i64, month: u32, delta: i64) -> (i64, u32) {
+    let index = year * 12 + (month as i64 - 1) + delta;
+    (index.div_euclid(12), (index.rem_euclid(12) + 1) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2024-01-15 00:00:00 UTC, a Monday.
+    const JAN_15_2024: u64 = 1_705_276_800;
+
+    #[test]
+    fn test_civil_round_trip() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+        assert_eq!(days_from_civil(2024, 2, 29), 19_782);
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        for days in [10_956i64, 11_016, 18_321, 47_541] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+    }
+
+    #[test]
+    fn test_days_in_month() {
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(2023, 2), 28);
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(2000, 2), 29);
+        assert_eq!(days_in_month(2024, 4), 30);
+        assert_eq!(days_in_month(2024, 12), 31);
+    }
+
+    #[test]
+    fn test_weekday() {
+        assert_eq!(weekday(0), 3);
+        assert_eq!(weekday((JAN_15_2024 / SECONDS_PER_DAY) as i64), 0);
+    }
+
+    #[test]
+    fn test_daily_and_weekly_bounds() {
+        let now = JAN_15_2024 + 3_600;
+        assert_eq!(
+            period_bounds(&LimitPeriod::Daily, now),
+            (JAN_15_2024, JAN_15_2024 + SECONDS_PER_DAY)
+        );
+        // Week starting Monday begins today
+        assert_eq!(
+            period_bounds(&LimitPeriod::Weekly(0), now),
+            (JAN_15_2024, JAN_15_2024 + 7 * SECONDS_PER_DAY)
+        );
+        // Week starting Friday began on 2024-01-12
+        assert_eq!(
+            period_bounds(&LimitPeriod::Weekly(4), now).0,
+            JAN_15_2024 - 3 * SECONDS_PER_DAY
+        );
+    }
+
+    #[test]
+    fn test_calendar_month_bounds() {
+        let day = |y, m, d| days_from_civil(y, m, d) as u64 * SECONDS_PER_DAY;
+
+        assert_eq!(
+            period_bounds(&LimitPeriod::CalendarMonth(1), JAN_15_2024),
+            (day(2024, 1, 1), day(2024, 2, 1))
+        );
+        // Payday on the 25th: mid-January falls in the period from 25 December
+        assert_eq!(
+            period_bounds(&LimitPeriod::CalendarMonth(25), JAN_15_2024),
+            (day(2023, 12, 25), day(2024, 1, 25))
+        );
+        // Start day 31 clamps to the end of February in a leap year
+        assert_eq!(
+            period_bounds(&LimitPeriod::CalendarMonth(31), day(2024, 3, 1)),
+            (day(2024, 2, 29), day(2024, 3, 31))
+        );
+    }
+
+    #[test]
+    fn test_custom_bounds() {
+        assert_eq!(
+            period_bounds(&LimitPeriod::Custom(3_600), 7_300),
+            (7_200, 10_800)
+        );
+    }
+}
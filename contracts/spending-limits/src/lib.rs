@@ -11,6 +11,15 @@
 //! - **Error Handling**: Gracefully handles invalid inputs with detailed error codes
 //! - **Optimized Storage**: Minimizes storage writes by batching operations
 //! - **Partial Failure Support**: Invalid updates don't affect valid ones
+//! - **Category Limits**: One overall limit plus per-category limits per user,
+//!   all checked on every spend
+//! - **Warning Thresholds**: One-time events as spending crosses configured
+//!   percentages of a limit, and an optional soft mode that records overruns
+//!   instead of rejecting spends
+//! - **Rollover**: Unused headroom carries into the next period, capped and
+//!   optionally decayed
+//! - **Configurable Periods**: Daily, weekly, calendar-month or custom limit
+//!   periods with an optional daily cap, reset on true UTC boundaries
 //!
 //! ## Optimization Strategies
 //!
@@ -21,14 +30,17 @@
 
 #![no_std]
 
+mod calendar;
 mod types;
 mod validation;
 
-use soroban_sdk::{contract, contractimpl, panic_with_error, Address, Env, Vec};
+use soroban_sdk::{contract, contractimpl, panic_with_error, Address, Env, Symbol, Vec};
 
+use crate::calendar::{period_bounds, SECONDS_PER_DAY};
 pub use crate::types::{
-    BatchLimitMetrics, BatchLimitResult, DataKey, ErrorCode, LimitEvents, LimitUpdateResult,
-    SpendingLimit, SpendingLimitRequest, MAX_BATCH_SIZE,
+    BatchLimitMetrics, BatchLimitResult, DataKey, ErrorCode, LimitEvents, LimitPeriod,
+    LimitUpdateResult, SpendingLimit, SpendingLimitRequest, MAX_BATCH_SIZE,
+    MAX_CATEGORIES_PER_USER,
 };
 use crate::validation::validate_limit_request;
 
@@ -48,8 +60,8 @@ pub enum SpendingLimitError {
     BatchTooLarge = 5,
     /// Daily limit exceeded
     DailyLimitExceeded = 6,
-    /// Monthly limit exceeded
-    MonthlyLimitExceeded = 7,
+    /// Period limit exceeded
+    PeriodLimitExceeded = 7,
     /// Invalid spend amount
     InvalidAmount = 8,
 }
@@ -60,6 +72,17 @@ impl From<SpendingLimitError> for soroban_sdk::Error {
     }
 }
 
+/// Spending totals a spend would produce against one limit.
+struct LimitUsage {
+    limit: SpendingLimit,
+    daily_key: DataKey,
+    period_key: DataKey,
+    current_period: i128,
+    new_daily: i128,
+    new_period: i128,
+    overrun: i128,
+}
+
 #[contract]
 pub struct SpendingLimitsContract;
 
@@ -85,11 +108,14 @@ impl SpendingLimitsContract {
             .set(&DataKey::TotalBatchesProcessed, &0u64);
     }
 
-    /// Updates monthly spending limits for multiple users in a batch.
+    /// Updates spending limits for multiple users in a batch.
     ///
     /// This is the main entry point for batch limit updates. It validates all requests,
     /// updates limits, emits events, and handles partial failures gracefully.
     ///
+    /// A request without a category upserts the user's overall limit; a request
+    /// with a category upserts that category's limit alongside any others.
+    ///
     /// # Arguments
     /// * `env` - The contract environment
     /// * `caller` - The address calling this function (must be admin)
@@ -140,6 +166,7 @@ impl SpendingLimitsContract {
 
         // Get current ledger timestamp
         let current_ledger = env.ledger().sequence() as u64;
+        let now = env.ledger().timestamp();
 
         // Initialize result tracking
         let mut results: Vec<LimitUpdateResult> = Vec::new(&env);
@@ -150,39 +177,53 @@ impl SpendingLimitsContract {
         // Process each request
         for request in requests.iter() {
             // Validate the request
-            match validate_limit_request(&request) {
+            let validation =
+                validate_limit_request(&request).and_then(|()| match &request.category {
+                    Some(category) => Self::index_category(&env, &request.user, category),
+                    None => Ok(()),
+                });
+            match validation {
                 Ok(()) => {
                     // Validation succeeded - update the limit
                     let limit = SpendingLimit {
                         user: request.user.clone(),
-                        monthly_limit: request.monthly_limit,
+                        period_limit: request.period_limit,
                         current_spending: 0, // Reset spending when updating limit
                         category: request.category.clone(),
+                        period: request.period.clone(),
+                        daily_cap: request.daily_cap,
+                        period_start: period_bounds(&request.period, now).0,
+                        warning_thresholds: request.warning_thresholds.clone(),
+                        soft_limit: request.soft_limit,
+                        current_overrun: 0,
+                        max_carry_over: request.max_carry_over,
+                        carry_decay_pct: request.carry_decay_pct,
+                        carry_over: 0,
                         updated_at: current_ledger,
                         is_active: true,
                     };
 
                     // Accumulate metrics
                     total_limits_value = total_limits_value
-                        .checked_add(request.monthly_limit)
+                        .checked_add(request.period_limit)
                         .unwrap_or(i128::MAX);
                     successful_count += 1;
 
                     // Store the limit (optimized - one write per limit)
                     env.storage()
                         .persistent()
-                        .set(&DataKey::SpendingLimit(request.user.clone()), &limit);
+                        .set(&Self::limit_key(&request.user, &request.category), &limit);
 
                     // Emit success event
                     LimitEvents::limit_updated(&env, batch_id, &limit);
 
                     // Emit high-value limit event if applicable (>= 1,000,000 XLM)
-                    if request.monthly_limit >= 10_000_000_000_000_000 {
+                    if request.period_limit >= 10_000_000_000_000_000 {
                         LimitEvents::high_value_limit(
                             &env,
                             batch_id,
                             &request.user,
-                            request.monthly_limit,
+                            request.period_limit,
                         );
                     }
 
@@ -259,117 +300,47 @@ impl SpendingLimitsContract {
         }
     }
 
-    /// Enforces the configured daily and monthly spending limits for a user.
+    /// Enforces the configured period limits and daily caps for a user.
     ///
     /// This function:
-    /// - Tracks per-user daily and monthly totals using the current ledger timestamp.
-    /// - Rejects spends that would exceed either the derived daily limit or the stored
-    ///   monthly limit.
-    /// - Emits a `limit_exceeded` event when a violation occurs.
+    /// - Checks the spend against the category's limit (if `category` is set and
+    ///   has a limit) and against the user's overall limit. Both must pass before
+    ///   either total is updated.
+    /// - Tracks per-limit totals for the current UTC day and the current limit
+    ///   period, both derived from the ledger timestamp.
+    /// - Emits a `limit_exceeded` event when a violation occurs. Soft limits
+    ///   allow the spend instead, record the excess as an overrun and emit
+    ///   `limit_overrun`.
+    /// - Emits a `threshold_reached` event for each warning threshold the
+    ///   spend crosses in the current period.
     ///
-    /// If no limit is configured for the user or the limit is inactive, the spend is
-    /// allowed and no state is updated.
-    pub fn enforce_spending_limit(env: Env, user: Address, amount: i128) {
+    /// Limits that are not configured or are inactive are skipped.
+    pub fn enforce_spending_limit(env: Env, user: Address, category: Option<Symbol>, amount: i128) {
         // Validate amount
         if amount <= 0 {
             panic_with_error!(&env, SpendingLimitError::InvalidAmount);
         }
 
-        // Look up configured limit; if none, there is nothing to enforce.
-        let mut limit: SpendingLimit = match env
-            .storage()
-            .persistent()
-            .get(&DataKey::SpendingLimit(user.clone()))
-        {
-            Some(l) => l,
-            None => return,
-        };
-
-        if !limit.is_active {
-            return;
-        }
-
         let now = env.ledger().timestamp();
 
-        // Derive simple logical day/month identifiers from timestamp.
-        const SECONDS_PER_DAY: u64 = 86_400;
-        const SECONDS_PER_MONTH: u64 = SECONDS_PER_DAY * 30;
-
-        let day_id = now / SECONDS_PER_DAY;
-        let month_id = now / SECONDS_PER_MONTH;
-
-        // Load current daily and monthly totals.
-        let daily_key = DataKey::DailySpending(user.clone(), day_id);
-        let monthly_key = DataKey::MonthlySpending(user.clone(), month_id);
+        // Check every applicable limit before recording anything.
+        let category_usage = category
+            .as_ref()
+            .and_then(|_| Self::active_limit(&env, &user, &category))
+            .map(|limit| Self::check_limit(&env, limit, amount, now));
+        let overall_usage = Self::active_limit(&env, &user, &None)
+            .map(|limit| Self::check_limit(&env, limit, amount, now));
 
-        let current_daily: i128 = env.storage().persistent().get(&daily_key).unwrap_or(0);
-        let current_monthly: i128 = env.storage().persistent().get(&monthly_key).unwrap_or(0);
-
-        let new_daily = current_daily
-            .checked_add(amount)
-            .unwrap_or_else(|| panic_with_error!(&env, SpendingLimitError::InvalidBatch));
-        let new_monthly = current_monthly
-            .checked_add(amount)
-            .unwrap_or_else(|| panic_with_error!(&env, SpendingLimitError::InvalidBatch));
-
-        // Derive a daily limit from the monthly limit (simple 30-day split).
-        let daily_limit = if limit.monthly_limit <= 0 {
-            0
-        } else {
-            let base = limit.monthly_limit / 30;
-            if base == 0 {
-                1
-            } else {
-                base
-            }
-        };
-
-        let mut daily_ok = true;
-        let mut monthly_ok = true;
-
-        if new_daily > daily_limit {
-            daily_ok = false;
-        }
-        if new_monthly > limit.monthly_limit {
-            monthly_ok = false;
+        for usage in [category_usage, overall_usage].into_iter().flatten() {
+            Self::record_usage(&env, usage);
         }
-
-        if !daily_ok || !monthly_ok {
-            let remaining_daily = if current_daily >= daily_limit {
-                0
-            } else {
-                daily_limit - current_daily
-            };
-            let remaining_monthly = if current_monthly >= limit.monthly_limit {
-                0
-            } else {
-                limit.monthly_limit - current_monthly
-            };
-
-            LimitEvents::limit_exceeded(&env, &user, amount, remaining_daily, remaining_monthly);
-
-            if !daily_ok {
-                panic_with_error!(&env, SpendingLimitError::DailyLimitExceeded);
-            } else {
-                panic_with_error!(&env, SpendingLimitError::MonthlyLimitExceeded);
-            }
-        }
-
-        // Persist updated totals.
-        env.storage().persistent().set(&daily_key, &new_daily);
-        env.storage().persistent().set(&monthly_key, &new_monthly);
-
-        // Keep the embedded "current_spending" and "updated_at" in sync with the
-        // current logical month usage.
-        limit.current_spending = new_monthly;
-        limit.updated_at = month_id;
-        env.storage()
-            .persistent()
-            .set(&DataKey::SpendingLimit(user), &limit);
     }
 
     /// Retrieves a user's spending limit.
     ///
+    /// The limit reflects the period containing the current ledger
+    /// timestamp, including any carry-over from the previous period.
+    ///
     /// # Arguments
     /// * `env` - The contract environment
     /// * `user` - The user's address
@@ -380,6 +351,29 @@ impl SpendingLimitsContract {
         env.storage()
             .persistent()
             .get(&DataKey::SpendingLimit(user))
+            .map(|limit| Self::roll_period(&env, limit, env.ledger().timestamp()))
+    }
+
+    /// Retrieves a user's spending limit for a category, as of the current period.
+    pub fn get_category_limit(env: Env, user: Address, category: Symbol) -> Option<SpendingLimit> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CategoryLimit(user, category))
+            .map(|limit| Self::roll_period(&env, limit, env.ledger().timestamp()))
+    }
+
+    /// Lists all of a user's spending limits, overall limit first.
+    pub fn get_spending_limits(env: Env, user: Address) -> Vec<SpendingLimit> {
+        let mut limits = Vec::new(&env);
+        if let Some(limit) = Self::get_spending_limit(env.clone(), user.clone()) {
+            limits.push_back(limit);
+        }
+        for category in Self::limit_categories(&env, &user).iter() {
+            if let Some(limit) = Self::get_category_limit(env.clone(), user.clone(), category) {
+                limits.push_back(limit);
+            }
+        }
+        limits
     }
 
     /// Returns the admin address.
@@ -422,6 +416,224 @@ impl SpendingLimitsContract {
             .unwrap_or(0)
     }
 
+    // Internal helper returning the storage key of a user's overall or category limit
+    fn limit_key(user: &Address, category: &Option<Symbol>) -> DataKey {
+        match category {
+            Some(category) => DataKey::CategoryLimit(user.clone(), category.clone()),
+            None => DataKey::SpendingLimit(user.clone()),
+        }
+    }
+
+    // Internal helper loading a limit if it exists and is active
+    fn active_limit(env: &Env, user: &Address, category: &Option<Symbol>) -> Option<SpendingLimit> {
+        env.storage()
+            .persistent()
+            .get::<_, SpendingLimit>(&Self::limit_key(user, category))
+            .filter(|limit| limit.is_active)
+    }
+
+    // Internal helper returning the categories a user has limits for
+    fn limit_categories(env: &Env, user: &Address) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LimitCategories(user.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    // Internal helper adding a category to a user's index, enforcing the cap
+    fn index_category(env: &Env, user: &Address, category: &Symbol) -> Result<(), u32> {
+        let mut categories = Self::limit_categories(env, user);
+        if categories.contains(category) {
+            return Ok(());
+        }
+        if categories.len() >= MAX_CATEGORIES_PER_USER {
+            return Err(ErrorCode::TOO_MANY_CATEGORIES);
+        }
+        categories.push_back(category.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::LimitCategories(user.clone()), &categories);
+        Ok(())
+    }
+
+    // Internal helper returning the daily spending key of a limit
+    fn daily_key(limit: &SpendingLimit, day_id: u64) -> DataKey {
+        match &limit.category {
+            Some(category) => {
+                DataKey::CategoryDailySpending(limit.user.clone(), category.clone(), day_id)
+            }
+            None => DataKey::DailySpending(limit.user.clone(), day_id),
+        }
+    }
+
+    // Internal helper returning the period spending key of a limit
+    fn period_key(limit: &SpendingLimit, period_start: u64) -> DataKey {
+        match &limit.category {
+            Some(category) => {
+                DataKey::CategoryPeriodSpending(limit.user.clone(), category.clone(), period_start)
+            }
+            None => DataKey::PeriodSpending(limit.user.clone(), period_start),
+        }
+    }
+
+    // Internal helper returning the carry-over key of a limit
+    fn carry_over_key(limit: &SpendingLimit, period_start: u64) -> DataKey {
+        match &limit.category {
+            Some(category) => {
+                DataKey::CategoryCarryOver(limit.user.clone(), category.clone(), period_start)
+            }
+            None => DataKey::CarryOver(limit.user.clone(), period_start),
+        }
+    }
+
+    // Internal helper moving a limit into the period containing `now`.
+    //
+    // Unused headroom rolls forward only from the immediately preceding
+    // period; a period with no recorded spending carries its base limit.
+    fn roll_period(env: &Env, mut limit: SpendingLimit, now: u64) -> SpendingLimit {
+        let (period_start, _) = period_bounds(&limit.period, now);
+        if period_start == limit.period_start {
+            return limit;
+        }
+
+        let carry_over = match env
+            .storage()
+            .persistent()
+            .get(&Self::carry_over_key(&limit, period_start))
+        {
+            Some(recorded) => recorded,
+            None if limit.max_carry_over == 0 => 0,
+            None => {
+                let (_, previous_end) = period_bounds(&limit.period, limit.period_start);
+                let unused = if previous_end == period_start {
+                    let spent: i128 = env
+                        .storage()
+                        .persistent()
+                        .get(&Self::period_key(&limit, limit.period_start))
+                        .unwrap_or(0);
+                    (limit.period_limit + limit.carry_over - spent).max(0)
+                } else {
+                    limit.period_limit
+                };
+                let retained = unused * (100 - limit.carry_decay_pct) as i128 / 100;
+                retained.min(limit.max_carry_over)
+            }
+        };
+
+        limit.period_start = period_start;
+        limit.carry_over = carry_over;
+        limit.current_spending = 0;
+        limit.current_overrun = 0;
+        limit
+    }
+
+    // Internal helper computing the totals a spend would produce against a
+    // limit. Emits `limit_exceeded` and panics if the spend is not allowed.
+    fn check_limit(env: &Env, limit: SpendingLimit, amount: i128, now: u64) -> LimitUsage {
+        let limit = Self::roll_period(env, limit, now);
+        let daily_key = Self::daily_key(&limit, now / SECONDS_PER_DAY);
+        let period_key = Self::period_key(&limit, limit.period_start);
+
+        let current_daily: i128 = env.storage().persistent().get(&daily_key).unwrap_or(0);
+        let current_period: i128 = env.storage().persistent().get(&period_key).unwrap_or(0);
+
+        let new_daily = current_daily
+            .checked_add(amount)
+            .unwrap_or_else(|| panic_with_error!(env, SpendingLimitError::InvalidAmount));
+        let new_period = current_period
+            .checked_add(amount)
+            .unwrap_or_else(|| panic_with_error!(env, SpendingLimitError::InvalidAmount));
+
+        // Carry-over from the previous period extends this period's limit.
+        let period_limit = limit.period_limit + limit.carry_over;
+
+        // A daily cap of 0 means only the period limit applies.
+        let daily_cap = if limit.daily_cap > 0 {
+            limit.daily_cap
+        } else {
+            period_limit
+        };
+
+        let daily_ok = limit.daily_cap == 0 || new_daily <= daily_cap;
+        let period_ok = new_period <= period_limit;
+
+        // Portion of this spend above whichever limit it crosses furthest.
+        let mut overrun = (new_period - current_period.max(period_limit)).max(0);
+        if limit.daily_cap > 0 {
+            overrun = overrun.max((new_daily - current_daily.max(daily_cap)).max(0));
+        }
+
+        if (!daily_ok || !period_ok) && !limit.soft_limit {
+            let remaining_daily = (daily_cap - current_daily).max(0);
+            let remaining_period = (period_limit - current_period).max(0);
+
+            LimitEvents::limit_exceeded(
+                env,
+                &limit.user,
+                &limit.category,
+                amount,
+                remaining_daily,
+                remaining_period,
+            );
+
+            if !daily_ok {
+                panic_with_error!(env, SpendingLimitError::DailyLimitExceeded);
+            } else {
+                panic_with_error!(env, SpendingLimitError::PeriodLimitExceeded);
+            }
+        }
+
+        LimitUsage {
+            limit,
+            daily_key,
+            period_key,
+            current_period,
+            new_daily,
+            new_period,
+            overrun,
+        }
+    }
+
+    // Internal helper persisting the totals computed by `check_limit`
+    fn record_usage(env: &Env, usage: LimitUsage) {
+        env.storage()
+            .persistent()
+            .set(&usage.daily_key, &usage.new_daily);
+        env.storage()
+            .persistent()
+            .set(&usage.period_key, &usage.new_period);
+
+        // Keep the embedded "current_spending" in sync with the current period.
+        let mut limit = usage.limit;
+        limit.current_spending = usage.new_period;
+        limit.current_overrun += usage.overrun;
+        env.storage()
+            .persistent()
+            .set(&Self::limit_key(&limit.user, &limit.category), &limit);
+        env.storage().persistent().set(
+            &Self::carry_over_key(&limit, limit.period_start),
+            &limit.carry_over,
+        );
+
+        if usage.overrun > 0 {
+            LimitEvents::limit_overrun(
+                env,
+                &limit,
+                usage.new_period - usage.current_period,
+                usage.overrun,
+            );
+        }
+
+        // Totals only grow within a period, so each threshold is crossed once.
+        let period_limit = limit.period_limit + limit.carry_over;
+        for threshold in limit.warning_thresholds.iter() {
+            let mark = period_limit * threshold as i128;
+            if usage.current_period * 100 < mark && usage.new_period * 100 >= mark {
+                LimitEvents::threshold_reached(env, &limit, threshold, usage.new_period);
+            }
+        }
+    }
+
     // Internal helper to verify admin
     fn require_admin(env: &Env, caller: &Address) {
         let admin: Address = env
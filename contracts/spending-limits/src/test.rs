@@ -2,10 +2,14 @@
 
 #![cfg(test)]
 
-use crate::{SpendingLimitsContract, SpendingLimitsContractClient};
-use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, Vec};
+use crate::{SpendingLimitError, SpendingLimitsContract, SpendingLimitsContractClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    Address, Env, IntoVal, Symbol, Vec,
+};
 
-use crate::types::{ErrorCode, LimitUpdateResult, SpendingLimitRequest};
+use crate::types::{ErrorCode, LimitPeriod, LimitUpdateResult, SpendingLimitRequest};
 
 /// Helper function to create a test environment with initialized contract.
 fn setup_test_contract() -> (Env, Address, SpendingLimitsContractClient<'static>) {
@@ -25,11 +29,33 @@ fn setup_test_contract() -> (Env, Address, SpendingLimitsContractClient<'static>
 fn create_valid_request(env: &Env, user: &Address, limit: i128) -> SpendingLimitRequest {
     SpendingLimitRequest {
         user: user.clone(),
-        monthly_limit: limit,
-        category: Some(symbol_short!("general")),
+        period_limit: limit,
+        category: None,
+        period: LimitPeriod::CalendarMonth(1),
+        daily_cap: 0,
+        warning_thresholds: Vec::new(env),
+        soft_limit: false,
+        max_carry_over: 0,
+        carry_decay_pct: 0,
     }
 }
 
+/// Helper function to configure a limit with a specific period and daily cap.
+fn set_period_limit(
+    env: &Env,
+    admin: &Address,
+    client: &SpendingLimitsContractClient<'static>,
+    user: &Address,
+    period_limit: i128,
+    period: LimitPeriod,
+    daily_cap: i128,
+) {
+    let mut request = create_valid_request(env, user, period_limit);
+    request.period = period;
+    request.daily_cap = daily_cap;
+    client.batch_update_spending_limits(admin, &Vec::from_array(env, [request]));
+}
+
 #[test]
 fn test_initialize() {
     let (_, admin, client) = setup_test_contract();
@@ -93,7 +119,7 @@ fn test_batch_update_spending_limits_multiple_users() {
     for limit_result in result.results.iter() {
         match limit_result {
             LimitUpdateResult::Success(limit) => {
-                assert!(limit.monthly_limit > 0);
+                assert!(limit.period_limit > 0);
                 assert_eq!(limit.current_spending, 0);
                 assert_eq!(limit.is_active, true);
             }
@@ -119,7 +145,7 @@ fn test_batch_update_with_invalid_requests() {
 
     // Invalid request - limit too low
     let mut invalid_request = create_valid_request(&env, &user2, 100);
-    invalid_request.monthly_limit = 100; // Below minimum
+    invalid_request.period_limit = 100; // Below minimum
     requests.push_back(invalid_request);
 
     let result = client.batch_update_spending_limits(&admin, &requests);
@@ -149,7 +175,7 @@ fn test_batch_update_invalid_limit_negative() {
 
     let mut requests: Vec<SpendingLimitRequest> = Vec::new(&env);
     let mut request = create_valid_request(&env, &user, 50_000_000_000);
-    request.monthly_limit = -1000; // Negative limit
+    request.period_limit = -1000; // Negative limit
     requests.push_back(request);
 
     let result = client.batch_update_spending_limits(&admin, &requests);
@@ -172,7 +198,7 @@ fn test_batch_update_invalid_limit_too_high() {
 
     let mut requests: Vec<SpendingLimitRequest> = Vec::new(&env);
     let mut request = create_valid_request(&env, &user, 50_000_000_000);
-    request.monthly_limit = 100_000_000_000_000_001; // Above maximum
+    request.period_limit = 100_000_000_000_000_001; // Above maximum
     requests.push_back(request);
 
     let result = client.batch_update_spending_limits(&admin, &requests);
@@ -229,7 +255,7 @@ fn test_get_spending_limit() {
     let limit = client.get_spending_limit(&user).unwrap();
 
     assert_eq!(limit.user, user);
-    assert_eq!(limit.monthly_limit, 50_000_000_000);
+    assert_eq!(limit.period_limit, 50_000_000_000);
     assert_eq!(limit.current_spending, 0);
     assert_eq!(limit.is_active, true);
 }
@@ -318,7 +344,7 @@ fn test_mixed_valid_and_invalid_requests() {
 
     // Invalid - limit too low
     let mut invalid1 = create_valid_request(&env, &user2, 100);
-    invalid1.monthly_limit = 100;
+    invalid1.period_limit = 100;
     requests.push_back(invalid1);
 
     // Valid
@@ -326,7 +352,7 @@ fn test_mixed_valid_and_invalid_requests() {
 
     // Invalid - negative limit
     let mut invalid2 = create_valid_request(&env, &user4, -1000);
-    invalid2.monthly_limit = -1000;
+    invalid2.period_limit = -1000;
     requests.push_back(invalid2);
 
     let result = client.batch_update_spending_limits(&admin, &requests);
@@ -350,7 +376,7 @@ fn test_update_existing_limit() {
     client.batch_update_spending_limits(&admin, &requests1);
 
     let limit1 = client.get_spending_limit(&user).unwrap();
-    assert_eq!(limit1.monthly_limit, 50_000_000_000);
+    assert_eq!(limit1.period_limit, 50_000_000_000);
 
     // Update the limit
     let mut requests2: Vec<SpendingLimitRequest> = Vec::new(&env);
@@ -358,7 +384,7 @@ fn test_update_existing_limit() {
     client.batch_update_spending_limits(&admin, &requests2);
 
     let limit2 = client.get_spending_limit(&user).unwrap();
-    assert_eq!(limit2.monthly_limit, 100_000_000_000);
+    assert_eq!(limit2.period_limit, 100_000_000_000);
     assert_eq!(limit2.current_spending, 0); // Reset on update
 }
 
@@ -413,64 +439,188 @@ fn test_maximum_valid_limit() {
     assert_eq!(result.failed, 0);
 }
 
+/// 2024-01-15 00:00:00 UTC, a Monday.
+const JAN_15_2024: u64 = 1_705_276_800;
+const DAY: u64 = 86_400;
+const LIMIT: i128 = 30_000_000;
+
 #[test]
-fn test_enforce_spending_limit_allows_within_daily_and_monthly() {
+fn test_enforce_spending_limit_allows_within_daily_and_period() {
     let (env, admin, client) = setup_test_contract();
     let user = Address::generate(&env);
+    env.ledger().set_timestamp(JAN_15_2024);
+    set_period_limit(
+        &env,
+        &admin,
+        &client,
+        &user,
+        LIMIT,
+        LimitPeriod::CalendarMonth(1),
+        10_000_000,
+    );
 
-    // Configure a monthly limit of 300 units; derived daily limit is 10 units.
-    let mut requests: Vec<SpendingLimitRequest> = Vec::new(&env);
-    requests.push_back(create_valid_request(&env, &user, 300));
-    client.batch_update_spending_limits(&admin, &requests);
-
-    // Same timestamp (same logical day/month).
-    env.ledger().set_timestamp(86_400); // day 1
+    client.enforce_spending_limit(&user, &None, &5_000_000);
+    client.enforce_spending_limit(&user, &None, &5_000_000);
 
-    // Two spends of 5 each are within daily (10) and monthly (300) limits.
-    client.enforce_spending_limit(&user, &5);
-    client.enforce_spending_limit(&user, &5);
+    let limit = client.get_spending_limit(&user).unwrap();
+    assert_eq!(limit.current_spending, 10_000_000);
+    // Period started on 2024-01-01
+    assert_eq!(limit.period_start, JAN_15_2024 - 14 * DAY);
 }
 
 #[test]
-#[should_panic]
+#[should_panic(expected = "Error(Contract, #6)")]
 fn test_enforce_spending_limit_daily_exceeded() {
     let (env, admin, client) = setup_test_contract();
     let user = Address::generate(&env);
+    env.ledger().set_timestamp(JAN_15_2024);
+    set_period_limit(
+        &env,
+        &admin,
+        &client,
+        &user,
+        LIMIT,
+        LimitPeriod::CalendarMonth(1),
+        10_000_000,
+    );
+
+    client.enforce_spending_limit(&user, &None, &5_000_000);
+    client.enforce_spending_limit(&user, &None, &5_000_000);
+    client.enforce_spending_limit(&user, &None, &1);
+}
 
-    // Monthly 300 -> daily 10
-    let mut requests: Vec<SpendingLimitRequest> = Vec::new(&env);
-    requests.push_back(create_valid_request(&env, &user, 300));
-    client.batch_update_spending_limits(&admin, &requests);
+#[test]
+fn test_daily_cap_resets_at_utc_midnight() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    env.ledger().set_timestamp(JAN_15_2024 + DAY - 1);
+    set_period_limit(
+        &env,
+        &admin,
+        &client,
+        &user,
+        LIMIT,
+        LimitPeriod::Weekly(0),
+        10_000_000,
+    );
+
+    client.enforce_spending_limit(&user, &None, &10_000_000);
+    env.ledger().set_timestamp(JAN_15_2024 + DAY);
+    client.enforce_spending_limit(&user, &None, &10_000_000);
+}
 
-    env.ledger().set_timestamp(2 * 86_400); // day 2
+#[test]
+#[should_panic(expected = "Error(Contract, #7)")]
+fn test_enforce_spending_limit_period_exceeded_over_multiple_days() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    env.ledger().set_timestamp(JAN_15_2024);
+    set_period_limit(
+        &env,
+        &admin,
+        &client,
+        &user,
+        LIMIT,
+        LimitPeriod::CalendarMonth(1),
+        1_000_000,
+    );
 
-    // 2 * 5 is allowed; the third spend pushes daily total above 10 and should panic.
-    client.enforce_spending_limit(&user, &5);
-    client.enforce_spending_limit(&user, &5);
-    client.enforce_spending_limit(&user, &1);
+    // Spend the daily cap on each of 30 days within January.
+    for d in 0..30u64 {
+        env.ledger().set_timestamp(JAN_15_2024 - 14 * DAY + d * DAY);
+        client.enforce_spending_limit(&user, &None, &1_000_000);
+    }
+
+    // January 31st is still in the same calendar month.
+    env.ledger().set_timestamp(JAN_15_2024 + 16 * DAY);
+    client.enforce_spending_limit(&user, &None, &1_000_000);
 }
 
 #[test]
-#[should_panic]
-fn test_enforce_spending_limit_monthly_exceeded_over_multiple_days() {
+fn test_calendar_month_resets_on_start_day() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    env.ledger().set_timestamp(JAN_15_2024);
+    // Payday on the 25th
+    set_period_limit(
+        &env,
+        &admin,
+        &client,
+        &user,
+        LIMIT,
+        LimitPeriod::CalendarMonth(25),
+        0,
+    );
+
+    client.enforce_spending_limit(&user, &None, &LIMIT);
+
+    // Still the period that began on 2023-12-25
+    env.ledger().set_timestamp(JAN_15_2024 + 9 * DAY + DAY - 1);
+    let result = client.try_enforce_spending_limit(&user, &None, &1);
+    assert!(result.is_err());
+
+    // 2024-01-25 starts a new period
+    env.ledger().set_timestamp(JAN_15_2024 + 10 * DAY);
+    client.enforce_spending_limit(&user, &None, &LIMIT);
+    assert_eq!(
+        client.get_spending_limit(&user).unwrap().period_start,
+        JAN_15_2024 + 10 * DAY
+    );
+}
+
+#[test]
+fn test_weekly_period_resets_on_start_weekday() {
     let (env, admin, client) = setup_test_contract();
     let user = Address::generate(&env);
+    // Friday 2024-01-19; weeks start on Saturday
+    env.ledger().set_timestamp(JAN_15_2024 + 4 * DAY);
+    set_period_limit(
+        &env,
+        &admin,
+        &client,
+        &user,
+        LIMIT,
+        LimitPeriod::Weekly(5),
+        0,
+    );
+
+    client.enforce_spending_limit(&user, &None, &LIMIT);
+    env.ledger().set_timestamp(JAN_15_2024 + 5 * DAY);
+    client.enforce_spending_limit(&user, &None, &LIMIT);
+}
 
-    // Monthly 30, daily 1 (30 / 30) => 1 unit per day max, 30 units per month.
-    let mut requests: Vec<SpendingLimitRequest> = Vec::new(&env);
-    requests.push_back(create_valid_request(&env, &user, 30));
-    client.batch_update_spending_limits(&admin, &requests);
+#[test]
+#[should_panic(expected = "Error(Contract, #7)")]
+fn test_custom_period_enforced() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    env.ledger().set_timestamp(JAN_15_2024);
+    set_period_limit(
+        &env,
+        &admin,
+        &client,
+        &user,
+        LIMIT,
+        LimitPeriod::Custom(3 * DAY),
+        0,
+    );
+
+    client.enforce_spending_limit(&user, &None, &LIMIT);
+    env.ledger().set_timestamp(JAN_15_2024 + DAY);
+    client.enforce_spending_limit(&user, &None, &1);
+}
 
-    // Spend 1 unit on 30 different "days" within the same logical month window.
-    for d in 0..30u64 {
-        env.ledger().set_timestamp(d * 86_400);
-        client.enforce_spending_limit(&user, &1);
-    }
+#[test]
+fn test_invalid_period_rejected_in_batch() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
 
-    // Next day is still within the same 30-day "month" bucket and should exceed the
-    // monthly limit, even though the daily limit would allow it.
-    env.ledger().set_timestamp(30 * 86_400);
-    client.enforce_spending_limit(&user, &1);
+    let mut request = create_valid_request(&env, &user, LIMIT);
+    request.period = LimitPeriod::Weekly(7);
+    let result = client.batch_update_spending_limits(&admin, &Vec::from_array(&env, [request]));
+
+    assert_eq!(result.failed, 1);
+    assert!(client.get_spending_limit(&user).is_none());
 }
 
 #[test]
@@ -478,8 +628,233 @@ fn test_enforce_without_limit_does_not_block() {
     let (env, _admin, client) = setup_test_contract();
     let user = Address::generate(&env);
 
-    env.ledger().set_timestamp(10 * 86_400);
+    env.ledger().set_timestamp(10 * DAY);
 
     // No limit configured for this user; enforce should be a no-op and not panic.
-    client.enforce_spending_limit(&user, &1_000_000);
+    client.enforce_spending_limit(&user, &None, &1_000_000);
+}
+
+#[test]
+fn test_category_limits_upsert_and_list() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut food = create_valid_request(&env, &user, LIMIT);
+    food.category = Some(symbol_short!("food"));
+    let mut fun = create_valid_request(&env, &user, 2 * LIMIT);
+    fun.category = Some(symbol_short!("fun"));
+    let overall = create_valid_request(&env, &user, 5 * LIMIT);
+    let result = client
+        .batch_update_spending_limits(&admin, &Vec::from_array(&env, [food.clone(), fun, overall]));
+    assert_eq!(result.successful, 3);
+
+    // Updating a category replaces it rather than adding another
+    food.period_limit = 3 * LIMIT;
+    client.batch_update_spending_limits(&admin, &Vec::from_array(&env, [food]));
+
+    let limits = client.get_spending_limits(&user);
+    assert_eq!(limits.len(), 3);
+    assert!(limits.get(0).unwrap().category.is_none());
+    assert_eq!(
+        client
+            .get_category_limit(&user, &symbol_short!("food"))
+            .unwrap()
+            .period_limit,
+        3 * LIMIT
+    );
+    assert_eq!(
+        client.get_spending_limit(&user).unwrap().period_limit,
+        5 * LIMIT
+    );
+}
+
+#[test]
+fn test_category_spend_counts_toward_both_limits() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let food = Some(symbol_short!("food"));
+
+    let mut category = create_valid_request(&env, &user, LIMIT);
+    category.category = food.clone();
+    let overall = create_valid_request(&env, &user, 2 * LIMIT);
+    client.batch_update_spending_limits(&admin, &Vec::from_array(&env, [category, overall]));
+
+    client.enforce_spending_limit(&user, &food, &LIMIT);
+
+    // Category limit is exhausted, the overall limit is not
+    let result = client.try_enforce_spending_limit(&user, &food, &1);
+    assert_eq!(
+        result,
+        Err(Ok(SpendingLimitError::PeriodLimitExceeded.into()))
+    );
+    client.enforce_spending_limit(&user, &Some(symbol_short!("fun")), &LIMIT);
+
+    // Now the overall limit is exhausted for every category
+    let result = client.try_enforce_spending_limit(&user, &None, &1);
+    assert!(result.is_err());
+    assert_eq!(
+        client.get_spending_limit(&user).unwrap().current_spending,
+        2 * LIMIT
+    );
+    assert_eq!(
+        client
+            .get_category_limit(&user, &symbol_short!("food"))
+            .unwrap()
+            .current_spending,
+        LIMIT
+    );
+}
+
+#[test]
+fn test_failed_overall_check_does_not_record_category_spend() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    let food = Some(symbol_short!("food"));
+
+    let mut category = create_valid_request(&env, &user, 2 * LIMIT);
+    category.category = food.clone();
+    let overall = create_valid_request(&env, &user, LIMIT);
+    client.batch_update_spending_limits(&admin, &Vec::from_array(&env, [category, overall]));
+
+    assert!(client
+        .try_enforce_spending_limit(&user, &food, &(LIMIT + 1))
+        .is_err());
+    assert_eq!(
+        client
+            .get_category_limit(&user, &symbol_short!("food"))
+            .unwrap()
+            .current_spending,
+        0
+    );
+}
+
+/// Returns the thresholds reported by `threshold_reached` events so far.
+fn thresholds_reached(env: &Env) -> Vec<u32> {
+    let threshold_topic = Symbol::new(env, "threshold");
+    let mut reached = Vec::new(env);
+    for (_, topics, _) in env.events().all().iter() {
+        if topics.len() != 3 {
+            continue;
+        }
+        let topic: Symbol = topics.get(1).unwrap().into_val(env);
+        if topic == threshold_topic {
+            reached.push_back(topics.get(2).unwrap().into_val(env));
+        }
+    }
+    reached
+}
+
+#[test]
+fn test_warning_thresholds_emit_once_per_period() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    env.ledger().set_timestamp(JAN_15_2024);
+
+    let mut request = create_valid_request(&env, &user, LIMIT);
+    request.warning_thresholds = Vec::from_array(&env, [50, 80, 100]);
+    client.batch_update_spending_limits(&admin, &Vec::from_array(&env, [request]));
+
+    // Crosses 50% and 80% in one spend
+    client.enforce_spending_limit(&user, &None, &25_000_000);
+    assert_eq!(thresholds_reached(&env), Vec::from_array(&env, [50, 80]));
+
+    // Staying between thresholds emits nothing
+    client.enforce_spending_limit(&user, &None, &1_000_000);
+    assert_eq!(thresholds_reached(&env).len(), 0);
+
+    client.enforce_spending_limit(&user, &None, &4_000_000);
+    assert_eq!(thresholds_reached(&env), Vec::from_array(&env, [100]));
+
+    // A new month starts the thresholds over
+    env.ledger().set_timestamp(JAN_15_2024 + 17 * DAY);
+    client.enforce_spending_limit(&user, &None, &15_000_000);
+    assert_eq!(thresholds_reached(&env), Vec::from_array(&env, [50]));
+}
+
+#[test]
+fn test_soft_limit_records_overrun() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    env.ledger().set_timestamp(JAN_15_2024);
+
+    let mut request = create_valid_request(&env, &user, LIMIT);
+    request.soft_limit = true;
+    client.batch_update_spending_limits(&admin, &Vec::from_array(&env, [request]));
+
+    client.enforce_spending_limit(&user, &None, &(LIMIT - 5_000_000));
+    client.enforce_spending_limit(&user, &None, &10_000_000);
+    client.enforce_spending_limit(&user, &None, &2_000_000);
+
+    let limit = client.get_spending_limit(&user).unwrap();
+    assert_eq!(limit.current_spending, LIMIT + 7_000_000);
+    assert_eq!(limit.current_overrun, 7_000_000);
+
+    // Overruns reset with the period
+    env.ledger().set_timestamp(JAN_15_2024 + 17 * DAY);
+    client.enforce_spending_limit(&user, &None, &1_000_000);
+    assert_eq!(client.get_spending_limit(&user).unwrap().current_overrun, 0);
+}
+
+#[test]
+fn test_invalid_thresholds_rejected_in_batch() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+
+    let mut request = create_valid_request(&env, &user, LIMIT);
+    request.warning_thresholds = Vec::from_array(&env, [80, 50]);
+    let result = client.batch_update_spending_limits(&admin, &Vec::from_array(&env, [request]));
+
+    assert_eq!(result.failed, 1);
+    match result.results.get(0).unwrap() {
+        LimitUpdateResult::Failure(_, code) => assert_eq!(code, ErrorCode::INVALID_THRESHOLDS),
+        LimitUpdateResult::Success(_) => panic!("Expected failure"),
+    }
+}
+
+#[test]
+fn test_unused_limit_rolls_over_with_cap_and_decay() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    env.ledger().set_timestamp(JAN_15_2024);
+
+    let mut request = create_valid_request(&env, &user, LIMIT);
+    request.period = LimitPeriod::Weekly(0);
+    request.max_carry_over = 15_000_000;
+    request.carry_decay_pct = 50;
+    client.batch_update_spending_limits(&admin, &Vec::from_array(&env, [request]));
+
+    // Week 1: spend 10 of 30, leaving 20 unused; half decays to 10
+    client.enforce_spending_limit(&user, &None, &10_000_000);
+    env.ledger().set_timestamp(JAN_15_2024 + 7 * DAY);
+    let limit = client.get_spending_limit(&user).unwrap();
+    assert_eq!(limit.carry_over, 10_000_000);
+    assert_eq!(limit.current_spending, 0);
+
+    // Week 2 allows 40 in total
+    client.enforce_spending_limit(&user, &None, &40_000_000);
+    assert!(client.try_enforce_spending_limit(&user, &None, &1).is_err());
+
+    // Week 3: nothing unused carries nothing
+    env.ledger().set_timestamp(JAN_15_2024 + 14 * DAY);
+    assert_eq!(client.get_spending_limit(&user).unwrap().carry_over, 0);
+
+    // Week 5 after an idle week 4: full base limit decays to 15, at the cap
+    env.ledger().set_timestamp(JAN_15_2024 + 28 * DAY);
+    client.enforce_spending_limit(&user, &None, &45_000_000);
+    assert_eq!(
+        client.get_spending_limit(&user).unwrap().carry_over,
+        15_000_000
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #7)")]
+fn test_rollover_disabled_by_default() {
+    let (env, admin, client) = setup_test_contract();
+    let user = Address::generate(&env);
+    env.ledger().set_timestamp(JAN_15_2024);
+    set_period_limit(&env, &admin, &client, &user, LIMIT, LimitPeriod::Daily, 0);
+
+    env.ledger().set_timestamp(JAN_15_2024 + DAY);
+    client.enforce_spending_limit(&user, &None, &(LIMIT + 1));
 }
@@ -1,26 +1,62 @@
 //! Data types and events for batch spending limit operations.
 
-use soroban_sdk::{contracttype, symbol_short, Address, Env, Vec};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
 
 /// Maximum number of user-limit pairs in a single batch for optimization.
 pub const MAX_BATCH_SIZE: u32 = 100;
 
-/// Minimum monthly spending limit (0.1 XLM in stroops)
+/// Maximum number of category limits per user.
+pub const MAX_CATEGORIES_PER_USER: u32 = 20;
+
+/// Maximum number of warning thresholds per limit.
+pub const MAX_WARNING_THRESHOLDS: u32 = 5;
+
+/// Minimum period spending limit (0.1 XLM in stroops)
 pub const MIN_SPENDING_LIMIT: i128 = 1_000_000;
 
-/// Maximum monthly spending limit (10 million XLM in stroops)
+/// Maximum period spending limit (10 million XLM in stroops)
 pub const MAX_SPENDING_LIMIT: i128 = 100_000_000_000_000_000;
 
+/// Period over which a spending limit accumulates before resetting.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum LimitPeriod {
+    /// Resets at UTC midnight
+    Daily,
+    /// Resets at UTC midnight on the given weekday (0 = Monday, 6 = Sunday)
+    Weekly(u32),
+    /// Resets at UTC midnight on the given day of each month (1-31),
+    /// falling on the last day of shorter months
+    CalendarMonth(u32),
+    /// Resets every given number of seconds since the Unix epoch
+    Custom(u64),
+}
+
 /// Represents a spending limit update request for a user.
 #[derive(Clone, Debug)]
 #[contracttype]
 pub struct SpendingLimitRequest {
     /// User's address
     pub user: Address,
-    /// New monthly spending limit (in stroops)
-    pub monthly_limit: i128,
-    /// Optional category-specific limit (e.g., "food", "entertainment")
-    pub category: Option<soroban_sdk::Symbol>,
+    /// New spending limit per period (in stroops)
+    pub period_limit: i128,
+    /// Category the limit applies to (e.g., "food", "entertainment"),
+    /// or `None` for the user's overall limit
+    pub category: Option<Symbol>,
+    /// Period the limit applies to
+    pub period: LimitPeriod,
+    /// Maximum spend per UTC day (in stroops), or 0 for no daily cap
+    pub daily_cap: i128,
+    /// Ascending percentages of the period limit (1-100) that trigger a
+    /// warning event when first reached in a period
+    pub warning_thresholds: Vec<u32>,
+    /// Allow spends over the limit and record them as overruns instead of rejecting
+    pub soft_limit: bool,
+    /// Maximum unused headroom (in stroops) carried into the next period,
+    /// or 0 to disable rollover
+    pub max_carry_over: i128,
+    /// Percentage (0-100) of unused headroom lost when it rolls over
+    pub carry_decay_pct: u32,
 }
 
 /// Represents a user's spending limit configuration.
@@ -29,12 +65,30 @@ pub struct SpendingLimitRequest {
 pub struct SpendingLimit {
     /// User's address
     pub user: Address,
-    /// Monthly spending limit (in stroops)
-    pub monthly_limit: i128,
-    /// Current month's spending (in stroops)
+    /// Spending limit per period (in stroops)
+    pub period_limit: i128,
+    /// Current period's spending (in stroops)
     pub current_spending: i128,
-    /// Optional category
-    pub category: Option<soroban_sdk::Symbol>,
+    /// Category the limit applies to, or `None` for the overall limit
+    pub category: Option<Symbol>,
+    /// Period the limit applies to
+    pub period: LimitPeriod,
+    /// Maximum spend per UTC day (in stroops), or 0 for no daily cap
+    pub daily_cap: i128,
+    /// Start timestamp of the period `current_spending` belongs to
+    pub period_start: u64,
+    /// Ascending percentages of the period limit that trigger warning events
+    pub warning_thresholds: Vec<u32>,
+    /// Whether overspending is allowed and recorded instead of rejected
+    pub soft_limit: bool,
+    /// Amount spent over the period limit or daily cap in the current period
+    pub current_overrun: i128,
+    /// Maximum unused headroom carried into the next period (0 disables rollover)
+    pub max_carry_over: i128,
+    /// Percentage of unused headroom lost when it rolls over
+    pub carry_decay_pct: u32,
+    /// Headroom carried into the current period, added to `period_limit`
+    pub carry_over: i128,
     /// Last update timestamp
     pub updated_at: u64,
     /// Whether the limit is active
@@ -42,6 +96,7 @@ pub struct SpendingLimit {
 }
 
 /// Result of processing a single limit update.
+#[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug)]
 #[contracttype]
 pub enum LimitUpdateResult {
@@ -93,16 +148,28 @@ pub enum DataKey {
     Admin,
     /// Last created batch ID
     LastBatchId,
-    /// Stored spending limit by user address
+    /// Stored overall spending limit by user address
     SpendingLimit(Address),
+    /// Stored category spending limit by user address and category
+    CategoryLimit(Address, Symbol),
+    /// Categories a user has limits for
+    LimitCategories(Address),
     /// Total limits updated lifetime
     TotalLimitsUpdated,
     /// Total batches processed lifetime
     TotalBatchesProcessed,
-    /// Per-user daily spending for a given logical day identifier.
+    /// Per-user daily spending for a given UTC day (days since the epoch).
     DailySpending(Address, u64),
-    /// Per-user monthly spending for a given logical month identifier.
-    MonthlySpending(Address, u64),
+    /// Per-user spending for the period starting at the given timestamp.
+    PeriodSpending(Address, u64),
+    /// Per-user, per-category daily spending for a given UTC day.
+    CategoryDailySpending(Address, Symbol, u64),
+    /// Per-user, per-category spending for the period starting at the given timestamp.
+    CategoryPeriodSpending(Address, Symbol, u64),
+    /// Per-user carry-over applied to the period starting at the given timestamp.
+    CarryOver(Address, u64),
+    /// Per-user, per-category carry-over applied to the period starting at the given timestamp.
+    CategoryCarryOver(Address, Symbol, u64),
 }
 
 /// Error codes for spending limit validation and updates.
@@ -115,6 +182,16 @@ pub mod ErrorCode {
     pub const INVALID_CATEGORY: u32 = 2;
     /// Limit already exists and cannot be overwritten
     pub const LIMIT_ALREADY_EXISTS: u32 = 3;
+    /// Period weekday, month day or length is out of range
+    pub const INVALID_PERIOD: u32 = 4;
+    /// Daily cap is negative or above the period limit
+    pub const INVALID_DAILY_CAP: u32 = 5;
+    /// User already has the maximum number of category limits
+    pub const TOO_MANY_CATEGORIES: u32 = 6;
+    /// Warning thresholds are out of range, unordered or too many
+    pub const INVALID_THRESHOLDS: u32 = 7;
+    /// Carry-over cap is out of range or decay is above 100%
+    pub const INVALID_ROLLOVER: u32 = 8;
 }
 
 /// Events emitted by the spending limits contract.
@@ -130,8 +207,14 @@ impl LimitEvents {
     /// Event emitted when a limit is successfully updated.
     pub fn limit_updated(env: &Env, batch_id: u64, limit: &SpendingLimit) {
         let topics = (symbol_short!("limit"), symbol_short!("updated"), batch_id);
-        env.events()
-            .publish(topics, (limit.user.clone(), limit.monthly_limit));
+        env.events().publish(
+            topics,
+            (
+                limit.user.clone(),
+                limit.category.clone(),
+                limit.period_limit,
+            ),
+        );
     }
 
     /// Event emitted when limit update fails.
@@ -159,22 +242,62 @@ impl LimitEvents {
         env.events().publish(topics, (user.clone(), amount));
     }
 
-    /// Event emitted when a spend attempt exceeds either the daily or monthly limit.
+    /// Event emitted the first time spending in a period reaches a warning threshold.
+    pub fn threshold_reached(
+        env: &Env,
+        limit: &SpendingLimit,
+        threshold: u32,
+        period_spending: i128,
+    ) {
+        let topics = (
+            symbol_short!("limit"),
+            symbol_short!("threshold"),
+            threshold,
+        );
+        env.events().publish(
+            topics,
+            (
+                limit.user.clone(),
+                limit.category.clone(),
+                period_spending,
+                limit.period_limit,
+            ),
+        );
+    }
+
+    /// Event emitted when a soft limit allows a spend over its limit.
+    pub fn limit_overrun(env: &Env, limit: &SpendingLimit, amount: i128, overrun: i128) {
+        let topics = (symbol_short!("limit"), symbol_short!("overrun"));
+        env.events().publish(
+            topics,
+            (
+                limit.user.clone(),
+                limit.category.clone(),
+                amount,
+                overrun,
+                limit.current_overrun,
+            ),
+        );
+    }
+
+    /// Event emitted when a spend attempt exceeds either the daily cap or the period limit.
     pub fn limit_exceeded(
         env: &Env,
         user: &Address,
+        category: &Option<Symbol>,
         attempted_amount: i128,
         remaining_daily: i128,
-        remaining_monthly: i128,
+        remaining_period: i128,
     ) {
         let topics = (symbol_short!("limit"), symbol_short!("exceeded"));
         env.events().publish(
             topics,
             (
                 user.clone(),
+                category.clone(),
                 attempted_amount,
                 remaining_daily,
-                remaining_monthly,
+                remaining_period,
             ),
         );
     }
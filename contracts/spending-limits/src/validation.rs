@@ -1,8 +1,11 @@
 //! Validation logic for spending limit update requests.
 
-use soroban_sdk::Address;
+use soroban_sdk::{Address, Vec};
 
-use crate::types::{ErrorCode, SpendingLimitRequest, MAX_SPENDING_LIMIT, MIN_SPENDING_LIMIT};
+use crate::types::{
+    ErrorCode, LimitPeriod, SpendingLimitRequest, MAX_SPENDING_LIMIT, MAX_WARNING_THRESHOLDS,
+    MIN_SPENDING_LIMIT,
+};
 
 /// Validates a spending limit update request.
 ///
@@ -15,11 +18,34 @@ pub fn validate_limit_request(request: &SpendingLimitRequest) -> Result<(), u32>
         return Err(ErrorCode::INVALID_USER_ADDRESS);
     }
 
-    // Validate monthly limit amount
-    if !is_valid_limit(request.monthly_limit) {
+    // Validate period limit amount
+    if !is_valid_limit(request.period_limit) {
         return Err(ErrorCode::INVALID_LIMIT);
     }
 
+    // Validate period configuration
+    if !is_valid_period(&request.period) {
+        return Err(ErrorCode::INVALID_PERIOD);
+    }
+
+    // Validate daily cap (0 disables it)
+    if request.daily_cap < 0 || request.daily_cap > request.period_limit {
+        return Err(ErrorCode::INVALID_DAILY_CAP);
+    }
+
+    // Validate warning thresholds
+    if !is_valid_thresholds(&request.warning_thresholds) {
+        return Err(ErrorCode::INVALID_THRESHOLDS);
+    }
+
+    // Validate rollover settings (a cap of 0 disables rollover)
+    if request.max_carry_over < 0
+        || request.max_carry_over > MAX_SPENDING_LIMIT
+        || request.carry_decay_pct > 100
+    {
+        return Err(ErrorCode::INVALID_ROLLOVER);
+    }
+
     // Validate category if provided
     // In Soroban, symbols are always valid by construction
     // This check exists for consistency with validation patterns
@@ -48,6 +74,39 @@ pub fn is_valid_limit(limit: i128) -> bool {
     limit >= MIN_SPENDING_LIMIT && limit <= MAX_SPENDING_LIMIT
 }
 
+/// Validates that a limit period is well formed.
+///
+/// # Returns
+/// * `true` if the weekday is 0-6, the day of month is 1-31 and a custom
+///   period is at least one second long
+pub fn is_valid_period(period: &LimitPeriod) -> bool {
+    match period {
+        LimitPeriod::Daily => true,
+        LimitPeriod::Weekly(start_weekday) => *start_weekday < 7,
+        LimitPeriod::CalendarMonth(start_day) => (1..=31).contains(start_day),
+        LimitPeriod::Custom(seconds) => *seconds > 0,
+    }
+}
+
+/// Validates a limit's warning thresholds.
+///
+/// # Returns
+/// * `true` if there are at most `MAX_WARNING_THRESHOLDS` percentages, each
+///   between 1 and 100, in strictly ascending order
+pub fn is_valid_thresholds(thresholds: &Vec<u32>) -> bool {
+    if thresholds.len() > MAX_WARNING_THRESHOLDS {
+        return false;
+    }
+    let mut previous = 0;
+    for threshold in thresholds.iter() {
+        if threshold <= previous || threshold > 100 {
+            return false;
+        }
+        previous = threshold;
+    }
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -56,8 +115,14 @@ mod tests {
     fn create_valid_request(env: &Env) -> SpendingLimitRequest {
         SpendingLimitRequest {
             user: Address::generate(env),
-            monthly_limit: 100_000_000_000, // 10,000 XLM
+            period_limit: 100_000_000_000, // 10,000 XLM
             category: Some(symbol_short!("general")),
+            period: LimitPeriod::CalendarMonth(1),
+            daily_cap: 0,
+            warning_thresholds: Vec::from_array(env, [50, 80, 100]),
+            soft_limit: false,
+            max_carry_over: 0,
+            carry_decay_pct: 0,
         }
     }
 
@@ -72,7 +137,7 @@ mod tests {
     fn test_invalid_limit_too_low() {
         let env = Env::default();
         let mut request = create_valid_request(&env);
-        request.monthly_limit = 100; // Below minimum
+        request.period_limit = 100; // Below minimum
         assert_eq!(
             validate_limit_request(&request),
             Err(ErrorCode::INVALID_LIMIT)
@@ -83,7 +148,7 @@ mod tests {
     fn test_invalid_limit_negative() {
         let env = Env::default();
         let mut request = create_valid_request(&env);
-        request.monthly_limit = -1000;
+        request.period_limit = -1000;
         assert_eq!(
             validate_limit_request(&request),
             Err(ErrorCode::INVALID_LIMIT)
@@ -94,7 +159,7 @@ mod tests {
     fn test_invalid_limit_too_high() {
         let env = Env::default();
         let mut request = create_valid_request(&env);
-        request.monthly_limit = MAX_SPENDING_LIMIT + 1;
+        request.period_limit = MAX_SPENDING_LIMIT + 1;
         assert_eq!(
             validate_limit_request(&request),
             Err(ErrorCode::INVALID_LIMIT)
@@ -111,6 +176,61 @@ mod tests {
         assert!(!is_valid_limit(-1000));
     }
 
+    #[test]
+    fn test_is_valid_period() {
+        assert!(is_valid_period(&LimitPeriod::Daily));
+        assert!(is_valid_period(&LimitPeriod::Weekly(6)));
+        assert!(!is_valid_period(&LimitPeriod::Weekly(7)));
+        assert!(is_valid_period(&LimitPeriod::CalendarMonth(31)));
+        assert!(!is_valid_period(&LimitPeriod::CalendarMonth(0)));
+        assert!(!is_valid_period(&LimitPeriod::CalendarMonth(32)));
+        assert!(!is_valid_period(&LimitPeriod::Custom(0)));
+    }
+
+    #[test]
+    fn test_is_valid_thresholds() {
+        let env = Env::default();
+        assert!(is_valid_thresholds(&Vec::new(&env)));
+        assert!(is_valid_thresholds(&Vec::from_array(&env, [50, 80, 100])));
+        assert!(!is_valid_thresholds(&Vec::from_array(&env, [80, 50])));
+        assert!(!is_valid_thresholds(&Vec::from_array(&env, [50, 50])));
+        assert!(!is_valid_thresholds(&Vec::from_array(&env, [0, 50])));
+        assert!(!is_valid_thresholds(&Vec::from_array(&env, [50, 101])));
+        assert!(!is_valid_thresholds(&Vec::from_array(
+            &env,
+            [10, 20, 30, 40, 50, 60]
+        )));
+    }
+
+    #[test]
+    fn test_invalid_rollover() {
+        let env = Env::default();
+        let mut request = create_valid_request(&env);
+        request.max_carry_over = -1;
+        assert_eq!(
+            validate_limit_request(&request),
+            Err(ErrorCode::INVALID_ROLLOVER)
+        );
+
+        request.max_carry_over = 1_000_000;
+        request.carry_decay_pct = 101;
+        assert_eq!(
+            validate_limit_request(&request),
+            Err(ErrorCode::INVALID_ROLLOVER)
+        );
+    }
+
+    #[test]
+    fn test_invalid_daily_cap_above_limit() {
+        let env = Env::default();
+        let mut request = create_valid_request(&env);
+        request.daily_cap = request.period_limit + 1;
+        assert_eq!(
+            validate_limit_request(&request),
+            Err(ErrorCode::INVALID_DAILY_CAP)
+        );
+    }
+
     #[test]
     fn test_valid_request_without_category() {
         let env = Env::default();